@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::process::ExitCode;
+use anchor_lang::AccountDeserialize;
+use casino_nchurch::certify::*;
+use casino_nchurch::state::casino::*;
+
+// Certify the RTP of every game against the casino's HouseEdgeConfig.
+// Accounts are read from raw dumps (`solana account <address> --output-file <path>`);
+// without --casino-state the initialize_casino defaults are certified, and without
+// --blackjack-table a S17 table with no surrender.
+//
+//   rtp_certify [--casino-state PATH] [--game-state PATH]... [--slots-machine PATH]...
+//               [--blackjack-table PATH] [--rounds N] [--seed N] [--tolerance-bps N]
+//
+// Exits non-zero when any exact RTP drifts outside the tolerance, or a simulation disagrees
+// with its enumeration by more than MAX_STANDARD_ERRORS.
+
+const DEFAULT_ROUNDS: u64 = 200_000;
+const MAX_STANDARD_ERRORS: f64 = 4.0;
+
+fn load<T: AccountDeserialize>(path: &str) -> Result<T, String> {
+    let data = std::fs::read(path).map_err(|error| format!("{}: {}", path, error))?;
+    T::try_deserialize(&mut data.as_slice()).map_err(|error| format!("{}: {}", path, error))
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("{} expects a number", flag))
+}
+
+fn run() -> Result<bool, String> {
+    let mut config = DEFAULT_HOUSE_EDGE_CONFIG;
+    let mut settle_rtp_bps = HashMap::new();
+    let mut slots_machines = Vec::new();
+    let mut blackjack_rules = BlackjackRules {
+        dealer_hits_soft_17: false,
+        surrender_allowed: false,
+        insurance_allowed: true,
+    };
+    let mut rounds = DEFAULT_ROUNDS;
+    let mut seed = 0u64;
+    let mut tolerance_bps = SLOTS_RTP_TOLERANCE_BPS;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let mut path = || args.next().ok_or_else(|| format!("{} expects a path", flag));
+        match flag.as_str() {
+            "--casino-state" => config = load::<CasinoState>(&path()?)?.house_edge_config,
+            "--game-state" => {
+                let game_state = load::<GameState>(&path()?)?;
+                settle_rtp_bps.insert(game_state.game_type as u8, game_state.rtp_bps);
+            },
+            "--slots-machine" => slots_machines.push(load::<SlotsMachineConfig>(&path()?)?),
+            "--blackjack-table" => blackjack_rules = load::<BlackjackTable>(&path()?)?.rules,
+            "--rounds" => rounds = parse_number(&flag, args.next())?,
+            "--seed" => seed = parse_number(&flag, args.next())?,
+            "--tolerance-bps" => tolerance_bps = parse_number(&flag, args.next())?,
+            _ => return Err(format!("unknown argument {}", flag)),
+        }
+    }
+
+    let inputs = CertificationInputs {
+        config: &config,
+        settle_rtp_bps,
+        slots_machines: slots_machines.iter().collect(),
+        blackjack_rules,
+        rounds,
+        seed,
+    };
+    let certificates = certify_games(&inputs).map_err(|error| error.to_string())?;
+
+    println!(
+        "{:<34} {:>10} {:>10} {:>9} {:>10} {:>10} {:>8}  result",
+        "game", "configured", "exact", "drift", "simulated", "variance", "hit %"
+    );
+    let mut all_pass = true;
+    for certificate in certificates.iter() {
+        let passes = certificate.passes(tolerance_bps, MAX_STANDARD_ERRORS);
+        all_pass &= passes;
+        println!(
+            "{:<34} {:>9.2}% {:>9.4}% {:>+9.1} {:>9.4}% {:>10.4} {:>7.2}%  {}",
+            certificate.name,
+            certificate.configured_rtp_bps as f64 / 100.0,
+            certificate.exact_rtp * 100.0,
+            certificate.drift_bps(),
+            certificate.simulated.rtp * 100.0,
+            certificate.simulated.variance,
+            certificate.simulated.hit_frequency * 100.0,
+            if passes { "ok" } else { "FAIL" }
+        );
+    }
+    if slots_machines.is_empty() {
+        println!("slots: no --slots-machine given, machines not certified");
+    }
+    println!("{} rounds per game, tolerance {} bps", rounds, tolerance_bps);
+
+    Ok(all_pass)
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("rtp_certify: {}", error);
+            ExitCode::from(2)
+        },
+    }
+}
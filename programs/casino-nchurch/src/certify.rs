@@ -0,0 +1,645 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use std::collections::HashMap;
+use crate::state::casino::*;
+use crate::utils::math::BPS_DENOMINATOR;
+use crate::utils::slots::calculate_payline_rtps;
+use crate::instructions::blackjack::{apply_player_action, deal_opening_cards, play_out_dealer, resolve_payout, CardDraws};
+use crate::utils::{calculate_blackjack_payout, calculate_crash_multiplier, calculate_hand_value, card_value, is_blackjack, is_soft_hand, should_dealer_hit};
+use crate::utils::{calculate_aviator_payout, calculate_roulette_payout};
+use crate::verify::{verify_outcome, GameInputs, GameRecord, Outcome};
+
+// RTP certification (host only, not built for SBF).
+// Every game is run through the same payout functions settlement uses. The exact RTP comes
+// from enumerating every outcome; a Monte-Carlo run through the on-chain draw path
+// (`verify_outcome` / RandomnessStream) adds variance and hit frequency and cross-checks it.
+// The `rtp_certify` binary (feature `rtp-certify`) prints the report for a deployment.
+
+const STAKE: u64 = 1_000_000;
+
+// Winning outcomes of every roulette bet type, on a fixed spot of the board
+pub const ROULETTE_CERTIFIED_BETS: [(RouletteBetType, &[u8]); 13] = [
+    (RouletteBetType::Straight, &[17]),
+    (RouletteBetType::Split, &[17, 20]),
+    (RouletteBetType::Street, &[13, 14, 15]),
+    (RouletteBetType::Corner, &[13, 14, 16, 17]),
+    (RouletteBetType::Line, &[13, 14, 15, 16, 17, 18]),
+    (RouletteBetType::Column, &[1, 4, 7, 10, 13, 16, 19, 22, 25, 28, 31, 34]),
+    (RouletteBetType::Dozen, &[13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24]),
+    (RouletteBetType::Red, &[]),
+    (RouletteBetType::Black, &[]),
+    (RouletteBetType::Even, &[]),
+    (RouletteBetType::Odd, &[]),
+    (RouletteBetType::Low, &[]),
+    (RouletteBetType::High, &[]),
+];
+
+// Cashout targets certified for aviator (1.01x to the 1000x cap)
+pub const AVIATOR_CERTIFIED_TARGETS: [u64; 6] = [101, 150, 200, 1_000, 10_000, AVIATOR_MAX_TARGET_MULTIPLIER];
+
+/// Monte-Carlo results, per unit staked
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SimulationStats {
+    pub rounds: u64,
+    pub rtp: f64,
+    pub variance: f64,
+    pub hit_frequency: f64,
+}
+
+impl SimulationStats {
+    fn from_returns(returns: impl Iterator<Item = f64>) -> Self {
+        let (mut rounds, mut sum, mut sum_squares, mut hits) = (0u64, 0f64, 0f64, 0u64);
+        for value in returns {
+            rounds += 1;
+            sum += value;
+            sum_squares += value * value;
+            if value > 0.0 {
+                hits += 1;
+            }
+        }
+        if rounds == 0 {
+            return Self::default();
+        }
+
+        let rtp = sum / rounds as f64;
+        Self {
+            rounds,
+            rtp,
+            variance: (sum_squares / rounds as f64 - rtp * rtp).max(0.0),
+            hit_frequency: hits as f64 / rounds as f64,
+        }
+    }
+
+    /// Standard error of the simulated RTP
+    pub fn standard_error(&self) -> f64 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+        (self.variance / self.rounds as f64).sqrt()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameCertificate {
+    pub name: String,
+    pub configured_rtp_bps: u16,   // HouseEdgeConfig value for the game
+    pub exact_rtp: f64,            // 0.973 = 97.3%
+    pub simulated: SimulationStats,
+}
+
+impl GameCertificate {
+    pub fn exact_rtp_bps(&self) -> f64 {
+        self.exact_rtp * BPS_DENOMINATOR as f64
+    }
+
+    /// Drift of the exact RTP from the configured one, in basis points
+    pub fn drift_bps(&self) -> f64 {
+        self.exact_rtp_bps() - self.configured_rtp_bps as f64
+    }
+
+    /// Exact RTP within `tolerance_bps` of the config, and the simulation within
+    /// `max_standard_errors` of the exact RTP (i.e. settlement draws what was enumerated)
+    pub fn passes(&self, tolerance_bps: u16, max_standard_errors: f64) -> bool {
+        let simulation_drift = (self.simulated.rtp - self.exact_rtp).abs();
+        self.drift_bps().abs() <= tolerance_bps as f64
+            && simulation_drift <= max_standard_errors * self.simulated.standard_error() + f64::EPSILON
+    }
+}
+
+// Deterministic reveal for simulated round `round`
+fn simulated_reveal(seed: u64, round: u64) -> [u8; 32] {
+    hashv(&[b"rtp-certify", &seed.to_le_bytes(), &round.to_le_bytes()]).to_bytes()
+}
+
+fn simulated_record(inputs: GameInputs, bet_amount: u64, seed: u64, round: u64) -> GameRecord {
+    GameRecord {
+        game: Pubkey::default(),
+        nonce: round,
+        bet_amount,
+        inputs,
+        randomness: simulated_reveal(seed, round),
+    }
+}
+
+fn ratio(payout: u64, stake: u64) -> f64 {
+    payout as f64 / stake as f64
+}
+
+/// Exact RTP of a spin on `active_paylines` (the mean of the played lines' exact RTPs)
+pub fn slots_exact_rtp(machine: &SlotsMachineConfig, active_paylines: u32) -> Result<f64> {
+    let payline_rtps = calculate_payline_rtps(&machine.reel_strips, &machine.paylines, &machine.paytable)?;
+    let played: Vec<u64> = payline_rtps
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| active_paylines & (1 << index) != 0)
+        .map(|(_, rtp_bps)| *rtp_bps)
+        .collect();
+    require!(!played.is_empty(), crate::errors::CasinoError::InvalidSlotsPayline);
+
+    Ok(played.iter().sum::<u64>() as f64 / (played.len() as u64 * BPS_DENOMINATOR) as f64)
+}
+
+pub fn simulate_slots(machine: &SlotsMachineConfig, active_paylines: u32, rounds: u64, seed: u64) -> Result<SimulationStats> {
+    let bet_amount = STAKE * active_paylines.count_ones() as u64;
+    let returns = (0..rounds)
+        .map(|round| {
+            let record = simulated_record(
+                GameInputs::Slots { machine: Pubkey::default(), active_paylines },
+                bet_amount,
+                seed,
+                round
+            );
+            match verify_outcome(&record, &record.randomness, Some(machine))? {
+                Outcome::Slots { payout, .. } => Ok(ratio(payout, bet_amount)),
+                _ => err!(crate::errors::CasinoError::InvalidGameState),
+            }
+        })
+        .collect::<Result<Vec<f64>>>()?;
+    Ok(SimulationStats::from_returns(returns.into_iter()))
+}
+
+/// Exact RTP of one roulette bet over all 37 pockets
+pub fn roulette_exact_rtp(bet_type: &RouletteBetType, numbers: &[u8]) -> Result<f64> {
+    let mut total = 0u64;
+    for winning_number in 0..=crate::utils::roulette::ROULETTE_MAX_NUMBER {
+        let payout = calculate_roulette_payout(bet_type.clone(), numbers, STAKE, winning_number)?;
+        total += payout;
+    }
+    Ok(ratio(total, STAKE * 37))
+}
+
+pub fn simulate_roulette(bet_type: &RouletteBetType, numbers: &[u8], rounds: u64, seed: u64) -> Result<SimulationStats> {
+    let bets = vec![RouletteBet { bet_type: bet_type.clone(), numbers: numbers.to_vec(), amount: STAKE }];
+    let returns = (0..rounds)
+        .map(|round| {
+            let record = simulated_record(GameInputs::Roulette { bets: bets.clone() }, STAKE, seed, round);
+            match verify_outcome(&record, &record.randomness, None)? {
+                Outcome::Roulette { payout, .. } => Ok(ratio(payout, STAKE)),
+                _ => err!(crate::errors::CasinoError::InvalidGameState),
+            }
+        })
+        .collect::<Result<Vec<f64>>>()?;
+    Ok(SimulationStats::from_returns(returns.into_iter()))
+}
+
+/// Exact RTP of an aviator cashout target. The crash point only grows with the 52-bit draw,
+/// so the winning draws are exactly those at or above the first one that reaches the target.
+pub fn aviator_exact_rtp(target_multiplier: u64, rtp_bps: u16) -> Result<f64> {
+    const RANGE: u64 = 1 << 52;
+    let (mut low, mut high) = (0u64, RANGE);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if calculate_crash_multiplier(middle, rtp_bps) >= target_multiplier {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    let winning_draws = RANGE - low;
+    let payout = calculate_aviator_payout(target_multiplier, target_multiplier, STAKE)?;
+    Ok(winning_draws as f64 / RANGE as f64 * ratio(payout, STAKE))
+}
+
+pub fn simulate_aviator(target_multiplier: u64, rtp_bps: u16, rounds: u64, seed: u64) -> Result<SimulationStats> {
+    let returns = (0..rounds)
+        .map(|round| {
+            let record = simulated_record(GameInputs::Aviator { target_multiplier, rtp_bps }, STAKE, seed, round);
+            match verify_outcome(&record, &record.randomness, None)? {
+                Outcome::Aviator { payout, .. } => Ok(ratio(payout, STAKE)),
+                _ => err!(crate::errors::CasinoError::InvalidGameState),
+            }
+        })
+        .collect::<Result<Vec<f64>>>()?;
+    Ok(SimulationStats::from_returns(returns.into_iter()))
+}
+
+/// Blackjack under a table's on-chain rules: double on any two cards (after a split too), one
+/// split of equal-value cards (split aces take one card each), late surrender when the table
+/// allows it, and no hole card until play-out with a dealer natural taking only the original
+/// stake. Insurance is always declined (2:1 against a 4/13 chance never pays on an infinite
+/// deck). Every decision is the best one for the cards shown, so this is a perfect player's
+/// RTP. Returns are per original stake: double and split stakes count as staked again.
+pub struct BlackjackModel {
+    rules: BlackjackRules,
+    // Dealer final hands per up card once the hole card is not a natural, one representative
+    // hand per value
+    dealer_outcomes: Vec<Vec<(Vec<u8>, f64)>>,
+    // Chance the hole card completes a dealer natural, per up card
+    dealer_naturals: Vec<f64>,
+    // (up card, player value, soft) -> (expected return of the best hit/stand play, hit)
+    hit_stand: HashMap<(u8, u8, bool), (f64, bool)>,
+}
+
+impl BlackjackModel {
+    pub fn new(rules: BlackjackRules) -> Self {
+        let mut dealer_outcomes = vec![Vec::new()];
+        let mut dealer_naturals = vec![0.0];
+        for up_card in 1..=13u8 {
+            let mut outcomes = HashMap::new();
+            collect_dealer_outcomes(&mut vec![up_card], 1.0, rules.dealer_hits_soft_17, &mut outcomes);
+
+            let natural: f64 = outcomes.iter().filter(|(key, _)| key.1).map(|(_, outcome)| outcome.1).sum();
+            dealer_outcomes.push(
+                outcomes
+                    .into_iter()
+                    .filter(|(key, _)| !key.1)
+                    .map(|(_, (dealer_hand, probability))| (dealer_hand, probability / (1.0 - natural)))
+                    .collect()
+            );
+            dealer_naturals.push(natural);
+        }
+
+        Self { rules, dealer_outcomes, dealer_naturals, hit_stand: HashMap::new() }
+    }
+
+    // Decisions only matter when the dealer has no natural (a natural takes the original
+    // stake whatever was played), so every return below is conditioned on that
+    fn stand_return(&self, player_hand: &Vec<u8>, up_card: u8, is_split: bool) -> Result<f64> {
+        let mut total = 0f64;
+        for (dealer_hand, probability) in self.dealer_outcomes[up_card as usize].iter() {
+            let payout = calculate_blackjack_payout(player_hand, dealer_hand, STAKE, is_split)?;
+            total += probability * ratio(payout, STAKE);
+        }
+        Ok(total)
+    }
+
+    /// Expected return of the best hit/stand play from `player_hand`, and whether it is a hit
+    pub fn best_hit_stand(&mut self, player_hand: &mut Vec<u8>, up_card: u8) -> Result<(f64, bool)> {
+        let value = calculate_hand_value(player_hand);
+        if value > BLACKJACK_VALUE {
+            return Ok((0.0, false));
+        }
+
+        let key = (up_card, value, is_soft_hand(player_hand));
+        if let Some(decision) = self.hit_stand.get(&key) {
+            return Ok(*decision);
+        }
+
+        // Split and non-split hands only pay differently on a natural, which never gets here
+        let stand = self.stand_return(player_hand, up_card, true)?;
+        let hit = if value < BLACKJACK_VALUE { self.hit_return(player_hand, up_card)? } else { 0.0 };
+
+        let decision = if hit > stand { (hit, true) } else { (stand, false) };
+        self.hit_stand.insert(key, decision);
+        Ok(decision)
+    }
+
+    fn hit_return(&mut self, player_hand: &mut Vec<u8>, up_card: u8) -> Result<f64> {
+        let mut total = 0f64;
+        for card in 1..=13u8 {
+            player_hand.push(card);
+            total += self.best_hit_stand(player_hand, up_card)?.0 / 13.0;
+            player_hand.pop();
+        }
+        Ok(total)
+    }
+
+    // Doubled stake, exactly one more card
+    fn double_return(&self, player_hand: &mut Vec<u8>, up_card: u8, is_split: bool) -> Result<f64> {
+        let mut total = 0f64;
+        for card in 1..=13u8 {
+            player_hand.push(card);
+            total += 2.0 * self.stand_return(player_hand, up_card, is_split)? / 13.0;
+            player_hand.pop();
+        }
+        Ok(total - 1.0)
+    }
+
+    // Two hands on the pair card, each with its own stake; split aces take one card each
+    fn split_return(&mut self, pair_card: u8, up_card: u8) -> Result<f64> {
+        let mut total = 0f64;
+        for card in 1..=13u8 {
+            let split_hand = BlackjackHand {
+                cards: vec![pair_card, card],
+                bet_amount: STAKE,
+                status: BlackjackHandStatus::Playing,
+                is_split: true,
+            };
+            total += 2.0 * self.hand_return(&split_hand, up_card, MAX_BLACKJACK_HANDS)? / 13.0;
+        }
+        Ok(total - 1.0)
+    }
+
+    fn hand_return(&mut self, hand: &BlackjackHand, up_card: u8, hand_count: usize) -> Result<f64> {
+        let split_aces = hand.is_split && hand.cards[0] == 1;
+        if split_aces || calculate_hand_value(&hand.cards) == BLACKJACK_VALUE {
+            return self.stand_return(&hand.cards, up_card, hand.is_split);
+        }
+        Ok(self.best_action(hand, up_card, hand_count)?.0)
+    }
+
+    /// Best action for a playing hand and its expected return, given the hands on the table
+    pub fn best_action(&mut self, hand: &BlackjackHand, up_card: u8, hand_count: usize) -> Result<(f64, BlackjackAction)> {
+        let mut cards = hand.cards.clone();
+        if cards.len() > 2 {
+            let (value, hit) = self.best_hit_stand(&mut cards, up_card)?;
+            return Ok((value, if hit { BlackjackAction::Hit } else { BlackjackAction::Stand }));
+        }
+
+        let mut options = vec![
+            (self.stand_return(&cards, up_card, hand.is_split)?, BlackjackAction::Stand),
+            (self.hit_return(&mut cards, up_card)?, BlackjackAction::Hit),
+            (self.double_return(&mut cards, up_card, hand.is_split)?, BlackjackAction::DoubleDown),
+        ];
+        if hand_count < MAX_BLACKJACK_HANDS && card_value(cards[0]) == card_value(cards[1]) {
+            options.push((self.split_return(cards[0], up_card)?, BlackjackAction::Split));
+        }
+        if self.rules.surrender_allowed && hand_count == 1 {
+            options.push((0.5, BlackjackAction::Surrender));
+        }
+
+        Ok(options
+            .into_iter()
+            .fold((f64::MIN, BlackjackAction::Stand), |best, option| if option.0 > best.0 { option } else { best }))
+    }
+
+    /// Exact RTP over every opening deal
+    pub fn exact_rtp(&mut self) -> Result<f64> {
+        let mut total = 0f64;
+        for up_card in 1..=13u8 {
+            let dealer_natural = self.dealer_naturals[up_card as usize];
+            for first_card in 1..=13u8 {
+                for second_card in 1..=13u8 {
+                    let cards = vec![first_card, second_card];
+                    total += if is_blackjack(&cards) {
+                        // Push against a dealer natural, 3:2 otherwise
+                        dealer_natural + (1.0 - dealer_natural) * ratio(
+                            calculate_blackjack_payout(&cards, &vec![up_card], STAKE, false)?,
+                            STAKE
+                        )
+                    } else {
+                        let hand = BlackjackHand { cards, bet_amount: STAKE, status: BlackjackHandStatus::Playing, is_split: false };
+                        (1.0 - dealer_natural) * self.best_action(&hand, up_card, 1)?.0
+                    };
+                }
+            }
+        }
+        Ok(total / (13u64 * 13 * 13) as f64)
+    }
+
+    /// Play `rounds` rounds through the settle path itself (deal, player actions, dealer
+    /// play-out and payout) on the on-chain card stream, with the model's decisions
+    pub fn simulate(&mut self, rounds: u64, seed: u64) -> Result<SimulationStats> {
+        let mut returns = Vec::with_capacity(rounds as usize);
+        for round in 0..rounds {
+            let randomness = simulated_reveal(seed, round);
+            let mut draws = CardDraws::new(Pubkey::default(), &randomness);
+            let mut game = simulated_blackjack_game();
+
+            deal_opening_cards(&mut game, &mut draws);
+            let up_card = game.dealer_hand[0];
+            while let Some(active_hand) = game.player_hands.iter().position(|hand| hand.status == BlackjackHandStatus::Playing) {
+                game.active_hand = active_hand as u8;
+                let (_, action) = self.best_action(&game.player_hands[active_hand], up_card, game.player_hands.len())?;
+
+                // The request collects the extra stake before the draw
+                if action == BlackjackAction::DoubleDown {
+                    game.player_hands[active_hand].bet_amount *= 2;
+                }
+                apply_player_action(&mut game, action, &mut draws);
+            }
+            play_out_dealer(&mut game, &self.rules, &mut draws);
+
+            let staked: u64 = game.player_hands.iter().map(|hand| hand.bet_amount).sum();
+            let payout = resolve_payout(&game)?;
+            returns.push((payout as f64 - (staked - STAKE) as f64) / STAKE as f64);
+        }
+        Ok(SimulationStats::from_returns(returns.into_iter()))
+    }
+}
+
+fn simulated_blackjack_game() -> BlackjackGame {
+    BlackjackGame {
+        user: Pubkey::default(),
+        table: Pubkey::default(),
+        nonce: 0,
+        bet_amount: STAKE,
+        total_wagered: STAKE,
+        max_payout: 0,
+        insurance_bet: 0,
+        player_hands: Vec::new(),
+        active_hand: 0,
+        dealer_hand: Vec::new(),
+        dealer_status: BlackjackHandStatus::Playing,
+        phase: BlackjackPhase::Dealing,
+        pending_action: None,
+        randomness_account: Pubkey::default(),
+        commit_slot: 0,
+        payout: 0,
+        is_complete: false,
+        bump: 0,
+    }
+}
+
+fn collect_dealer_outcomes(
+    dealer_hand: &mut Vec<u8>,
+    probability: f64,
+    hits_soft_17: bool,
+    outcomes: &mut HashMap<(u8, bool), (Vec<u8>, f64)>,
+) {
+    if should_dealer_hit(dealer_hand, hits_soft_17) {
+        for card in 1..=13u8 {
+            dealer_hand.push(card);
+            collect_dealer_outcomes(dealer_hand, probability / 13.0, hits_soft_17, outcomes);
+            dealer_hand.pop();
+        }
+        return;
+    }
+
+    let key = (calculate_hand_value(dealer_hand), is_blackjack(dealer_hand));
+    outcomes.entry(key).or_insert_with(|| (dealer_hand.clone(), 0.0)).1 += probability;
+}
+
+/// Settlement RTP per game (the GameState values), defaulting to the HouseEdgeConfig
+pub struct CertificationInputs<'a> {
+    pub config: &'a HouseEdgeConfig,
+    pub settle_rtp_bps: HashMap<u8, u16>,     // GameType as u8 -> GameState.rtp_bps
+    pub slots_machines: Vec<&'a SlotsMachineConfig>,
+    pub blackjack_rules: BlackjackRules,
+    pub rounds: u64,
+    pub seed: u64,
+}
+
+impl CertificationInputs<'_> {
+    fn settle_rtp(&self, game_type: GameType, configured: u16) -> u16 {
+        *self.settle_rtp_bps.get(&(game_type as u8)).unwrap_or(&configured)
+    }
+}
+
+/// Certificates for every game: each slots machine on all its paylines, every roulette bet
+/// type, the aviator cashout targets and blackjack
+pub fn certify_games(inputs: &CertificationInputs) -> Result<Vec<GameCertificate>> {
+    let config = inputs.config;
+    let mut certificates = Vec::new();
+
+    for machine in inputs.slots_machines.iter() {
+        let all_paylines = ((1u64 << machine.paylines.len()) - 1) as u32;
+        certificates.push(GameCertificate {
+            name: format!("slots machine {} ({} lines)", machine.machine_id, machine.paylines.len()),
+            configured_rtp_bps: config.slots_rtp_bps,
+            exact_rtp: slots_exact_rtp(machine, all_paylines)?,
+            simulated: simulate_slots(machine, all_paylines, inputs.rounds, inputs.seed)?,
+        });
+    }
+
+    for (bet_type, numbers) in ROULETTE_CERTIFIED_BETS.iter() {
+        certificates.push(GameCertificate {
+            name: format!("roulette {:?}", bet_type),
+            configured_rtp_bps: config.roulette_rtp_bps,
+            exact_rtp: roulette_exact_rtp(bet_type, numbers)?,
+            simulated: simulate_roulette(bet_type, numbers, inputs.rounds, inputs.seed)?,
+        });
+    }
+
+    let aviator_rtp_bps = inputs.settle_rtp(GameType::Aviator, config.aviator_rtp_bps);
+    for target_multiplier in AVIATOR_CERTIFIED_TARGETS {
+        certificates.push(GameCertificate {
+            name: format!(
+                "aviator cashout {}.{:02}x",
+                target_multiplier / AVIATOR_MULTIPLIER_SCALE,
+                target_multiplier % AVIATOR_MULTIPLIER_SCALE
+            ),
+            configured_rtp_bps: config.aviator_rtp_bps,
+            exact_rtp: aviator_exact_rtp(target_multiplier, aviator_rtp_bps)?,
+            simulated: simulate_aviator(target_multiplier, aviator_rtp_bps, inputs.rounds, inputs.seed)?,
+        });
+    }
+
+    let rules = &inputs.blackjack_rules;
+    let mut blackjack = BlackjackModel::new(rules.clone());
+    certificates.push(GameCertificate {
+        name: format!(
+            "blackjack ({}{})",
+            if rules.dealer_hits_soft_17 { "H17" } else { "S17" },
+            if rules.surrender_allowed { ", surrender" } else { "" }
+        ),
+        configured_rtp_bps: config.blackjack_rtp_bps,
+        exact_rtp: blackjack.exact_rtp()?,
+        simulated: blackjack.simulate(inputs.rounds, inputs.seed)?,
+    });
+
+    Ok(certificates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn machine() -> SlotsMachineConfig {
+        let strip = ReelStrip {
+            stops: vec![
+                ReelStop { symbol: 0, weight: 5 },
+                ReelStop { symbol: 1, weight: 3 },
+                ReelStop { symbol: 2, weight: 2 },
+                ReelStop { symbol: 3, weight: 1 },
+            ],
+        };
+        SlotsMachineConfig {
+            machine_id: 3,
+            reel_count: 3,
+            reel_strips: vec![strip; 3],
+            paylines: vec![Payline { rows: vec![0, 0, 0] }, Payline { rows: vec![1, 1, 1] }],
+            paytable: vec![
+                SlotsPayout { symbol: 1, count: 3, multiplier: 2_000 },
+                SlotsPayout { symbol: 2, count: 3, multiplier: 5_000 },
+                SlotsPayout { symbol: 3, count: 3, multiplier: 50_000 },
+            ],
+            rtp_bps: 0,
+            is_active: true,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn roulette_is_single_zero_at_true_odds() {
+        for (bet_type, numbers) in ROULETTE_CERTIFIED_BETS.iter() {
+            let rtp = roulette_exact_rtp(bet_type, numbers).unwrap();
+            assert!((rtp - 36.0 / 37.0).abs() < 1e-12, "{:?}", bet_type);
+
+            // The RTP the casino is configured with is the wheel's own
+            let certificate = GameCertificate {
+                name: format!("roulette {:?}", bet_type),
+                configured_rtp_bps: crate::utils::roulette::ROULETTE_RTP_BPS,
+                exact_rtp: rtp,
+                simulated: SimulationStats { rounds: 1, rtp, variance: 0.0, hit_frequency: 0.5 },
+            };
+            assert!(certificate.drift_bps().abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn aviator_returns_the_configured_rtp_at_every_target() {
+        for target_multiplier in AVIATOR_CERTIFIED_TARGETS {
+            let rtp = aviator_exact_rtp(target_multiplier, 9_600).unwrap();
+            assert!((rtp - 0.96).abs() < 1e-6, "{} -> {}", target_multiplier, rtp);
+        }
+    }
+
+    #[test]
+    fn simulations_agree_with_enumeration() {
+        let machine = machine();
+        let exact = slots_exact_rtp(&machine, 0b11).unwrap();
+        let simulated = simulate_slots(&machine, 0b11, 4_000, 1).unwrap();
+        assert!((simulated.rtp - exact).abs() <= 4.0 * simulated.standard_error());
+        assert!(simulated.hit_frequency > 0.0 && simulated.hit_frequency < 1.0);
+
+        let exact = roulette_exact_rtp(&RouletteBetType::Dozen, ROULETTE_CERTIFIED_BETS[6].1).unwrap();
+        let simulated = simulate_roulette(&RouletteBetType::Dozen, ROULETTE_CERTIFIED_BETS[6].1, 4_000, 2).unwrap();
+        assert!((simulated.rtp - exact).abs() <= 4.0 * simulated.standard_error());
+        assert!((simulated.hit_frequency - 12.0 / 37.0).abs() < 0.03);
+    }
+
+    fn rules(dealer_hits_soft_17: bool, surrender_allowed: bool) -> BlackjackRules {
+        BlackjackRules { dealer_hits_soft_17, surrender_allowed, insurance_allowed: true }
+    }
+
+    #[test]
+    fn blackjack_full_rules_rtp_is_plausible() {
+        let mut model = BlackjackModel::new(rules(false, false));
+        let exact = model.exact_rtp().unwrap();
+        // Infinite deck, 3:2, double after split, one split: about half a percent edge
+        assert!(exact > 0.993 && exact < 0.997, "{}", exact);
+        assert!((exact * 10_000.0 - DEFAULT_HOUSE_EDGE_CONFIG.blackjack_rtp_bps as f64).abs() <= SLOTS_RTP_TOLERANCE_BPS as f64);
+
+        let simulated = model.simulate(4_000, 3).unwrap();
+        assert!((simulated.rtp - exact).abs() <= 4.0 * simulated.standard_error());
+        assert!(BlackjackModel::new(rules(true, false)).exact_rtp().unwrap() < exact);
+        assert!(BlackjackModel::new(rules(false, true)).exact_rtp().unwrap() > exact);
+    }
+
+    #[test]
+    fn blackjack_decisions_follow_basic_strategy() {
+        let mut model = BlackjackModel::new(rules(false, true));
+        let hand = |cards: &[u8]| BlackjackHand {
+            cards: cards.to_vec(),
+            bet_amount: STAKE,
+            status: BlackjackHandStatus::Playing,
+            is_split: false,
+        };
+        let action = |model: &mut BlackjackModel, cards: &[u8], up_card: u8| model.best_action(&hand(cards), up_card, 1).unwrap().1;
+
+        assert!(action(&mut model, &[6, 5], 6) == BlackjackAction::DoubleDown);
+        assert!(action(&mut model, &[10, 6], 10) == BlackjackAction::Surrender);
+        assert!(action(&mut model, &[8, 8], 10) == BlackjackAction::Split);
+        assert!(action(&mut model, &[1, 1], 10) == BlackjackAction::Split);
+        assert!(action(&mut model, &[10, 10], 6) == BlackjackAction::Stand);
+        assert!(action(&mut model, &[10, 2], 2) == BlackjackAction::Hit);
+        assert!(action(&mut model, &[10, 3, 4], 10) == BlackjackAction::Stand);
+    }
+
+    #[test]
+    fn certificates_fail_outside_tolerance() {
+        let certificate = GameCertificate {
+            name: "roulette Red".to_string(),
+            configured_rtp_bps: 9_730,
+            exact_rtp: 36.0 / 37.0 * 0.973,
+            simulated: SimulationStats { rounds: 1, rtp: 36.0 / 37.0 * 0.973, variance: 0.0, hit_frequency: 0.5 },
+        };
+        assert!(certificate.drift_bps() < -260.0);
+        assert!(!certificate.passes(25, 4.0));
+
+        let certificate = GameCertificate { exact_rtp: 0.973, simulated: SimulationStats { rtp: 0.973, ..certificate.simulated }, ..certificate };
+        assert!(certificate.passes(25, 4.0));
+    }
+}
@@ -61,8 +61,78 @@ pub enum CasinoError {
     
     #[msg("Invalid slots payline")]
     InvalidSlotsPayline,
+    
+    #[msg("Too many pending games")]
+    TooManyPendingGames,
+    
+    #[msg("Unauthorized")]
+    Unauthorized,
+    
+    #[msg("Game has not reached the VRF timeout")]
+    GameNotExpired,
+    
+    #[msg("Blackjack table not active")]
+    BlackjackTableNotActive,
+    
+    #[msg("Blackjack rule not enabled at this table")]
+    BlackjackRuleDisabled,
+    
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    
+    #[msg("Too many bets on one roulette spin")]
+    TooManyRouletteBets,
+    
+    #[msg("Invalid slots machine configuration")]
+    InvalidSlotsConfig,
+    
+    #[msg("Slots machine RTP does not match the configured RTP")]
+    SlotsRtpMismatch,
+    
+    #[msg("Slots machine not active")]
+    SlotsMachineNotActive,
+    
+    #[msg("Game not active")]
+    GameNotActive,
+    
+    #[msg("Invalid bet limits")]
+    InvalidBetLimits,
+    
+    #[msg("Bet exceeds the bankroll exposure limit")]
+    InsufficientBankroll,
+    
+    #[msg("Invalid exposure configuration")]
+    InvalidExposureConfig,
+    
+    #[msg("Fees must be booked into the current fee epoch")]
+    InvalidFeeEpoch,
+    
+    #[msg("Invalid VIP tier table")]
+    InvalidVipTiers,
+    
+    #[msg("No rakeback to claim")]
+    NoRakebackToClaim,
+    
+    #[msg("Invalid referral configuration")]
+    InvalidReferralConfig,
+    
+    #[msg("Invalid jackpot configuration")]
+    InvalidJackpotConfig,
+    
+    #[msg("Invalid tournament configuration")]
+    InvalidTournamentConfig,
+    
+    #[msg("Randomness for this game was already revealed; settle it instead")]
+    RandomnessAlreadyRevealed,
+    
+    #[msg("Jackpot vault missing or not the jackpot's vault")]
+    InvalidJackpotVault,
+    
+    #[msg("Account is already on the current layout or its layout is unknown")]
+    UnsupportedAccountLayout,
 }
 
+
 #[error_code]
 pub enum QuestError {
     #[msg("Quest campaign not found")]
@@ -184,4 +254,109 @@ pub enum LiquidityError {
     
     #[msg("Invalid fee share configuration")]
     InvalidFeeShareConfig,
+    
+    #[msg("Fee epoch has not ended")]
+    FeeEpochNotEnded,
+    
+    #[msg("Fee epoch already distributed")]
+    FeesAlreadyDistributed,
+    
+    #[msg("Liquidity pool has no equity left")]
+    LiquidityPoolInsolvent,
+
+    #[msg("Withdrawal exceeds the epoch's unwithdrawn platform fees")]
+    InsufficientPlatformFees,
+}
+
+#[error_code]
+pub enum LoyaltyError {
+    #[msg("Insufficient loyalty points")]
+    InsufficientLoyaltyPoints,
+    
+    #[msg("Loyalty item not active")]
+    LoyaltyItemNotActive,
+    
+    #[msg("Loyalty item out of stock")]
+    LoyaltyItemOutOfStock,
+    
+    #[msg("Invalid loyalty reward")]
+    InvalidLoyaltyReward,
+    
+    #[msg("No free spins available")]
+    NoFreeSpins,
+    
+    #[msg("Free spins already held at a different bet")]
+    FreeSpinBetMismatch,
+    
+    #[msg("Points mint not enabled")]
+    PointsMintNotEnabled,
+}
+
+#[error_code]
+pub enum LimitsError {
+    #[msg("Player is self-excluded")]
+    SelfExcluded,
+    
+    #[msg("Wager limit exceeded")]
+    WagerLimitExceeded,
+    
+    #[msg("Net loss limit exceeded")]
+    LossLimitExceeded,
+    
+    #[msg("Session over, cooldown in progress")]
+    SessionCooldown,
+    
+    #[msg("Invalid self-exclusion period")]
+    InvalidExclusionPeriod,
+}
+
+#[error_code]
+pub enum SessionError {
+    #[msg("Signer is neither the player nor one of their session keys")]
+    UnauthorizedSigner,
+    
+    #[msg("Session key expired")]
+    SessionExpired,
+    
+    #[msg("Game not allowed for this session key")]
+    GameNotAllowed,
+    
+    #[msg("Session key wager cap reached")]
+    SessionWagerCapReached,
+    
+    #[msg("Invalid session key parameters")]
+    InvalidSessionParameters,
+}
+
+#[error_code]
+pub enum ReferralError {
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+    
+    #[msg("No referral earnings to claim")]
+    NoReferralEarnings,
+    
+    #[msg("Upline referrer stats missing or out of order")]
+    InvalidReferrerStats,
+}
+
+#[error_code]
+pub enum TournamentError {
+    #[msg("Tournament has already ended")]
+    TournamentEnded,
+    
+    #[msg("Player is already registered in a running tournament")]
+    AlreadyInTournament,
+    
+    #[msg("Tournament accounts missing or not the player's registration")]
+    InvalidTournamentAccounts,
+    
+    #[msg("Tournament has not ended yet")]
+    TournamentNotEnded,
+    
+    #[msg("Tournament already finalized")]
+    TournamentAlreadyFinalized,
+    
+    #[msg("Winner token account missing or out of order")]
+    InvalidWinnerAccount,
 }
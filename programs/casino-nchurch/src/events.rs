@@ -0,0 +1,654 @@
+use anchor_lang::prelude::*;
+use crate::state::casino::{BlackjackAction, BlackjackHand, FeeBasis, GameType, RouletteBetResult};
+use crate::state::jackpot::JackpotTrigger;
+use crate::state::loyalty::LoyaltyReward;
+use crate::state::limits::LimitSettings;
+use crate::state::referral::ReferralConfig;
+use crate::state::tournament::TournamentConfig;
+use crate::state::vip::{VipTier, VipVolumeBasis};
+use crate::verify::GameRecord;
+
+// Every event starts with the layout version and the slot it was emitted in, so indexers
+// can decode old and new layouts side by side. Bump EVENT_VERSION on any field change.
+pub const EVENT_VERSION: u8 = 1;
+
+// Casino configuration events
+
+#[event]
+pub struct CasinoInitialized {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub vault: Pubkey,
+    pub fee_vault: Pubkey,
+}
+
+// An account rewritten from an older layout (see instructions::migrate)
+#[event]
+pub struct AccountLayoutMigrated {
+    pub version: u8,
+    pub slot: u64,
+    pub account: Pubkey,
+    pub old_layout_version: u8,
+    pub new_layout_version: u8,
+}
+
+#[event]
+pub struct GameInitialized {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub game_type: GameType,
+    pub rtp_bps: u16,
+    pub min_bet: u64,
+    pub max_bet: u64,
+}
+
+#[event]
+pub struct RtpUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub game_type: GameType,
+    pub old_rtp_bps: u16,
+    pub new_rtp_bps: u16,
+}
+
+#[event]
+pub struct GameUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub game_type: GameType,
+    pub old_min_bet: u64,
+    pub new_min_bet: u64,
+    pub old_max_bet: u64,
+    pub new_max_bet: u64,
+    pub old_is_active: bool,
+    pub new_is_active: bool,
+}
+
+#[event]
+pub struct PlatformFeeUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+}
+
+#[event]
+pub struct FeeBasisUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub old_fee_basis: FeeBasis,
+    pub new_fee_basis: FeeBasis,
+}
+
+#[event]
+pub struct MaxPendingGamesUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub old_max_pending_games: u8,
+    pub new_max_pending_games: u8,
+}
+
+#[event]
+pub struct MaxExposureUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub old_max_exposure_bps: u16,
+    pub new_max_exposure_bps: u16,
+}
+
+#[event]
+pub struct CasinoActiveChanged {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub old_is_active: bool,
+    pub new_is_active: bool,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub old_pending_authority: Option<Pubkey>,
+    pub new_pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityAccepted {
+    pub version: u8,
+    pub slot: u64,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct SlotsMachineInitialized {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub machine: Pubkey,
+    pub machine_id: u64,
+    pub rtp_bps: u16,
+}
+
+#[event]
+pub struct SlotsMachineActiveChanged {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub machine: Pubkey,
+    pub machine_id: u64,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct BlackjackTableUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub table: Pubkey,
+    pub table_id: u64,
+    pub dealer_hits_soft_17: bool,
+    pub surrender_allowed: bool,
+    pub insurance_allowed: bool,
+    pub is_active: bool,
+}
+
+// Game events. `game` is the VrfGameState or BlackjackGame account of the round.
+
+#[event]
+pub struct GameRequested {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub game_type: GameType,
+    pub nonce: u64,
+    pub bet_amount: u64,
+    pub max_payout: u64,
+    pub randomness_account: Pubkey,
+}
+
+#[event]
+pub struct SlotsSettled {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub nonce: u64,
+    pub machine: Pubkey,
+    pub bet_amount: u64,
+    pub active_paylines: u32,
+    pub window: Vec<u8>,     // Symbols reel by reel, SLOTS_ROWS per reel
+    pub payout: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct RouletteSettled {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub nonce: u64,
+    pub bet_amount: u64,
+    pub winning_number: u8,
+    pub results: Vec<RouletteBetResult>,
+    pub payout: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct AviatorSettled {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub nonce: u64,
+    pub bet_amount: u64,
+    pub target_multiplier: u64,
+    pub crash_multiplier: u64,
+    pub payout: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct BlackjackActionRequested {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub nonce: u64,
+    pub hand: u8,
+    pub action: BlackjackAction,
+    pub extra_stake: u64,
+    pub randomness_account: Pubkey,
+}
+
+#[event]
+pub struct BlackjackInsurancePlaced {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub nonce: u64,
+    pub insurance_bet: u64,
+}
+
+#[event]
+pub struct BlackjackCardsDrawn {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub nonce: u64,
+    pub active_hand: u8,
+    pub player_hands: Vec<BlackjackHand>,
+    pub dealer_hand: Vec<u8>,
+}
+
+#[event]
+pub struct BlackjackSettled {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub nonce: u64,
+    pub player_hands: Vec<BlackjackHand>,
+    pub dealer_hand: Vec<u8>,
+    pub total_wagered: u64,
+    pub insurance_bet: u64,
+    pub payout: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct BlackjackRoundExpired {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub nonce: u64,
+    pub dropped_action: Option<BlackjackAction>,   // Pending action whose draw never landed
+    pub randomness_account: Pubkey,                // Fresh draw for the dealer's play-out
+}
+
+// Everything needed to recompute a settled draw with verify::verify_outcome
+#[event]
+pub struct GameProof {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub record: GameRecord,
+}
+
+#[event]
+pub struct GameRefunded {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub game_type: GameType,
+    pub nonce: u64,
+    pub amount: u64,
+    pub voided: bool,        // Voided by the authority rather than expired
+}
+
+// Quest events
+
+#[event]
+pub struct QuestCreated {
+    pub version: u8,
+    pub slot: u64,
+    pub creator: Pubkey,
+    pub campaign: Pubkey,
+    pub campaign_counter: u64,
+    pub reward_pool: u64,
+    pub platform_fee: u64,
+    pub max_participants: u32,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct QuestJoined {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub campaign: Pubkey,
+    pub participants: u32,
+}
+
+#[event]
+pub struct QuestCompleted {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub campaign: Pubkey,
+    pub reward_amount: u64,
+}
+
+#[event]
+pub struct QuestRewardPaid {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub campaign: Pubkey,
+    pub recipient_token_account: Pubkey,
+    pub amount: u64,
+    pub distribution_complete: bool,
+}
+
+// Liquidity events
+
+#[event]
+pub struct LiquidityPoolInitialized {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub liquidity_pool: Pubkey,
+    pub lp_token_mint: Pubkey,
+}
+
+#[event]
+pub struct LiquidityDeposited {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub lp_tokens: u64,
+    pub total_liquidity: u64,
+    pub lp_token_supply: u64,
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub lp_tokens: u64,
+    pub total_liquidity: u64,
+    pub lp_token_supply: u64,
+}
+
+#[event]
+pub struct LiquiditySynced {
+    pub version: u8,
+    pub slot: u64,
+    pub old_total_liquidity: u64,
+    pub new_total_liquidity: u64,
+}
+
+#[event]
+pub struct LPTokensStaked {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub staking: Pubkey,
+    pub amount: u64,
+    pub staking_end_time: i64,
+}
+
+#[event]
+pub struct PlatformFeesDistributed {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub epoch: u64,
+    pub total_platform_fees: u64,
+    pub lp_fee_share: u64,
+    pub platform_fee_share: u64,
+}
+
+#[event]
+pub struct PlatformFeesWithdrawn {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub remaining: u64,      // Platform share of the epoch left in the fee vault
+}
+
+// Loyalty events
+
+#[event]
+pub struct PointsMintEnabled {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub points_mint: Pubkey,
+}
+
+#[event]
+pub struct LoyaltyItemUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub item: Pubkey,
+    pub item_id: u64,
+    pub reward: LoyaltyReward,
+    pub cost_points: u64,
+    pub stock: Option<u32>,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct LoyaltyPointsTokenized {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub points_remaining: u64,
+}
+
+#[event]
+pub struct LoyaltyRedeemed {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub item: Pubkey,
+    pub item_id: u64,
+    pub reward: LoyaltyReward,
+    pub cost_points: u64,
+    pub from_tokens: bool,   // Burned from the points token account rather than UserStats
+}
+
+// VIP events
+
+#[event]
+pub struct VipConfigUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub volume_basis: VipVolumeBasis,
+    pub tiers: Vec<VipTier>,
+}
+
+#[event]
+pub struct VipTierChanged {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub old_tier: u8,
+    pub new_tier: u8,
+    pub volume: u64,       // Volume on the configured basis at the time of the change
+}
+
+#[event]
+pub struct RakebackClaimed {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub vip_tier: u8,
+}
+
+// Responsible-gaming events
+
+#[event]
+pub struct PlayerLimitsUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub limits: LimitSettings,                  // In force now
+    pub pending_limits: Option<LimitSettings>,  // Looser limits queued behind the delay
+    pub pending_effective_at: i64,
+}
+
+#[event]
+pub struct PlayerSelfExcluded {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub excluded_until: i64,   // i64::MAX = permanent
+}
+
+// Session key events
+
+#[event]
+pub struct SessionKeyCreated {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub session_key: Pubkey,
+    pub session_signer: Pubkey,
+    pub expires_at: i64,
+    pub max_wager: u64,
+    pub allowed_games: Vec<GameType>,
+}
+
+#[event]
+pub struct SessionKeyRevoked {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub session_key: Pubkey,
+    pub session_signer: Pubkey,
+    pub wagered: u64,
+}
+
+// Referral events
+
+#[event]
+pub struct ReferralConfigUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub old_referral_config: ReferralConfig,
+    pub new_referral_config: ReferralConfig,
+}
+
+#[event]
+pub struct ReferrerSet {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+    pub upline: Vec<Pubkey>,
+}
+
+#[event]
+pub struct ReferralCredited {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,          // Player whose game was settled
+    pub referrer: Pubkey,
+    pub level: u8,             // 1 = direct referrer
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralEarningsClaimed {
+    pub version: u8,
+    pub slot: u64,
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+// Jackpot events
+
+#[event]
+pub struct JackpotConfigUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub contribution_bps: u16,
+    pub reseed_amount: u64,
+    pub jackpot_symbol: u8,
+    pub jackpot_run: u8,
+    pub roll_odds: u64,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct JackpotFunded {
+    pub version: u8,
+    pub slot: u64,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub pot: u64,              // Vault balance after funding
+}
+
+#[event]
+pub struct JackpotWon {
+    pub version: u8,
+    pub slot: u64,
+    pub user: Pubkey,
+    pub game: Pubkey,
+    pub amount: u64,
+    pub trigger: JackpotTrigger,
+    pub pot_after: u64,        // Reseed left in the vault
+}
+
+// Tournament events
+
+#[event]
+pub struct TournamentCreated {
+    pub version: u8,
+    pub slot: u64,
+    pub tournament: Pubkey,
+    pub tournament_id: u64,
+    pub config: TournamentConfig,
+}
+
+#[event]
+pub struct TournamentJoined {
+    pub version: u8,
+    pub slot: u64,
+    pub tournament: Pubkey,
+    pub user: Pubkey,
+    pub entry_fee: u64,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct TournamentScoreUpdated {
+    pub version: u8,
+    pub slot: u64,
+    pub tournament: Pubkey,
+    pub user: Pubkey,
+    pub score: i64,
+    pub rank: Option<u8>,      // 1-based leaderboard place, None when off the board
+}
+
+#[event]
+pub struct TournamentFinalized {
+    pub version: u8,
+    pub slot: u64,
+    pub tournament: Pubkey,
+    pub prize_pool: u64,
+    pub winners: Vec<Pubkey>,  // Leaderboard order
+    pub prizes: Vec<u64>,
+    pub unclaimed: u64,        // Returned to the casino vault when nobody scored
+}
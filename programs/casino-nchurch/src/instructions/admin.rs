@@ -0,0 +1,303 @@
+use anchor_lang::prelude::*;
+use crate::state::casino::*;
+use crate::state::referral::ReferralConfig;
+use crate::utils::*;
+use crate::events::*;
+use crate::errors::*;
+
+// Authority-only changes to the casino configuration
+#[derive(Accounts)]
+pub struct UpdateCasinoConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    pub authority: Signer<'info>,
+}
+
+impl <'info> UpdateCasinoConfig<'info> {
+    pub fn update_platform_fee(&mut self, fee_bps: u16) -> Result<()> {
+        validate_fee_config(fee_bps)?;
+
+        let old_fee_bps = self.casino_state.house_edge_config.platform_fee_bps;
+        self.casino_state.house_edge_config.platform_fee_bps = fee_bps;
+
+        emit!(PlatformFeeUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            old_fee_bps,
+            new_fee_bps: fee_bps,
+        });
+        Ok(())
+    }
+
+    pub fn set_fee_basis(&mut self, fee_basis: FeeBasis) -> Result<()> {
+        let old_fee_basis = self.casino_state.house_edge_config.fee_basis.clone();
+        self.casino_state.house_edge_config.fee_basis = fee_basis.clone();
+
+        emit!(FeeBasisUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            old_fee_basis,
+            new_fee_basis: fee_basis,
+        });
+        Ok(())
+    }
+
+    pub fn set_max_pending_games(&mut self, max_pending_games: u8) -> Result<()> {
+        require!(max_pending_games > 0, CasinoError::InvalidGameState);
+
+        let old_max_pending_games = self.casino_state.max_pending_games;
+        self.casino_state.max_pending_games = max_pending_games;
+
+        emit!(MaxPendingGamesUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            old_max_pending_games,
+            new_max_pending_games: max_pending_games,
+        });
+        Ok(())
+    }
+
+    pub fn set_max_exposure(&mut self, max_exposure_bps: u16) -> Result<()> {
+        require!(
+            max_exposure_bps > 0 && max_exposure_bps as u64 <= BPS_DENOMINATOR,
+            CasinoError::InvalidExposureConfig
+        );
+
+        let old_max_exposure_bps = self.casino_state.max_exposure_bps;
+        self.casino_state.max_exposure_bps = max_exposure_bps;
+
+        emit!(MaxExposureUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            old_max_exposure_bps,
+            new_max_exposure_bps: max_exposure_bps,
+        });
+        Ok(())
+    }
+
+    pub fn set_referral_config(&mut self, referral_config: ReferralConfig) -> Result<()> {
+        crate::utils::referral::validate_referral_config(&referral_config)?;
+
+        let old_referral_config = self.casino_state.referral_config.clone();
+        self.casino_state.referral_config = referral_config.clone();
+
+        emit!(ReferralConfigUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            old_referral_config,
+            new_referral_config: referral_config,
+        });
+        Ok(())
+    }
+
+    // Pausing stops new games; games already requested can still be settled or refunded
+    pub fn set_casino_active(&mut self, is_active: bool) -> Result<()> {
+        let old_is_active = self.casino_state.is_active;
+        self.casino_state.is_active = is_active;
+
+        emit!(CasinoActiveChanged {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            old_is_active,
+            new_is_active: is_active,
+        });
+        Ok(())
+    }
+
+    // First step of an authority rotation; the new authority must accept before it takes over
+    pub fn propose_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        let old_pending_authority = self.casino_state.pending_authority;
+        self.casino_state.pending_authority = Some(new_authority);
+
+        emit!(AuthorityProposed {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            old_pending_authority,
+            new_pending_authority: new_authority,
+        });
+        Ok(())
+    }
+}
+
+// Second step of an authority rotation, signed by the proposed authority
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        constraint = casino_state.pending_authority == Some(new_authority.key()) @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    pub new_authority: Signer<'info>,
+}
+
+impl <'info> AcceptAuthority<'info> {
+    pub fn accept_authority(&mut self) -> Result<()> {
+        let old_authority = self.casino_state.authority;
+        self.casino_state.authority = self.new_authority.key();
+        self.casino_state.pending_authority = None;
+
+        emit!(AuthorityAccepted {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            old_authority,
+            new_authority: self.new_authority.key(),
+        });
+        Ok(())
+    }
+}
+
+// Create the GameState that holds one game's limits, RTP, switch and totals
+#[derive(Accounts)]
+#[instruction(game_type: GameType)]
+pub struct InitializeGame<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = GameState::DISCRIMINATOR.len() + GameState::INIT_SPACE,
+        seeds = [b"game_state", [game_type.clone() as u8].as_ref()],
+        bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> InitializeGame<'info> {
+    pub fn initialize_game(&mut self, game_type: GameType, bumps: &InitializeGameBumps) -> Result<()> {
+        let mut config = self.casino_state.house_edge_config.clone();
+        let rtp_bps = *game_rtp(&mut config, &game_type);
+
+        self.game_state.set_inner(GameState {
+            game_type: game_type.clone(),
+            total_bets: 0,
+            total_payouts: 0,
+            total_games: 0,
+            rtp_bps,
+            min_bet: DEFAULT_MIN_BET,
+            max_bet: DEFAULT_MAX_BET,
+            is_active: true,
+            bump: bumps.game_state,
+        });
+
+        emit!(GameInitialized {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            game_type,
+            rtp_bps,
+            min_bet: DEFAULT_MIN_BET,
+            max_bet: DEFAULT_MAX_BET,
+        });
+        Ok(())
+    }
+}
+
+// Tune or switch off a single game without touching the others
+#[derive(Accounts)]
+#[instruction(game_type: GameType)]
+pub struct UpdateGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state", [game_type.clone() as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+impl <'info> UpdateGame<'info> {
+    pub fn update_game(&mut self, game_type: GameType, min_bet: u64, max_bet: u64, is_active: bool) -> Result<()> {
+        require!(min_bet > 0 && min_bet <= max_bet, CasinoError::InvalidBetLimits);
+
+        let game_state = &mut self.game_state;
+        let event = GameUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            game_type,
+            old_min_bet: game_state.min_bet,
+            new_min_bet: min_bet,
+            old_max_bet: game_state.max_bet,
+            new_max_bet: max_bet,
+            old_is_active: game_state.is_active,
+            new_is_active: is_active,
+        };
+
+        game_state.min_bet = min_bet;
+        game_state.max_bet = max_bet;
+        game_state.is_active = is_active;
+
+        emit!(event);
+        Ok(())
+    }
+
+    // The casino-wide config and the GameState are updated together so they never disagree
+    pub fn update_rtp(&mut self, game_type: GameType, rtp_bps: u16) -> Result<()> {
+        validate_rtp_config(rtp_bps)?;
+
+        // Roulette pays true odds, so its RTP is set by the wheel rather than configured
+        require!(
+            game_type != GameType::Roulette || rtp_bps == crate::utils::roulette::ROULETTE_RTP_BPS,
+            CasinoError::InvalidHouseEdgeConfig
+        );
+
+        let rtp_field = game_rtp(&mut self.casino_state.house_edge_config, &game_type);
+        let old_rtp_bps = *rtp_field;
+        *rtp_field = rtp_bps;
+        self.game_state.rtp_bps = rtp_bps;
+
+        emit!(RtpUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            game_type,
+            old_rtp_bps,
+            new_rtp_bps: rtp_bps,
+        });
+        Ok(())
+    }
+}
+
+fn game_rtp<'a>(config: &'a mut HouseEdgeConfig, game_type: &GameType) -> &'a mut u16 {
+    match game_type {
+        GameType::Slots => &mut config.slots_rtp_bps,
+        GameType::Roulette => &mut config.roulette_rtp_bps,
+        GameType::Aviator => &mut config.aviator_rtp_bps,
+        GameType::Blackjack => &mut config.blackjack_rtp_bps,
+    }
+}
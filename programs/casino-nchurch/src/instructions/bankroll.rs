@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::casino::*;
+use crate::utils::bankroll::*;
+
+// Largest bet the bankroll currently accepts for a game, returned to the caller.
+// `max_payout_bps` is the gross worst-case payout of the bet being quoted
+// (e.g. 360_000 for a straight-up roulette bet, 20_000 for even money).
+#[derive(Accounts)]
+#[instruction(game_type: GameType)]
+pub struct QuoteMaxBet<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"game_state", [game_type.clone() as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl <'info> QuoteMaxBet<'info> {
+    pub fn quote_max_bet(&self, _game_type: GameType, max_payout_bps: u64) -> Result<u64> {
+        let vault_balance = self.casino_vault.amount;
+
+        // Kelly stake for the house edge, capped by the remaining exposure headroom and the game limit
+        let kelly_bet = kelly_max_bet(vault_balance, self.game_state.rtp_bps, max_payout_bps)?;
+        let exposure_bet = exposure_max_bet(
+            self.casino_state.outstanding_liabilities,
+            vault_balance,
+            self.casino_state.max_exposure_bps,
+            max_payout_bps
+        )?;
+        let max_bet = kelly_bet.min(exposure_bet).min(self.game_state.max_bet);
+
+        msg!("Max bet quote: Kelly: {}, Exposure: {}, Max Bet: {}", kelly_bet, exposure_bet, max_bet);
+        Ok(max_bet)
+    }
+}
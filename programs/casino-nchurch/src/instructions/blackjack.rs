@@ -0,0 +1,1264 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::state::casino::*;
+use crate::state::history::GameHistory;
+use crate::state::session::SessionKey;
+use crate::state::tournament::{Tournament, TournamentEntry};
+use crate::state::vip::VipConfig;
+use crate::utils::*;
+use crate::events::*;
+use crate::errors::*;
+
+// Blackjack is played as a chain of request/settle rounds on one BlackjackGame account.
+// Every round commits a fresh Switchboard randomness account and every card drawn in the
+// settle is derived from that reveal. The dealer takes no hole card at the deal; it is drawn
+// at play-out, and a dealer blackjack only takes the original stake (double and split stakes
+// are returned), which matches the odds of a peek game.
+
+// Create a blackjack table with its own rule set
+#[derive(Accounts)]
+#[instruction(table_id: u64)]
+pub struct InitializeBlackjackTable<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = BlackjackTable::DISCRIMINATOR.len() + BlackjackTable::INIT_SPACE,
+        seeds = [b"blackjack_table", table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub blackjack_table: Account<'info, BlackjackTable>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> InitializeBlackjackTable<'info> {
+    pub fn initialize_blackjack_table(&mut self, table_id: u64, rules: BlackjackRules, bumps: &InitializeBlackjackTableBumps) -> Result<()> {
+        self.blackjack_table.set_inner(BlackjackTable {
+            table_id,
+            rules,
+            is_active: true,
+            bump: bumps.blackjack_table,
+        });
+
+        emit!(BlackjackTableUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            table: self.blackjack_table.key(),
+            table_id,
+            dealer_hits_soft_17: self.blackjack_table.rules.dealer_hits_soft_17,
+            surrender_allowed: self.blackjack_table.rules.surrender_allowed,
+            insurance_allowed: self.blackjack_table.rules.insurance_allowed,
+            is_active: self.blackjack_table.is_active,
+        });
+        Ok(())
+    }
+}
+
+// Change a table's rules or take it offline
+#[derive(Accounts)]
+#[instruction(table_id: u64)]
+pub struct UpdateBlackjackTable<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        seeds = [b"blackjack_table", table_id.to_le_bytes().as_ref()],
+        bump = blackjack_table.bump
+    )]
+    pub blackjack_table: Account<'info, BlackjackTable>,
+
+    pub authority: Signer<'info>,
+}
+
+impl <'info> UpdateBlackjackTable<'info> {
+    pub fn update_blackjack_table(&mut self, table_id: u64, rules: BlackjackRules, is_active: bool) -> Result<()> {
+        self.blackjack_table.rules = rules;
+        self.blackjack_table.is_active = is_active;
+
+        emit!(BlackjackTableUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            table: self.blackjack_table.key(),
+            table_id,
+            dealer_hits_soft_17: self.blackjack_table.rules.dealer_hits_soft_17,
+            surrender_allowed: self.blackjack_table.rules.surrender_allowed,
+            insurance_allowed: self.blackjack_table.rules.insurance_allowed,
+            is_active: self.blackjack_table.is_active,
+        });
+        Ok(())
+    }
+}
+
+// Place the opening bet and commit randomness for the deal
+#[derive(Accounts)]
+pub struct RequestBlackjackDeal<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state", [GameType::Blackjack as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"blackjack_table", blackjack_table.table_id.to_le_bytes().as_ref()],
+        bump = blackjack_table.bump
+    )]
+    pub blackjack_table: Account<'info, BlackjackTable>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = UserStats::DISCRIMINATOR.len() + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        seeds = [b"vip_config"],
+        bump = vip_config.bump
+    )]
+    pub vip_config: Option<Account<'info, VipConfig>>,
+
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = BlackjackGame::DISCRIMINATOR.len() + BlackjackGame::INIT_SPACE,
+        seeds = [b"blackjack_game", user.key().as_ref(), &user_stats.game_nonce.to_le_bytes()],
+        bump
+    )]
+    pub blackjack_game: Account<'info, BlackjackGame>,
+
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    // The player, or one of their session keys
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: The account's data is validated manually within the handler.
+    pub randomness_account_data: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> RequestBlackjackDeal<'info> {
+    pub fn request_blackjack_deal(&mut self, bet_amount: u64, randomness_account: Pubkey, bumps: &RequestBlackjackDealBumps) -> Result<()> {
+        let casino_state = &mut self.casino_state;
+        let game_state = &mut self.game_state;
+        let user_stats = &mut self.user_stats;
+
+        // Validate casino, game and table are active
+        require!(casino_state.is_active, CasinoError::CasinoNotActive);
+        require!(game_state.is_active, CasinoError::GameNotActive);
+        require!(self.blackjack_table.is_active, CasinoError::BlackjackTableNotActive);
+
+        // Validate user has room for another open game
+        require!(
+            user_stats.pending_games < casino_state.max_pending_games,
+            CasinoError::TooManyPendingGames
+        );
+
+        // Validate bet amount (the max bet is raised by the player's VIP tier)
+        user_stats.user = self.user.key();
+        let vip_tier = crate::instructions::vip::refresh_vip_tier(user_stats, self.vip_config.as_deref(), Clock::get()?.unix_timestamp)?;
+        validate_bet_amount(bet_amount, game_state.min_bet, crate::utils::vip::vip_max_bet(game_state.max_bet, vip_tier.as_ref())?)?;
+
+        // Validate the vault can cover a natural on the opening stake
+        let max_payout = calculate_blackjack_max_payout(bet_amount, 0)?;
+        crate::utils::bankroll::validate_exposure(
+            casino_state.outstanding_liabilities,
+            max_payout,
+            self.casino_vault.amount,
+            casino_state.max_exposure_bps
+        )?;
+
+        // Parse and validate Switchboard randomness data
+        let randomness_data = crate::utils::vrf::parse_randomness_data(&self.randomness_account_data)?;
+        let clock = Clock::get()?;
+
+        // Validate the signer is the player or a session key allowed this game and stake
+        crate::instructions::session::authorize_play(&self.user, &self.signer, self.session_key.as_mut(), GameType::Blackjack, bet_amount)?;
+
+        // Validate the stake against the player's responsible-gaming limits
+        crate::instructions::limits::enforce_player_limits(&self.player_limits, bet_amount, clock.unix_timestamp)?;
+
+        // Ensure randomness is from the previous slot
+        require!(
+            randomness_data.seed_slot == clock.slot - 1,
+            CasinoError::VrfRequestFailed
+        );
+
+        crate::instructions::session::take_player_stake(
+            &self.user,
+            self.session_key.as_ref(),
+            &self.user_token_account,
+            &self.casino_vault,
+            &self.usdc_mint,
+            &self.token_program,
+            bet_amount,
+        )?;
+
+        self.blackjack_game.set_inner(BlackjackGame {
+            user: self.user.key(),
+            table: self.blackjack_table.key(),
+            nonce: user_stats.game_nonce,
+            bet_amount,
+            total_wagered: bet_amount,
+            max_payout,
+            insurance_bet: 0,
+            player_hands: Vec::new(),
+            active_hand: 0,
+            dealer_hand: Vec::new(),
+            dealer_status: BlackjackHandStatus::Playing,
+            phase: BlackjackPhase::Dealing,
+            pending_action: None,
+            randomness_account,
+            commit_slot: randomness_data.seed_slot,
+            payout: 0,
+            is_complete: false,
+            bump: bumps.blackjack_game,
+        });
+
+        // Update user stats
+        user_stats.total_bets += bet_amount;
+        crate::instructions::vip::record_volume(user_stats, bet_amount, clock.unix_timestamp);
+        user_stats.games_played += 1;
+        user_stats.game_nonce += 1;
+        user_stats.pending_games += 1;
+        user_stats.bump = bumps.user_stats;
+        user_stats.layout_version = crate::state::migration::USER_STATS_VERSION;
+
+        // Update casino stats
+        casino_state.total_games_played += 1;
+        casino_state.total_volume += bet_amount;
+        casino_state.outstanding_liabilities += max_payout;
+        casino_state.pending_stakes += bet_amount;
+
+        // Update game stats
+        game_state.total_games += 1;
+        game_state.total_bets += bet_amount;
+
+        emit!(GameRequested {
+            version: EVENT_VERSION,
+            slot: clock.slot,
+            user: self.user.key(),
+            game: self.blackjack_game.key(),
+            game_type: GameType::Blackjack,
+            nonce: self.blackjack_game.nonce,
+            bet_amount,
+            max_payout,
+            randomness_account,
+        });
+        Ok(())
+    }
+}
+
+// Commit the player's next action and the randomness it will draw from
+#[derive(Accounts)]
+#[instruction(game_nonce: u64)]
+pub struct RequestBlackjackAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state", [GameType::Blackjack as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = blackjack_game.table)]
+    pub blackjack_table: Account<'info, BlackjackTable>,
+
+    #[account(
+        mut,
+        seeds = [b"blackjack_game", user.key().as_ref(), &game_nonce.to_le_bytes()],
+        bump = blackjack_game.bump
+    )]
+    pub blackjack_game: Account<'info, BlackjackGame>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    // The player, or one of their session keys
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: The account's data is validated manually within the handler.
+    pub randomness_account_data: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl <'info> RequestBlackjackAction<'info> {
+    pub fn request_blackjack_action(&mut self, _game_nonce: u64, action: BlackjackAction, randomness_account: Pubkey) -> Result<()> {
+        let game = &mut self.blackjack_game;
+
+        require!(game.phase == BlackjackPhase::PlayerTurn, CasinoError::BlackjackGameNotInProgress);
+
+        let hand_count = game.player_hands.len();
+        let active_hand = game.active_hand as usize;
+        let hand = &mut game.player_hands[active_hand];
+        require!(hand.status == BlackjackHandStatus::Playing, CasinoError::InvalidBlackjackAction);
+
+        // Validate the action against the hand and collect any extra stake it needs
+        let extra_stake = match action {
+            BlackjackAction::Hit | BlackjackAction::Stand => 0,
+            BlackjackAction::DoubleDown => {
+                require!(hand.cards.len() == 2, CasinoError::InvalidBlackjackAction);
+                let extra = hand.bet_amount;
+                hand.bet_amount += extra;
+                extra
+            },
+            BlackjackAction::Split => {
+                require!(hand_count < MAX_BLACKJACK_HANDS, CasinoError::InvalidBlackjackAction);
+                require!(
+                    hand.cards.len() == 2 && card_value(hand.cards[0]) == card_value(hand.cards[1]),
+                    CasinoError::InvalidBlackjackAction
+                );
+                hand.bet_amount
+            },
+            BlackjackAction::Surrender => {
+                require!(self.blackjack_table.rules.surrender_allowed, CasinoError::BlackjackRuleDisabled);
+                require!(hand_count == 1 && hand.cards.len() == 2, CasinoError::InvalidBlackjackAction);
+                0
+            },
+        };
+
+        // Parse and validate Switchboard randomness data
+        let randomness_data = crate::utils::vrf::parse_randomness_data(&self.randomness_account_data)?;
+        let clock = Clock::get()?;
+
+        // Ensure randomness is from the previous slot
+        require!(
+            randomness_data.seed_slot == clock.slot - 1,
+            CasinoError::VrfRequestFailed
+        );
+
+        // Validate the signer is the player or a session key allowed this game and stake
+        crate::instructions::session::authorize_play(&self.user, &self.signer, self.session_key.as_mut(), GameType::Blackjack, extra_stake)?;
+
+        if extra_stake > 0 {
+            // Validate the extra stake against the player's responsible-gaming limits
+            crate::instructions::limits::enforce_player_limits(&self.player_limits, extra_stake, clock.unix_timestamp)?;
+
+            let max_payout = calculate_blackjack_max_payout(game.total_wagered + extra_stake, game.insurance_bet)?;
+            reserve_liability(&mut self.casino_state, self.casino_vault.amount, game, max_payout)?;
+
+            crate::instructions::session::take_player_stake(
+                &self.user,
+                self.session_key.as_ref(),
+                &self.user_token_account,
+                &self.casino_vault,
+                &self.usdc_mint,
+                &self.token_program,
+                extra_stake,
+            )?;
+
+            game.total_wagered += extra_stake;
+            self.user_stats.total_bets += extra_stake;
+            crate::instructions::vip::record_volume(&mut self.user_stats, extra_stake, Clock::get()?.unix_timestamp);
+            self.casino_state.total_volume += extra_stake;
+            self.casino_state.pending_stakes += extra_stake;
+            self.game_state.total_bets += extra_stake;
+        }
+
+        game.pending_action = Some(action.clone());
+        game.randomness_account = randomness_account;
+        game.commit_slot = randomness_data.seed_slot;
+        game.phase = BlackjackPhase::ActionPending;
+
+        emit!(BlackjackActionRequested {
+            version: EVENT_VERSION,
+            slot: clock.slot,
+            user: self.user.key(),
+            game: game.key(),
+            nonce: game.nonce,
+            hand: game.active_hand,
+            action,
+            extra_stake,
+            randomness_account,
+        });
+        Ok(())
+    }
+}
+
+// Take insurance against a dealer ace (half the original stake, pays 2:1)
+#[derive(Accounts)]
+#[instruction(game_nonce: u64)]
+pub struct PlaceBlackjackInsurance<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state", [GameType::Blackjack as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = blackjack_game.table)]
+    pub blackjack_table: Account<'info, BlackjackTable>,
+
+    #[account(
+        mut,
+        seeds = [b"blackjack_game", user.key().as_ref(), &game_nonce.to_le_bytes()],
+        bump = blackjack_game.bump
+    )]
+    pub blackjack_game: Account<'info, BlackjackGame>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    // The player, or one of their session keys
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl <'info> PlaceBlackjackInsurance<'info> {
+    pub fn place_blackjack_insurance(&mut self, _game_nonce: u64) -> Result<()> {
+        let game = &mut self.blackjack_game;
+
+        require!(self.blackjack_table.rules.insurance_allowed, CasinoError::BlackjackRuleDisabled);
+        require!(game.phase == BlackjackPhase::PlayerTurn, CasinoError::BlackjackGameNotInProgress);
+
+        // Only before the player acts, and only against a dealer ace
+        require!(game.insurance_bet == 0, CasinoError::InvalidBlackjackAction);
+        require!(
+            game.player_hands.len() == 1 && game.player_hands[0].cards.len() == 2,
+            CasinoError::InvalidBlackjackAction
+        );
+        require!(game.dealer_hand.first() == Some(&1), CasinoError::InvalidBlackjackAction);
+
+        let insurance_bet = mul_div(game.bet_amount, 1, 2)?;
+        require!(insurance_bet > 0, CasinoError::InvalidBetAmount);
+
+        // Validate the signer is the player or a session key allowed this game and stake
+        crate::instructions::session::authorize_play(&self.user, &self.signer, self.session_key.as_mut(), GameType::Blackjack, insurance_bet)?;
+
+        // Validate the insurance stake against the player's responsible-gaming limits
+        crate::instructions::limits::enforce_player_limits(&self.player_limits, insurance_bet, Clock::get()?.unix_timestamp)?;
+
+        let max_payout = calculate_blackjack_max_payout(game.total_wagered + insurance_bet, insurance_bet)?;
+        reserve_liability(&mut self.casino_state, self.casino_vault.amount, game, max_payout)?;
+
+        crate::instructions::session::take_player_stake(
+            &self.user,
+            self.session_key.as_ref(),
+            &self.user_token_account,
+            &self.casino_vault,
+            &self.usdc_mint,
+            &self.token_program,
+            insurance_bet,
+        )?;
+
+        game.insurance_bet = insurance_bet;
+        game.total_wagered += insurance_bet;
+        self.user_stats.total_bets += insurance_bet;
+        crate::instructions::vip::record_volume(&mut self.user_stats, insurance_bet, Clock::get()?.unix_timestamp);
+        self.casino_state.total_volume += insurance_bet;
+        self.casino_state.pending_stakes += insurance_bet;
+        self.game_state.total_bets += insurance_bet;
+
+        emit!(BlackjackInsurancePlaced {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            game: game.key(),
+            nonce: game.nonce,
+            insurance_bet,
+        });
+        Ok(())
+    }
+}
+
+// Reveal the committed randomness, draw the cards and resolve the round if it is over
+#[derive(Accounts)]
+#[instruction(game_nonce: u64, epoch: u64)]
+pub struct SettleBlackjack<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state", [GameType::Blackjack as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = casino_state.fee_vault_bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = FeeEpoch::DISCRIMINATOR.len() + FeeEpoch::INIT_SPACE,
+        seeds = [b"fee_epoch", epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub fee_epoch: Account<'info, FeeEpoch>,
+
+    #[account(address = blackjack_game.table)]
+    pub blackjack_table: Account<'info, BlackjackTable>,
+
+    #[account(
+        mut,
+        seeds = [b"blackjack_game", user.key().as_ref(), &game_nonce.to_le_bytes()],
+        bump = blackjack_game.bump
+    )]
+    pub blackjack_game: Account<'info, BlackjackGame>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        seeds = [b"vip_config"],
+        bump = vip_config.bump
+    )]
+    pub vip_config: Option<Account<'info, VipConfig>>,
+
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+
+    /// CHECK: The player's Referral PDA, credited only once the player has set a referrer.
+    #[account(
+        seeds = [b"referral", user.key().as_ref()],
+        bump
+    )]
+    pub referral: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Option<Account<'info, Tournament>>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament_entry", tournament_entry.tournament.as_ref(), user.key().as_ref()],
+        bump = tournament_entry.bump
+    )]
+    pub tournament_entry: Option<Account<'info, TournamentEntry>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = GameHistory::DISCRIMINATOR.len() + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", user.key().as_ref()],
+        bump
+    )]
+    pub game_history: Account<'info, GameHistory>,
+
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    // The player, or one of their session keys
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: The account's data is validated manually within the handler.
+    pub randomness_account_data: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl <'info> SettleBlackjack<'info> {
+    pub fn settle_blackjack(&mut self, _game_nonce: u64, epoch: u64, bumps: &SettleBlackjackBumps, remaining_accounts: &[AccountInfo]) -> Result<()> {
+        let game_key = self.blackjack_game.key();
+        let game = &mut self.blackjack_game;
+        let rules = self.blackjack_table.rules.clone();
+
+        // Validate game state
+        require!(
+            game.phase == BlackjackPhase::Dealing || game.phase == BlackjackPhase::ActionPending,
+            CasinoError::InvalidGameState
+        );
+
+        require!(
+            game.randomness_account == self.randomness_account_data.key(),
+            CasinoError::VrfRequestFailed
+        );
+
+        // Parse and validate Switchboard randomness data
+        let randomness_data = crate::utils::vrf::parse_randomness_data(&self.randomness_account_data)?;
+
+        // Validate randomness timing
+        crate::utils::vrf::validate_randomness_timing(&randomness_data, game.commit_slot)?;
+
+        // Get revealed randomness
+        let randomness_bytes = crate::utils::vrf::get_revealed_randomness(&randomness_data)?;
+        let mut draws = CardDraws::new(game_key, &randomness_bytes);
+
+        if game.phase == BlackjackPhase::Dealing {
+            deal_opening_cards(game, &mut draws);
+        } else {
+            let action = game.pending_action.take().ok_or(CasinoError::InvalidGameState)?;
+            apply_player_action(game, action, &mut draws);
+        }
+
+        // Move to the next unfinished hand, or play out the dealer once every hand is done
+        if let Some(next_hand) = game.player_hands.iter().position(|hand| hand.status == BlackjackHandStatus::Playing) {
+            game.active_hand = next_hand as u8;
+            game.phase = BlackjackPhase::PlayerTurn;
+
+            // Validate the signer may settle for the player (finish_settle does this once the round is over)
+            crate::instructions::settle::authorize_settle_signer(&self.casino_state, &self.user, &self.signer, self.session_key.as_ref())?;
+
+            let record = draws.record(game, game_key, randomness_bytes);
+            crate::instructions::settle::keep_game_proof(&mut self.game_history, self.user.key(), bumps.game_history, record, 0)?;
+
+            emit!(BlackjackCardsDrawn {
+                version: EVENT_VERSION,
+                slot: Clock::get()?.slot,
+                user: self.user.key(),
+                game: game_key,
+                nonce: game.nonce,
+                active_hand: game.active_hand,
+                player_hands: game.player_hands.clone(),
+                dealer_hand: game.dealer_hand.clone(),
+            });
+            return Ok(());
+        }
+
+        play_out_dealer(game, &rules, &mut draws);
+        let proof = draws.record(game, game_key, randomness_bytes);
+        let payout = resolve_payout(game)?;
+
+        game.payout = payout;
+        game.phase = BlackjackPhase::Finished;
+        game.is_complete = true;
+
+        // Book the round: stats, rakeback, limits, tournament, payout, fees and proof
+        let settled = crate::instructions::settle::SettledGame {
+            game_type: GameType::Blackjack,
+            wagered: game.total_wagered,
+            payout,
+            max_payout: game.max_payout,
+            rtp_bps: self.game_state.rtp_bps,
+            record: proof,
+        };
+        let fee = crate::instructions::settle::SettleAccounts {
+            casino_state: &mut self.casino_state,
+            casino_vault: &self.casino_vault,
+            game_state: &mut self.game_state,
+            fee_vault: &self.fee_vault,
+            fee_epoch: &mut self.fee_epoch,
+            user_stats: &mut self.user_stats,
+            vip_config: self.vip_config.as_deref(),
+            player_limits: &self.player_limits,
+            referral: &self.referral,
+            tournament: self.tournament.as_mut(),
+            tournament_entry: self.tournament_entry.as_mut(),
+            game_history: &mut self.game_history,
+            user: &self.user,
+            signer: &self.signer,
+            session_key: self.session_key.as_ref(),
+            user_token_account: &self.user_token_account,
+            usdc_mint: &self.usdc_mint,
+            token_program: &self.token_program,
+            epoch,
+            fee_epoch_bump: bumps.fee_epoch,
+            game_history_bump: bumps.game_history,
+        }.finish_settle(settled, remaining_accounts)?;
+
+        emit!(BlackjackSettled {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            game: game_key,
+            nonce: self.blackjack_game.nonce,
+            player_hands: self.blackjack_game.player_hands.clone(),
+            dealer_hand: self.blackjack_game.dealer_hand.clone(),
+            total_wagered: self.blackjack_game.total_wagered,
+            insurance_bet: self.blackjack_game.insurance_bet,
+            payout,
+            fee,
+        });
+
+        // Return the rent now that the round is over
+        self.blackjack_game.close(self.user.to_account_info())
+    }
+}
+
+// Sequential card draws (1-13, infinite deck) from one revealed randomness value
+pub(crate) struct CardDraws<'a> {
+    stream: crate::utils::randomness::RandomnessStream<'a>,
+}
+
+impl<'a> CardDraws<'a> {
+    pub(crate) fn new(game: Pubkey, randomness_bytes: &'a [u8]) -> Self {
+        Self {
+            stream: crate::utils::randomness::RandomnessStream::new(
+                crate::utils::randomness::DOMAIN_BLACKJACK_CARD,
+                game.to_bytes(),
+                randomness_bytes,
+            ),
+        }
+    }
+
+    fn next_card(&mut self) -> u8 {
+        crate::replay::next_card(&mut self.stream)
+    }
+
+    // Record of the cards drawn so far in this settle round, for verify::verify_outcome
+    fn record(&self, game: &BlackjackGame, game_key: Pubkey, randomness: [u8; 32]) -> crate::verify::GameRecord {
+        crate::verify::GameRecord {
+            game: game_key,
+            nonce: game.nonce,
+            bet_amount: game.total_wagered,
+            inputs: crate::verify::GameInputs::BlackjackDraw { cards: self.stream.draws_taken() as u8 },
+            randomness,
+        }
+    }
+}
+
+pub(crate) fn deal_opening_cards(game: &mut BlackjackGame, draws: &mut CardDraws) {
+    let first_card = draws.next_card();
+    let dealer_up_card = draws.next_card();
+    let second_card = draws.next_card();
+
+    let cards = vec![first_card, second_card];
+    let status = if is_blackjack(&cards) {
+        BlackjackHandStatus::Blackjack
+    } else {
+        BlackjackHandStatus::Playing
+    };
+
+    game.player_hands = vec![BlackjackHand {
+        cards,
+        bet_amount: game.bet_amount,
+        status,
+        is_split: false,
+    }];
+    game.dealer_hand = vec![dealer_up_card];
+}
+
+pub(crate) fn apply_player_action(game: &mut BlackjackGame, action: BlackjackAction, draws: &mut CardDraws) {
+    let active_hand = game.active_hand as usize;
+    let hand = &mut game.player_hands[active_hand];
+
+    match action {
+        BlackjackAction::Hit => {
+            hand.cards.push(draws.next_card());
+            update_hand_status(hand);
+        },
+        BlackjackAction::Stand => {
+            hand.status = BlackjackHandStatus::Stand;
+        },
+        BlackjackAction::DoubleDown => {
+            // Stake was doubled at request time; exactly one more card
+            hand.cards.push(draws.next_card());
+            update_hand_status(hand);
+            if hand.status == BlackjackHandStatus::Playing {
+                hand.status = BlackjackHandStatus::Stand;
+            }
+        },
+        BlackjackAction::Split => {
+            let split_aces = hand.cards[0] == 1;
+            let split_card = hand.cards.pop().unwrap_or_default();
+            hand.is_split = true;
+            hand.cards.push(draws.next_card());
+
+            let mut new_hand = BlackjackHand {
+                cards: vec![split_card, draws.next_card()],
+                bet_amount: hand.bet_amount,
+                status: BlackjackHandStatus::Playing,
+                is_split: true,
+            };
+
+            // Split aces receive one card each
+            if split_aces {
+                hand.status = BlackjackHandStatus::Stand;
+                new_hand.status = BlackjackHandStatus::Stand;
+            } else {
+                update_hand_status(hand);
+                update_hand_status(&mut new_hand);
+            }
+
+            game.player_hands.push(new_hand);
+        },
+        BlackjackAction::Surrender => {
+            hand.status = BlackjackHandStatus::Surrendered;
+        },
+    }
+}
+
+// Stand every hand still in play on a round the player abandoned, dropping its pending action.
+// The extra stake of a dropped double or split is forfeited: the hand stands on its original
+// stake without the card it paid for.
+pub(crate) fn stand_abandoned_hands(game: &mut BlackjackGame) -> Option<BlackjackAction> {
+    let dropped_action = game.pending_action.take();
+    if dropped_action == Some(BlackjackAction::DoubleDown) {
+        let hand = &mut game.player_hands[game.active_hand as usize];
+        hand.bet_amount /= 2;
+    }
+
+    for hand in game.player_hands.iter_mut() {
+        if hand.status == BlackjackHandStatus::Playing {
+            hand.status = BlackjackHandStatus::Stand;
+        }
+    }
+    dropped_action
+}
+
+// Stop a hand once it busts or reaches 21
+fn update_hand_status(hand: &mut BlackjackHand) {
+    let value = calculate_hand_value(&hand.cards);
+    if value > BLACKJACK_VALUE {
+        hand.status = BlackjackHandStatus::Busted;
+    } else if value == BLACKJACK_VALUE {
+        hand.status = BlackjackHandStatus::Stand;
+    }
+}
+
+pub(crate) fn play_out_dealer(game: &mut BlackjackGame, rules: &BlackjackRules, draws: &mut CardDraws) {
+    let has_live_hand = game.player_hands.iter().any(|hand| {
+        matches!(
+            hand.status,
+            BlackjackHandStatus::Stand | BlackjackHandStatus::Blackjack | BlackjackHandStatus::Surrendered
+        )
+    });
+
+    // Nothing left for the dealer to beat or insure against
+    if !has_live_hand && game.insurance_bet == 0 {
+        game.dealer_status = BlackjackHandStatus::Stand;
+        return;
+    }
+
+    // Hole card decides naturals, insurance and surrender
+    game.dealer_hand.push(draws.next_card());
+    if is_blackjack(&game.dealer_hand) {
+        game.dealer_status = BlackjackHandStatus::Blackjack;
+        return;
+    }
+
+    // Only hands standing on a total need the dealer to finish drawing
+    let must_draw = game.player_hands.iter().any(|hand| hand.status == BlackjackHandStatus::Stand);
+    while must_draw && should_dealer_hit(&game.dealer_hand, rules.dealer_hits_soft_17) {
+        game.dealer_hand.push(draws.next_card());
+    }
+
+    game.dealer_status = if calculate_hand_value(&game.dealer_hand) > BLACKJACK_VALUE {
+        BlackjackHandStatus::Busted
+    } else {
+        BlackjackHandStatus::Stand
+    };
+}
+
+// Gross amount owed to the player for every stake on the table
+pub(crate) fn resolve_payout(game: &BlackjackGame) -> Result<u64> {
+    let dealer_natural = game.dealer_status == BlackjackHandStatus::Blackjack;
+
+    let mut payout = 0u64;
+    for hand in game.player_hands.iter() {
+        let hand_payout = match hand.status {
+            BlackjackHandStatus::Surrendered if dealer_natural => 0,
+            BlackjackHandStatus::Surrendered => mul_div(hand.bet_amount, 1, 2)?,
+            _ => calculate_blackjack_payout(&hand.cards, &game.dealer_hand, hand.bet_amount, hand.is_split)?,
+        };
+        payout = checked_add(payout, hand_payout)?;
+    }
+
+    if dealer_natural {
+        // Dealer blackjack only takes the original stake
+        let hand_stakes = game.player_hands.iter().try_fold(0u64, |total, hand| checked_add(total, hand.bet_amount))?;
+        payout = checked_add(payout, hand_stakes - game.bet_amount)?;
+
+        // Insurance pays 2:1
+        let insurance_payout = checked_add(game.insurance_bet, mul_div(game.insurance_bet, INSURANCE_PAYOUT, 100)?)?;
+        payout = checked_add(payout, insurance_payout)?;
+    }
+
+    Ok(payout)
+}
+
+// Move a stake from the player into the casino vault
+// Reserve the growth in a round's worst-case payout when the player adds stake
+fn reserve_liability(casino_state: &mut CasinoState, vault_balance: u64, game: &mut BlackjackGame, max_payout: u64) -> Result<()> {
+    let additional_liability = max_payout.saturating_sub(game.max_payout);
+    crate::utils::bankroll::validate_exposure(
+        casino_state.outstanding_liabilities,
+        additional_liability,
+        vault_balance,
+        casino_state.max_exposure_bps
+    )?;
+    casino_state.outstanding_liabilities += additional_liability;
+    game.max_payout = max_payout;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BET: u64 = 1_000;
+
+    fn hand(cards: &[u8], bet_amount: u64, status: BlackjackHandStatus, is_split: bool) -> BlackjackHand {
+        BlackjackHand { cards: cards.to_vec(), bet_amount, status, is_split }
+    }
+
+    fn game(player_hands: Vec<BlackjackHand>, dealer_hand: &[u8], insurance_bet: u64) -> BlackjackGame {
+        let dealer_status = if is_blackjack(&dealer_hand.to_vec()) {
+            BlackjackHandStatus::Blackjack
+        } else if calculate_hand_value(&dealer_hand.to_vec()) > BLACKJACK_VALUE {
+            BlackjackHandStatus::Busted
+        } else {
+            BlackjackHandStatus::Stand
+        };
+        let hand_stakes: u64 = player_hands.iter().map(|hand| hand.bet_amount).sum();
+        BlackjackGame {
+            user: Pubkey::default(),
+            table: Pubkey::default(),
+            nonce: 0,
+            bet_amount: BET,
+            total_wagered: hand_stakes + insurance_bet,
+            max_payout: 0,
+            insurance_bet,
+            player_hands,
+            active_hand: 0,
+            dealer_hand: dealer_hand.to_vec(),
+            dealer_status,
+            phase: BlackjackPhase::Finished,
+            pending_action: None,
+            randomness_account: Pubkey::default(),
+            commit_slot: 0,
+            payout: 0,
+            is_complete: true,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn naturals_pay_three_to_two_and_push_against_each_other() {
+        assert_eq!(calculate_blackjack_payout(&vec![1, 13], &vec![10, 9], BET, false).unwrap(), 2_500);
+        assert_eq!(calculate_blackjack_payout(&vec![1, 13], &vec![1, 12], BET, false).unwrap(), BET);
+        assert_eq!(calculate_blackjack_payout(&vec![10, 9], &vec![10, 7], BET, false).unwrap(), 2_000);
+        assert_eq!(calculate_blackjack_payout(&vec![10, 7], &vec![10, 7], BET, false).unwrap(), BET);
+        assert_eq!(calculate_blackjack_payout(&vec![10, 6, 9], &vec![10, 6, 8], BET, false).unwrap(), 0);
+    }
+
+    #[test]
+    fn doubled_hand_pays_on_the_doubled_stake() {
+        let doubled = game(vec![hand(&[5, 6, 10], 2 * BET, BlackjackHandStatus::Stand, false)], &[10, 10], 0);
+        assert_eq!(resolve_payout(&doubled).unwrap(), 4 * BET);
+
+        let busted = game(vec![hand(&[6, 6, 10], 2 * BET, BlackjackHandStatus::Busted, false)], &[10, 6, 10], 0);
+        assert_eq!(resolve_payout(&busted).unwrap(), 0);
+    }
+
+    #[test]
+    fn split_hands_settle_independently_and_never_pay_as_naturals() {
+        let split = game(vec![
+            hand(&[8, 3, 8], BET, BlackjackHandStatus::Stand, true),
+            hand(&[8, 10, 5], BET, BlackjackHandStatus::Busted, true),
+        ], &[10, 8], 0);
+        assert_eq!(resolve_payout(&split).unwrap(), 2 * BET);
+
+        // A split ace and a ten is a plain 21: 1:1, and a push against a dealer 21
+        let split_aces = game(vec![
+            hand(&[1, 13], BET, BlackjackHandStatus::Stand, true),
+            hand(&[1, 9], BET, BlackjackHandStatus::Stand, true),
+        ], &[10, 9], 0);
+        assert_eq!(resolve_payout(&split_aces).unwrap(), 4 * BET);
+        assert_eq!(calculate_blackjack_payout(&vec![1, 13], &vec![10, 5, 6], BET, true).unwrap(), BET);
+    }
+
+    #[test]
+    fn split_aces_take_one_card_each() {
+        let mut round = game(vec![hand(&[1, 1], BET, BlackjackHandStatus::Playing, false)], &[6], 0);
+        let randomness = [7u8; 32];
+        let mut draws = CardDraws::new(Pubkey::default(), &randomness);
+        apply_player_action(&mut round, BlackjackAction::Split, &mut draws);
+
+        assert_eq!(round.player_hands.len(), 2);
+        for split_hand in round.player_hands.iter() {
+            assert_eq!(split_hand.cards.len(), 2);
+            assert_eq!(split_hand.cards[0], 1);
+            assert!(split_hand.is_split);
+            assert!(split_hand.status == BlackjackHandStatus::Stand);
+            assert_eq!(split_hand.bet_amount, BET);
+        }
+    }
+
+    #[test]
+    fn dealer_natural_only_takes_the_original_stake() {
+        // Doubled: the extra stake comes back
+        let doubled = game(vec![hand(&[5, 6, 9], 2 * BET, BlackjackHandStatus::Stand, false)], &[1, 13], 0);
+        assert_eq!(resolve_payout(&doubled).unwrap(), BET);
+
+        // Split: the second hand's stake comes back
+        let split = game(vec![
+            hand(&[9, 9, 2], BET, BlackjackHandStatus::Stand, true),
+            hand(&[9, 10], BET, BlackjackHandStatus::Stand, true),
+        ], &[13, 1], 0);
+        assert_eq!(resolve_payout(&split).unwrap(), BET);
+
+        // A player natural still pushes
+        let natural = game(vec![hand(&[1, 12], BET, BlackjackHandStatus::Blackjack, false)], &[1, 13], 0);
+        assert_eq!(resolve_payout(&natural).unwrap(), BET);
+    }
+
+    #[test]
+    fn insurance_pays_two_to_one_on_a_dealer_natural_only() {
+        let insured = game(vec![hand(&[10, 9], BET, BlackjackHandStatus::Stand, false)], &[1, 10], BET / 2);
+        assert_eq!(resolve_payout(&insured).unwrap(), 3 * BET / 2);
+
+        // No dealer natural: the insurance is lost and the hand settles normally
+        let lost = game(vec![hand(&[10, 9], BET, BlackjackHandStatus::Stand, false)], &[1, 6], BET / 2);
+        assert_eq!(resolve_payout(&lost).unwrap(), 2 * BET);
+    }
+
+    #[test]
+    fn surrender_returns_half_unless_the_dealer_has_a_natural() {
+        let surrendered = game(vec![hand(&[10, 6], BET, BlackjackHandStatus::Surrendered, false)], &[10, 8], 0);
+        assert_eq!(resolve_payout(&surrendered).unwrap(), BET / 2);
+
+        let against_natural = game(vec![hand(&[10, 6], BET, BlackjackHandStatus::Surrendered, false)], &[10, 1], 0);
+        assert_eq!(resolve_payout(&against_natural).unwrap(), 0);
+    }
+
+    #[test]
+    fn abandoned_hands_stand_and_forfeit_a_pending_double() {
+        let mut doubled = game(vec![hand(&[5, 6], 2 * BET, BlackjackHandStatus::Playing, false)], &[10, 10], 0);
+        doubled.pending_action = Some(BlackjackAction::DoubleDown);
+        assert!(stand_abandoned_hands(&mut doubled) == Some(BlackjackAction::DoubleDown));
+        assert!(doubled.pending_action.is_none());
+        assert!(doubled.player_hands[0].status == BlackjackHandStatus::Stand);
+        assert_eq!(doubled.player_hands[0].cards.len(), 2);
+
+        // The extra stake stays in total_wagered but is never paid back, even on a push
+        assert_eq!(doubled.player_hands[0].bet_amount, BET);
+        assert_eq!(doubled.total_wagered, 2 * BET);
+        doubled.dealer_hand = vec![10, 1];
+        assert_eq!(resolve_payout(&doubled).unwrap(), 0);
+
+        // A split round abandoned between hands stands the unplayed hand too
+        let mut split = game(vec![
+            hand(&[8, 3, 9], BET, BlackjackHandStatus::Stand, true),
+            hand(&[8, 2], BET, BlackjackHandStatus::Playing, true),
+        ], &[10, 7], 0);
+        split.active_hand = 1;
+        assert!(stand_abandoned_hands(&mut split).is_none());
+        assert!(split.player_hands.iter().all(|hand| hand.status == BlackjackHandStatus::Stand));
+        assert_eq!(resolve_payout(&split).unwrap(), 2 * BET);
+    }
+
+    #[test]
+    fn dealer_soft_17_follows_the_table_rule() {
+        let soft_17 = vec![1, 6];
+        assert!(should_dealer_hit(&soft_17, true));
+        assert!(!should_dealer_hit(&soft_17, false));
+
+        // Hard 17 and soft 18 stand under both rules, 16 always draws
+        for hits_soft_17 in [true, false] {
+            assert!(!should_dealer_hit(&vec![10, 7], hits_soft_17));
+            assert!(!should_dealer_hit(&vec![1, 7], hits_soft_17));
+            assert!(should_dealer_hit(&vec![10, 6], hits_soft_17));
+            assert!(should_dealer_hit(&vec![1, 5], hits_soft_17));
+        }
+
+        // A soft 17 that turned hard after a hit stands
+        assert!(!should_dealer_hit(&vec![1, 6, 10], true));
+        assert!(!is_soft_hand(&[1, 6, 10]));
+    }
+}
@@ -1,10 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use crate::state::casino::*;
+use crate::state::history::GameHistory;
+use crate::state::jackpot::*;
+use crate::state::tournament::{Tournament, TournamentEntry};
+use crate::state::session::SessionKey;
+use crate::state::vip::VipConfig;
 use crate::utils::*;
+use crate::events::*;
 use crate::errors::*;
 
 
@@ -29,6 +35,17 @@ pub struct InitializeCasino<'info> {
     )]
     pub casino_vault: InterfaceAccount<'info, TokenAccount>,
     
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = casino_state,
+        token::token_program = token_program,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -42,22 +59,33 @@ impl <'info> InitializeCasino<'info> {
     pub fn initialize_casino(&mut self, bumps: &InitializeCasinoBumps) -> Result<()> {
         self.casino_state.set_inner(CasinoState {
             authority: self.authority.key(),
+            pending_authority: None,
             vault: self.casino_vault.key(),
+            fee_vault: self.fee_vault.key(),
             total_games_played: 0,
             total_volume: 0,
             total_payouts: 0,
-            house_edge_config: HouseEdgeConfig {
-                slots_rtp_bps: 9500,      // 95% RTP
-                roulette_rtp_bps: 9730,   // 97.3% RTP
-                aviator_rtp_bps: 9600,    // 96% RTP
-                blackjack_rtp_bps: 9950,   // 99.5% RTP
-                platform_fee_bps: 200,    // 2% platform fee
-            },
+            total_fees_collected: 0,
+            outstanding_liabilities: 0,
+            pending_stakes: 0,
+            rakeback_owed: 0,
+            house_edge_config: DEFAULT_HOUSE_EDGE_CONFIG,
+            referral_config: crate::state::referral::ReferralConfig::default(),
             is_active: true,
-            casino_state_bump: bumps.casino_state
+            max_pending_games: DEFAULT_MAX_PENDING_GAMES,
+            max_exposure_bps: DEFAULT_MAX_EXPOSURE_BPS,
+            fee_vault_bump: bumps.fee_vault,
+            casino_state_bump: bumps.casino_state,
+            layout_version: crate::state::migration::CASINO_STATE_VERSION,
         });
         
-        msg!("Casino initialized successfully");
+        emit!(CasinoInitialized {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            vault: self.casino_vault.key(),
+            fee_vault: self.fee_vault.key(),
+        });
         Ok(())
     }
 }
@@ -81,27 +109,85 @@ pub struct RequestSlotsGame<'info> {
     pub casino_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
-        init,
-        payer = user,
-        space = crate::utils::vrf::VrfGameState::DISCRIMINATOR.len() + crate::utils::vrf::VrfGameState::INIT_SPACE,
-        seeds = [b"vrf_game_state", user.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"game_state", [GameType::Slots as u8].as_ref()],
+        bump = game_state.bump
     )]
-    pub vrf_game_state: Account<'info, crate::utils::vrf::VrfGameState>,
+    pub game_state: Account<'info, GameState>,
+    
+    #[account(
+        seeds = [b"slots_machine", slots_machine.machine_id.to_le_bytes().as_ref()],
+        bump = slots_machine.bump
+    )]
+    pub slots_machine: Account<'info, SlotsMachineConfig>,
     
     #[account(
         init_if_needed,
-        payer = user,
+        payer = signer,
         space = UserStats::DISCRIMINATOR.len() + UserStats::INIT_SPACE,
         seeds = [b"user_stats", user.key().as_ref()],
         bump
     )]
     pub user_stats: Account<'info, UserStats>,
     
+    #[account(
+        seeds = [b"vip_config"],
+        bump = vip_config.bump
+    )]
+    pub vip_config: Option<Account<'info, VipConfig>>,
+    
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+    
+    /// CHECK: The ProgressiveJackpot PDA; once the jackpot is initialized every paid spin contributes.
+    #[account(
+        mut,
+        seeds = [b"progressive_jackpot"],
+        bump
+    )]
+    pub jackpot: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"jackpot_vault"],
+        bump
+    )]
+    pub jackpot_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = signer,
+        space = crate::utils::vrf::VrfGameState::DISCRIMINATOR.len() + crate::utils::vrf::VrfGameState::INIT_SPACE,
+        seeds = [b"vrf_game_state", user.key().as_ref(), &user_stats.game_nonce.to_le_bytes()],
+        bump
+    )]
+    pub vrf_game_state: Account<'info, crate::utils::vrf::VrfGameState>,
+    
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub user: SystemAccount<'info>,
     
+    // The player, or one of their session keys
     #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+    
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
     pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     pub usdc_mint: InterfaceAccount<'info, Mint>,
@@ -116,6 +202,7 @@ pub struct RequestSlotsGame<'info> {
 
 // Settle slots game (Phase 2: Reveal randomness and determine outcome)
 #[derive(Accounts)]
+#[instruction(game_nonce: u64, epoch: u64)]
 pub struct SettleSlotsGame<'info> {
     #[account(
         mut,
@@ -134,7 +221,37 @@ pub struct SettleSlotsGame<'info> {
     
     #[account(
         mut,
-        seeds = [b"vrf_game_state", user.key().as_ref()],
+        seeds = [b"game_state", [GameType::Slots as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = casino_state.fee_vault_bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = FeeEpoch::DISCRIMINATOR.len() + FeeEpoch::INIT_SPACE,
+        seeds = [b"fee_epoch", epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub fee_epoch: Account<'info, FeeEpoch>,
+    
+    #[account(
+        seeds = [b"slots_machine", slots_machine.machine_id.to_le_bytes().as_ref()],
+        bump = slots_machine.bump
+    )]
+    pub slots_machine: Account<'info, SlotsMachineConfig>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"vrf_game_state", user.key().as_ref(), &game_nonce.to_le_bytes()],
         bump = vrf_game_state.bump
     )]
     pub vrf_game_state: Account<'info, crate::utils::vrf::VrfGameState>,
@@ -146,10 +263,84 @@ pub struct SettleSlotsGame<'info> {
     )]
     pub user_stats: Account<'info, UserStats>,
     
+    #[account(
+        seeds = [b"vip_config"],
+        bump = vip_config.bump
+    )]
+    pub vip_config: Option<Account<'info, VipConfig>>,
+    
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+    
+    /// CHECK: The player's Referral PDA, credited only once the player has set a referrer.
+    #[account(
+        seeds = [b"referral", user.key().as_ref()],
+        bump
+    )]
+    pub referral: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Option<Account<'info, Tournament>>,
+    
+    #[account(
+        mut,
+        seeds = [b"tournament_entry", tournament_entry.tournament.as_ref(), user.key().as_ref()],
+        bump = tournament_entry.bump
+    )]
+    pub tournament_entry: Option<Account<'info, TournamentEntry>>,
+    
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = GameHistory::DISCRIMINATOR.len() + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", user.key().as_ref()],
+        bump
+    )]
+    pub game_history: Account<'info, GameHistory>,
+    
+    #[account(
+        mut,
+        seeds = [b"progressive_jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Account<'info, ProgressiveJackpot>>,
+    
+    #[account(
+        mut,
+        seeds = [b"jackpot_vault"],
+        bump
+    )]
+    pub jackpot_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub user: SystemAccount<'info>,
     
+    // The player, or one of their session keys
     #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+    
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
     pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     pub usdc_mint: InterfaceAccount<'info, Mint>,
@@ -163,16 +354,64 @@ pub struct SettleSlotsGame<'info> {
 }
 
 impl <'info> RequestSlotsGame<'info> {
-    pub fn request_slots_game(&mut self, bet_amount: u64, randomness_account: Pubkey, bumps: &RequestSlotsGameBumps) -> Result<()> {
+    pub fn request_slots_game(&mut self, bet_amount: u64, active_paylines: u32, randomness_account: Pubkey, bumps: &RequestSlotsGameBumps) -> Result<()> {
+        self.request_spin(bet_amount, active_paylines, randomness_account, false, bumps)
+    }
+    
+    // Spend one free spin redeemed from loyalty points (the house funds the stake)
+    pub fn request_free_spin(&mut self, active_paylines: u32, randomness_account: Pubkey, bumps: &RequestSlotsGameBumps) -> Result<()> {
+        require!(self.user_stats.free_spins > 0, LoyaltyError::NoFreeSpins);
+        self.user_stats.free_spins -= 1;
+        
+        let bet_amount = self.user_stats.free_spin_bet;
+        self.request_spin(bet_amount, active_paylines, randomness_account, true, bumps)
+    }
+    
+    fn request_spin(&mut self, bet_amount: u64, active_paylines: u32, randomness_account: Pubkey, is_free_spin: bool, bumps: &RequestSlotsGameBumps) -> Result<()> {
         let casino_state = &mut self.casino_state;
+        let game_state = &mut self.game_state;
         let vrf_game_state = &mut self.vrf_game_state;
         let user_stats = &mut self.user_stats;
         
-        // Validate casino is active
+        // Validate casino and game are active
         require!(casino_state.is_active, CasinoError::CasinoNotActive);
+        require!(game_state.is_active, CasinoError::GameNotActive);
+        
+        // Validate user has room for another open game
+        require!(
+            user_stats.pending_games < casino_state.max_pending_games,
+            CasinoError::TooManyPendingGames
+        );
+        
+        // Validate bet amount (the max bet is raised by the player's VIP tier)
+        user_stats.user = self.user.key();
+        let vip_tier = crate::instructions::vip::refresh_vip_tier(user_stats, self.vip_config.as_deref(), Clock::get()?.unix_timestamp)?;
+        validate_bet_amount(bet_amount, game_state.min_bet, crate::utils::vip::vip_max_bet(game_state.max_bet, vip_tier.as_ref())?)?;
+        
+        // Validate the machine is open and still certified for the configured RTP
+        let slots_machine = &self.slots_machine;
+        require!(slots_machine.is_active, CasinoError::SlotsMachineNotActive);
+        require!(
+            slots_machine.rtp_bps.abs_diff(game_state.rtp_bps) <= SLOTS_RTP_TOLERANCE_BPS,
+            CasinoError::SlotsRtpMismatch
+        );
+        
+        // Validate the selected paylines and split the bet evenly across them
+        let line_count = crate::utils::slots::validate_active_paylines(slots_machine.paylines.len(), active_paylines)?;
+        require!(bet_amount / line_count * line_count == bet_amount, CasinoError::InvalidBetAmount);
+        let max_payout = crate::utils::slots::calculate_slots_max_payout(
+            &slots_machine.paytable,
+            bet_amount / line_count,
+            line_count
+        )?;
         
-        // Validate bet amount
-        validate_bet_amount(bet_amount, 1000, 1000000)?; // Min 1 USDC, Max 1000 USDC
+        // Validate the vault can cover the worst case alongside every other pending game
+        crate::utils::bankroll::validate_exposure(
+            casino_state.outstanding_liabilities,
+            max_payout,
+            self.casino_vault.amount,
+            casino_state.max_exposure_bps
+        )?;
         
         // Parse and validate Switchboard randomness data
         let randomness_data = crate::utils::vrf::parse_randomness_data(&self.randomness_account_data)?;
@@ -184,55 +423,95 @@ impl <'info> RequestSlotsGame<'info> {
             CasinoError::VrfRequestFailed
         );
         
-        // Transfer bet amount from user to casino vault (take collateral on request)
-        let transfer_instruction = TransferChecked {
-            from: self.user_token_account.to_account_info(),
-            to: self.casino_vault.to_account_info(),
-            mint: self.usdc_mint.to_account_info(),
-            authority: self.user.to_account_info(),
-        };
+        // A free spin has no stake: nothing is taken and nothing counts as wagered
+        let wagered = if is_free_spin { 0 } else { bet_amount };
         
-        let cpi_ctx = CpiContext::new(
-            self.token_program.to_account_info(),
-            transfer_instruction,
-        );
+        // Validate the signer is the player or a session key allowed this game and stake
+        crate::instructions::session::authorize_play(&self.user, &self.signer, self.session_key.as_mut(), GameType::Slots, wagered)?;
         
-        transfer_checked(cpi_ctx, bet_amount, self.usdc_mint.decimals)?;
+        // Validate the stake against the player's responsible-gaming limits
+        crate::instructions::limits::enforce_player_limits(&self.player_limits, wagered, clock.unix_timestamp)?;
+        
+        // Transfer bet amount from user to casino vault (take collateral on request)
+        if !is_free_spin {
+            crate::instructions::session::take_player_stake(
+                &self.user,
+                self.session_key.as_ref(),
+                &self.user_token_account,
+                &self.casino_vault,
+                &self.usdc_mint,
+                &self.token_program,
+                bet_amount,
+            )?;
+        }
+        
+        // Move the progressive jackpot's slice of the stake into the jackpot vault
+        let jackpot_contribution = crate::instructions::jackpot::contribute_to_jackpot(
+            casino_state,
+            &self.jackpot,
+            self.jackpot_vault.as_ref(),
+            &self.casino_vault,
+            &self.usdc_mint,
+            &self.token_program,
+            wagered,
+        )?;
         
         // Initialize VRF game state
         vrf_game_state.set_inner(crate::utils::vrf::VrfGameState {
             user: self.user.key(),
+            nonce: user_stats.game_nonce,
             game_type: GameType::Slots,
             bet_amount,
+            max_payout,
+            is_free_spin,
+            jackpot_contribution,
+            rtp_bps: game_state.rtp_bps,
             randomness_account,
-            commit_slot: 100, //randomness_data.seed_slot,
+            commit_slot: randomness_data.seed_slot,
             game_phase: crate::utils::vrf::GamePhase::Requested,
-            game_data: Vec::new(), // Will be filled in settle phase
+            game_data: [slots_machine.key().to_bytes().as_ref(), &active_paylines.to_le_bytes()].concat(),
             payout: 0,
             is_complete: false,
             bump: bumps.vrf_game_state,
         });
         
         // Update user stats
-        user_stats.user = self.user.key();
-        user_stats.total_bets += bet_amount;
+        user_stats.total_bets += wagered;
+        crate::instructions::vip::record_volume(user_stats, wagered, clock.unix_timestamp);
         user_stats.games_played += 1;
+        user_stats.game_nonce += 1;
+        user_stats.pending_games += 1;
         user_stats.bump = bumps.user_stats;
+        user_stats.layout_version = crate::state::migration::USER_STATS_VERSION;
         
         // Update casino stats
         casino_state.total_games_played += 1;
-        casino_state.total_volume += bet_amount;
+        casino_state.total_volume += wagered;
+        casino_state.outstanding_liabilities += max_payout;
+        casino_state.pending_stakes += wagered;
+        
+        // Update game stats
+        game_state.total_games += 1;
+        game_state.total_bets += wagered;
         
-        msg!("Slots game requested: Bet: {}, Randomness Account: {}", bet_amount, randomness_account);
+        emit!(GameRequested {
+            version: EVENT_VERSION,
+            slot: clock.slot,
+            user: self.user.key(),
+            game: vrf_game_state.key(),
+            game_type: GameType::Slots,
+            nonce: vrf_game_state.nonce,
+            bet_amount,
+            max_payout,
+            randomness_account,
+        });
         Ok(())
     }
 }
 
 impl <'info> SettleSlotsGame<'info> {
-    pub fn settle_slots_game(&mut self, bumps: &SettleSlotsGameBumps) -> Result<()> {
-        let casino_state = &mut self.casino_state;
+    pub fn settle_slots_game(&mut self, _game_nonce: u64, epoch: u64, bumps: &SettleSlotsGameBumps, remaining_accounts: &[AccountInfo]) -> Result<()> {
         let vrf_game_state = &mut self.vrf_game_state;
-        let user_stats = &mut self.user_stats;
         
         // Validate game state
         require!(
@@ -251,58 +530,140 @@ impl <'info> SettleSlotsGame<'info> {
         // // Validate randomness timing
         crate::utils::vrf::validate_randomness_timing(&randomness_data, vrf_game_state.commit_slot)?;
         
-        // // Get revealed randomness
-        let randomness_bytes = crate::utils::vrf::get_revealed_randomness(&randomness_data)?;
-
-        msg!("Randomness bytes: {:?}", randomness_bytes);
-        
-        // Generate game-specific randomness
-        let reels_bytes = crate::utils::vrf::generate_game_randomness(
-            GameType::Slots,
-            &randomness_bytes
-        )?;
+        // Get revealed randomness
+        let randomness = crate::utils::vrf::get_revealed_randomness(&randomness_data)?;
         
-        // Convert to reels array
-        let reels = [reels_bytes[0], reels_bytes[1], reels_bytes[2]];
+        // Derive the outcome from the committed inputs exactly as an off-chain verifier would
+        let record = crate::verify::GameRecord {
+            game: vrf_game_state.key(),
+            nonce: vrf_game_state.nonce,
+            bet_amount: vrf_game_state.bet_amount,
+            inputs: crate::verify::decode_inputs(&vrf_game_state.game_type, &vrf_game_state.game_data, vrf_game_state.rtp_bps)?,
+            randomness,
+        };
         
-        // Calculate payout
-        let payout = calculate_slots_payout(reels, vrf_game_state.bet_amount);
+        // Validate the spin is settled on the machine it was placed on
+        let slots_machine = &self.slots_machine;
+        let active_paylines = match &record.inputs {
+            crate::verify::GameInputs::Slots { machine, active_paylines } if *machine == slots_machine.key() => *active_paylines,
+            _ => return err!(CasinoError::InvalidGameState),
+        };
         
-        // Apply house edge
-        let rtp_multiplier = casino_state.house_edge_config.slots_rtp_bps as f64 / 10000.0;
-        let final_payout = (payout as f64 * rtp_multiplier) as u64;
+        // Land every reel on a weighted stop (the house edge is built into the strips and paytable)
+        let crate::verify::Outcome::Slots { window, payout: final_payout, .. } =
+            crate::verify::verify_outcome(&record, &randomness, Some(slots_machine))? else {
+            return err!(CasinoError::InvalidGameState);
+        };
         
         // Update VRF game state
-        vrf_game_state.game_data = reels.to_vec();
         vrf_game_state.payout = final_payout;
         vrf_game_state.game_phase = crate::utils::vrf::GamePhase::Settled;
         vrf_game_state.is_complete = true;
         
-        // Update user stats
-        if final_payout > 0 {
-            user_stats.total_wins += final_payout;
-            user_stats.loyalty_points += vrf_game_state.bet_amount / 100;
-        } else {
-            user_stats.total_losses += vrf_game_state.bet_amount;
+        // Book the game: stats, rakeback, limits, tournament, payout, fees and proof
+        let settled = crate::instructions::settle::SettledGame {
+            game_type: vrf_game_state.game_type.clone(),
+            wagered: vrf_game_state.wagered(),
+            payout: final_payout,
+            max_payout: vrf_game_state.max_payout,
+            rtp_bps: vrf_game_state.rtp_bps,
+            record,
+        };
+        let fee = crate::instructions::settle::SettleAccounts {
+            casino_state: &mut self.casino_state,
+            casino_vault: &self.casino_vault,
+            game_state: &mut self.game_state,
+            fee_vault: &self.fee_vault,
+            fee_epoch: &mut self.fee_epoch,
+            user_stats: &mut self.user_stats,
+            vip_config: self.vip_config.as_deref(),
+            player_limits: &self.player_limits,
+            referral: &self.referral,
+            tournament: self.tournament.as_mut(),
+            tournament_entry: self.tournament_entry.as_mut(),
+            game_history: &mut self.game_history,
+            user: &self.user,
+            signer: &self.signer,
+            session_key: self.session_key.as_ref(),
+            user_token_account: &self.user_token_account,
+            usdc_mint: &self.usdc_mint,
+            token_program: &self.token_program,
+            epoch,
+            fee_epoch_bump: bumps.fee_epoch,
+            game_history_bump: bumps.game_history,
+        }.finish_settle(settled, remaining_accounts)?;
+        
+        // Pay the progressive jackpot on the top combination or a jackpot roll
+        self.settle_jackpot(active_paylines, &window, &randomness)?;
+        
+        emit!(SlotsSettled {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            game: self.vrf_game_state.key(),
+            nonce: self.vrf_game_state.nonce,
+            machine: self.slots_machine.key(),
+            bet_amount: self.vrf_game_state.bet_amount,
+            active_paylines,
+            window,
+            payout: final_payout,
+            fee,
+        });
+        Ok(())
+    }
+    
+    fn settle_jackpot(&mut self, active_paylines: u32, window: &[u8], randomness: &[u8]) -> Result<()> {
+        let (Some(jackpot), Some(jackpot_vault)) = (self.jackpot.as_mut(), self.jackpot_vault.as_ref()) else {
+            return Ok(());
+        };
+        
+        // Only spins that paid into the pot can win it
+        if self.vrf_game_state.jackpot_contribution == 0 {
+            return Ok(());
         }
         
-        // Update casino stats
-        casino_state.total_payouts += final_payout;
-        
-        // Transfer payout to user if they won
-        if final_payout > 0 {
-            let payout_instruction = TransferChecked {
-                from: self.casino_vault.to_account_info(),
-                to: self.user_token_account.to_account_info(),
-                authority: casino_state.to_account_info(),
-                mint: self.usdc_mint.to_account_info(),
-            };
-            
-            let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[self.casino_state.casino_state_bump]]];
-            let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), payout_instruction, seeds);
-            transfer_checked(cpi_ctx, final_payout, self.usdc_mint.decimals)?;
+        let game = self.vrf_game_state.key();
+        let trigger = if crate::utils::jackpot::hits_top_combination(
+            &self.slots_machine.paylines,
+            window,
+            active_paylines,
+            jackpot.jackpot_symbol,
+            jackpot.jackpot_run
+        ) {
+            JackpotTrigger::TopCombination
+        } else if crate::utils::jackpot::jackpot_roll_hits(game, randomness, jackpot.roll_odds) {
+            JackpotTrigger::Roll
+        } else {
+            return Ok(());
+        };
+        
+        // The winner takes everything above the reseed
+        let amount = jackpot_vault.amount.saturating_sub(jackpot.reseed_amount);
+        if amount == 0 {
+            return Ok(());
         }
-        Ok(())
+        
+        let clock = Clock::get()?;
+        crate::instructions::jackpot::pay_jackpot(
+            &self.casino_state,
+            jackpot,
+            jackpot_vault,
+            &self.user_token_account,
+            &self.usdc_mint,
+            &self.token_program,
+            JackpotWin {
+                user: self.user.key(),
+                game,
+                amount,
+                trigger,
+                slot: clock.slot,
+                timestamp: clock.unix_timestamp,
+            },
+        )?;
+        
+        // Count the jackpot as a player win and credit it against their loss limits
+        self.user_stats.total_wins += amount;
+        crate::instructions::limits::credit_player_limits(&self.player_limits, 0, amount, clock.unix_timestamp)
     }
 }
 
@@ -325,27 +686,64 @@ pub struct RequestRouletteGame<'info> {
     pub casino_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
-        init,
-        payer = user,
-        space = crate::utils::vrf::VrfGameState::DISCRIMINATOR.len() + crate::utils::vrf::VrfGameState::INIT_SPACE,
-        seeds = [b"vrf_game_state", user.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"game_state", [GameType::Roulette as u8].as_ref()],
+        bump = game_state.bump
     )]
-    pub vrf_game_state: Account<'info, crate::utils::vrf::VrfGameState>,
+    pub game_state: Account<'info, GameState>,
     
     #[account(
         init_if_needed,
-        payer = user,
+        payer = signer,
         space = UserStats::DISCRIMINATOR.len() + UserStats::INIT_SPACE,
         seeds = [b"user_stats", user.key().as_ref()],
         bump
     )]
     pub user_stats: Account<'info, UserStats>,
     
+    #[account(
+        seeds = [b"vip_config"],
+        bump = vip_config.bump
+    )]
+    pub vip_config: Option<Account<'info, VipConfig>>,
+    
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = signer,
+        space = crate::utils::vrf::VrfGameState::DISCRIMINATOR.len() + crate::utils::vrf::VrfGameState::INIT_SPACE,
+        seeds = [b"vrf_game_state", user.key().as_ref(), &user_stats.game_nonce.to_le_bytes()],
+        bump
+    )]
+    pub vrf_game_state: Account<'info, crate::utils::vrf::VrfGameState>,
+    
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub user: SystemAccount<'info>,
     
+    // The player, or one of their session keys
     #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+    
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
     pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     pub usdc_mint: InterfaceAccount<'info, Mint>,
@@ -360,6 +758,7 @@ pub struct RequestRouletteGame<'info> {
 
 // Settle roulette game (Phase 2: Reveal randomness and determine outcome)
 #[derive(Accounts)]
+#[instruction(game_nonce: u64, epoch: u64)]
 pub struct SettleRouletteGame<'info> {
     #[account(
         mut,
@@ -378,7 +777,31 @@ pub struct SettleRouletteGame<'info> {
     
     #[account(
         mut,
-        seeds = [b"vrf_game_state", user.key().as_ref()],
+        seeds = [b"game_state", [GameType::Roulette as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = casino_state.fee_vault_bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = FeeEpoch::DISCRIMINATOR.len() + FeeEpoch::INIT_SPACE,
+        seeds = [b"fee_epoch", epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub fee_epoch: Account<'info, FeeEpoch>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"vrf_game_state", user.key().as_ref(), &game_nonce.to_le_bytes()],
         bump = vrf_game_state.bump
     )]
     pub vrf_game_state: Account<'info, crate::utils::vrf::VrfGameState>,
@@ -390,10 +813,70 @@ pub struct SettleRouletteGame<'info> {
     )]
     pub user_stats: Account<'info, UserStats>,
     
+    #[account(
+        seeds = [b"vip_config"],
+        bump = vip_config.bump
+    )]
+    pub vip_config: Option<Account<'info, VipConfig>>,
+    
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+    
+    /// CHECK: The player's Referral PDA, credited only once the player has set a referrer.
+    #[account(
+        seeds = [b"referral", user.key().as_ref()],
+        bump
+    )]
+    pub referral: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Option<Account<'info, Tournament>>,
+    
+    #[account(
+        mut,
+        seeds = [b"tournament_entry", tournament_entry.tournament.as_ref(), user.key().as_ref()],
+        bump = tournament_entry.bump
+    )]
+    pub tournament_entry: Option<Account<'info, TournamentEntry>>,
+    
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = GameHistory::DISCRIMINATOR.len() + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", user.key().as_ref()],
+        bump
+    )]
+    pub game_history: Account<'info, GameHistory>,
+    
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub user: SystemAccount<'info>,
     
+    // The player, or one of their session keys
     #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+    
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
     pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     pub usdc_mint: InterfaceAccount<'info, Mint>,
@@ -409,55 +892,61 @@ pub struct SettleRouletteGame<'info> {
 impl <'info> RequestRouletteGame<'info> {
     pub fn request_roulette_game(
         &mut self, 
-        bet_amount: u64, 
-        bet_type: RouletteBetType, 
-        numbers: Vec<u8>,
+        total_amount: u64, 
+        bets: Vec<RouletteBet>,
         randomness_account: Pubkey,
         bumps: &RequestRouletteGameBumps
     ) -> Result<()> {
         let casino_state = &mut self.casino_state;
+        let game_state = &mut self.game_state;
         let vrf_game_state = &mut self.vrf_game_state;
         let user_stats = &mut self.user_stats;
         
-        // Validate casino is active
+        // Validate casino and game are active
         require!(casino_state.is_active, CasinoError::CasinoNotActive);
+        require!(game_state.is_active, CasinoError::GameNotActive);
         
-        // Validate bet amount
-        validate_bet_amount(bet_amount, 1000, 1000000)?;
+        // Validate user has room for another open game
+        require!(
+            user_stats.pending_games < casino_state.max_pending_games,
+            CasinoError::TooManyPendingGames
+        );
         
-        // Validate bet numbers based on bet type
-        match bet_type {
-            RouletteBetType::Straight => {
-                require!(numbers.len() == 1, CasinoError::InvalidRouletteNumbers);
-                require!(numbers[0] <= 36, CasinoError::InvalidRouletteNumbers);
-            },
-            RouletteBetType::Split => {
-                require!(numbers.len() == 2, CasinoError::InvalidRouletteNumbers);
-            },
-            RouletteBetType::Street => {
-                require!(numbers.len() == 3, CasinoError::InvalidRouletteNumbers);
-            },
-            RouletteBetType::Corner => {
-                require!(numbers.len() == 4, CasinoError::InvalidRouletteNumbers);
-            },
-            RouletteBetType::Line => {
-                require!(numbers.len() == 6, CasinoError::InvalidRouletteNumbers);
-            },
-            RouletteBetType::Column => {
-                require!(numbers.len() == 12, CasinoError::InvalidRouletteNumbers);
-            },
-            RouletteBetType::Dozen => {
-                require!(numbers.len() == 12, CasinoError::InvalidRouletteNumbers);
-            },
-            _ => {
-                require!(numbers.is_empty(), CasinoError::InvalidRouletteNumbers);
-            },
+        // Validate spin total (the max bet is raised by the player's VIP tier)
+        user_stats.user = self.user.key();
+        let vip_tier = crate::instructions::vip::refresh_vip_tier(user_stats, self.vip_config.as_deref(), Clock::get()?.unix_timestamp)?;
+        validate_bet_amount(total_amount, game_state.min_bet, crate::utils::vip::vip_max_bet(game_state.max_bet, vip_tier.as_ref())?)?;
+        
+        // Validate each bet against the table layout and the spin total
+        require!(!bets.is_empty(), CasinoError::InvalidRouletteBet);
+        require!(bets.len() <= MAX_ROULETTE_BETS, CasinoError::TooManyRouletteBets);
+        let mut bets_total = 0u64;
+        for bet in bets.iter() {
+            require!(bet.amount > 0, CasinoError::InvalidBetAmount);
+            crate::utils::roulette::validate_roulette_bet(&bet.bet_type, &bet.numbers)?;
+            bets_total = checked_add(bets_total, bet.amount)?;
         }
+        require!(bets_total == total_amount, CasinoError::InvalidBetAmount);
+        let max_payout = crate::utils::roulette::max_spin_payout(&bets)?;
+        
+        // Validate the vault can cover the worst case alongside every other pending game
+        crate::utils::bankroll::validate_exposure(
+            casino_state.outstanding_liabilities,
+            max_payout,
+            self.casino_vault.amount,
+            casino_state.max_exposure_bps
+        )?;
         
         // Parse and validate Switchboard randomness data
         let randomness_data = crate::utils::vrf::parse_randomness_data(&self.randomness_account_data)?;
         let clock = Clock::get()?;
         
+        // Validate the signer is the player or a session key allowed this game and stake
+        crate::instructions::session::authorize_play(&self.user, &self.signer, self.session_key.as_mut(), GameType::Roulette, total_amount)?;
+        
+        // Validate the stake against the player's responsible-gaming limits
+        crate::instructions::limits::enforce_player_limits(&self.player_limits, total_amount, clock.unix_timestamp)?;
+        
         // Ensure randomness is from the previous slot
         require!(
             randomness_data.seed_slot == clock.slot - 1,
@@ -465,30 +954,30 @@ impl <'info> RequestRouletteGame<'info> {
         );
         
         // Transfer bet amount from user to casino vault
-        let transfer_instruction = TransferChecked {
-            from: self.user_token_account.to_account_info(),
-            to: self.casino_vault.to_account_info(),
-            authority: self.user.to_account_info(),
-            mint: self.usdc_mint.to_account_info(),
-        };
-        
-        let cpi_ctx = CpiContext::new(
-            self.token_program.to_account_info(),
-            transfer_instruction,
-        );
-        
-        transfer_checked(cpi_ctx, bet_amount, self.usdc_mint.decimals)?;
+        crate::instructions::session::take_player_stake(
+            &self.user,
+            self.session_key.as_ref(),
+            &self.user_token_account,
+            &self.casino_vault,
+            &self.usdc_mint,
+            &self.token_program,
+            total_amount,
+        )?;
         
-        // Store bet information in game data
+        // Store every bet in game data
         let mut game_data = Vec::new();
-        game_data.push(bet_type.clone() as u8);
-        game_data.extend_from_slice(&numbers);
+        bets.serialize(&mut game_data)?;
         
         // Initialize VRF game state
         vrf_game_state.set_inner(crate::utils::vrf::VrfGameState {
             user: self.user.key(),
+            nonce: user_stats.game_nonce,
             game_type: GameType::Roulette,
-            bet_amount,
+            bet_amount: total_amount,
+            max_payout,
+            is_free_spin: false,
+            jackpot_contribution: 0,
+            rtp_bps: game_state.rtp_bps,
             randomness_account,
             commit_slot: randomness_data.seed_slot,
             game_phase: crate::utils::vrf::GamePhase::Requested,
@@ -499,25 +988,42 @@ impl <'info> RequestRouletteGame<'info> {
         });
         
         // Update user stats
-        user_stats.user = self.user.key();
-        user_stats.total_bets += bet_amount;
+        user_stats.total_bets += total_amount;
+        crate::instructions::vip::record_volume(user_stats, total_amount, clock.unix_timestamp);
         user_stats.games_played += 1;
+        user_stats.game_nonce += 1;
+        user_stats.pending_games += 1;
         user_stats.bump = bumps.user_stats;
+        user_stats.layout_version = crate::state::migration::USER_STATS_VERSION;
         
         // Update casino stats
         casino_state.total_games_played += 1;
-        casino_state.total_volume += bet_amount;
+        casino_state.total_volume += total_amount;
+        casino_state.outstanding_liabilities += max_payout;
+        casino_state.pending_stakes += total_amount;
         
-        msg!("Roulette game requested successfully");
+        // Update game stats
+        game_state.total_games += 1;
+        game_state.total_bets += total_amount;
+        
+        emit!(GameRequested {
+            version: EVENT_VERSION,
+            slot: clock.slot,
+            user: self.user.key(),
+            game: vrf_game_state.key(),
+            game_type: GameType::Roulette,
+            nonce: vrf_game_state.nonce,
+            bet_amount: total_amount,
+            max_payout,
+            randomness_account,
+        });
         Ok(())
     }
 }
 
 impl <'info> SettleRouletteGame<'info> {
-    pub fn settle_roulette_game(&mut self, bumps: &SettleRouletteGameBumps) -> Result<()> {
-        let casino_state = &mut self.casino_state;
+    pub fn settle_roulette_game(&mut self, _game_nonce: u64, epoch: u64, bumps: &SettleRouletteGameBumps, remaining_accounts: &[AccountInfo]) -> Result<()> {
         let vrf_game_state = &mut self.vrf_game_state;
-        let user_stats = &mut self.user_stats;
         
         // Validate game state
         require!(
@@ -537,76 +1043,515 @@ impl <'info> SettleRouletteGame<'info> {
         crate::utils::vrf::validate_randomness_timing(&randomness_data, vrf_game_state.commit_slot)?;
         
         // Get revealed randomness
-        let randomness_bytes = crate::utils::vrf::get_revealed_randomness(&randomness_data)?;
+        let randomness = crate::utils::vrf::get_revealed_randomness(&randomness_data)?;
         
-        // Generate game-specific randomness
-        let winning_number_bytes = crate::utils::vrf::generate_game_randomness(
-            GameType::Roulette,
-            &randomness_bytes
-        )?;
-        
-        let winning_number = winning_number_bytes[0];
-        
-        // Parse stored bet information
-        let bet_type_byte = vrf_game_state.game_data[0];
-        let bet_type = match bet_type_byte {
-            0 => RouletteBetType::Straight,
-            1 => RouletteBetType::Split,
-            2 => RouletteBetType::Street,
-            3 => RouletteBetType::Corner,
-            4 => RouletteBetType::Line,
-            5 => RouletteBetType::Column,
-            6 => RouletteBetType::Dozen,
-            7 => RouletteBetType::Red,
-            8 => RouletteBetType::Black,
-            9 => RouletteBetType::Even,
-            10 => RouletteBetType::Odd,
-            11 => RouletteBetType::Low,
-            12 => RouletteBetType::High,
-            _ => return Err(CasinoError::InvalidRouletteBet.into()),
+        // Derive the outcome from the committed inputs exactly as an off-chain verifier would
+        let record = crate::verify::GameRecord {
+            game: vrf_game_state.key(),
+            nonce: vrf_game_state.nonce,
+            bet_amount: vrf_game_state.bet_amount,
+            inputs: crate::verify::decode_inputs(&vrf_game_state.game_type, &vrf_game_state.game_data, vrf_game_state.rtp_bps)?,
+            randomness,
         };
-        let bet_numbers = vrf_game_state.game_data[1..].to_vec();
         
-        // Calculate payout
-        let payout = calculate_roulette_payout(bet_type.clone(), vrf_game_state.bet_amount, winning_number);
-        
-        // Apply house edge
-        let rtp_multiplier = casino_state.house_edge_config.roulette_rtp_bps as f64 / 10000.0;
-        let final_payout = (payout as f64 * rtp_multiplier) as u64;
+        // Draw the winning number (0-36) and settle every bet against it
+        let crate::verify::Outcome::Roulette { winning_number, results, payout: final_payout } =
+            crate::verify::verify_outcome(&record, &randomness, None)? else {
+            return err!(CasinoError::InvalidGameState);
+        };
         
         // Update VRF game state
-        vrf_game_state.game_data = vec![winning_number];
         vrf_game_state.payout = final_payout;
         vrf_game_state.game_phase = crate::utils::vrf::GamePhase::Settled;
         vrf_game_state.is_complete = true;
         
+        // Book the game: stats, rakeback, limits, tournament, payout, fees and proof
+        let settled = crate::instructions::settle::SettledGame {
+            game_type: vrf_game_state.game_type.clone(),
+            wagered: vrf_game_state.wagered(),
+            payout: final_payout,
+            max_payout: vrf_game_state.max_payout,
+            rtp_bps: vrf_game_state.rtp_bps,
+            record,
+        };
+        let fee = crate::instructions::settle::SettleAccounts {
+            casino_state: &mut self.casino_state,
+            casino_vault: &self.casino_vault,
+            game_state: &mut self.game_state,
+            fee_vault: &self.fee_vault,
+            fee_epoch: &mut self.fee_epoch,
+            user_stats: &mut self.user_stats,
+            vip_config: self.vip_config.as_deref(),
+            player_limits: &self.player_limits,
+            referral: &self.referral,
+            tournament: self.tournament.as_mut(),
+            tournament_entry: self.tournament_entry.as_mut(),
+            game_history: &mut self.game_history,
+            user: &self.user,
+            signer: &self.signer,
+            session_key: self.session_key.as_ref(),
+            user_token_account: &self.user_token_account,
+            usdc_mint: &self.usdc_mint,
+            token_program: &self.token_program,
+            epoch,
+            fee_epoch_bump: bumps.fee_epoch,
+            game_history_bump: bumps.game_history,
+        }.finish_settle(settled, remaining_accounts)?;
+        
+        emit!(RouletteSettled {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            game: vrf_game_state.key(),
+            nonce: vrf_game_state.nonce,
+            bet_amount: vrf_game_state.bet_amount,
+            winning_number,
+            results,
+            payout: final_payout,
+            fee,
+        });
+        Ok(())
+    }
+}
+
+// Request aviator game (Phase 1: Commit bet, auto-cashout target and randomness)
+#[derive(Accounts)]
+pub struct RequestAviatorGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+    
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"game_state", [GameType::Aviator as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+    
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = UserStats::DISCRIMINATOR.len() + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+    
+    #[account(
+        seeds = [b"vip_config"],
+        bump = vip_config.bump
+    )]
+    pub vip_config: Option<Account<'info, VipConfig>>,
+    
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = signer,
+        space = crate::utils::vrf::VrfGameState::DISCRIMINATOR.len() + crate::utils::vrf::VrfGameState::INIT_SPACE,
+        seeds = [b"vrf_game_state", user.key().as_ref(), &user_stats.game_nonce.to_le_bytes()],
+        bump
+    )]
+    pub vrf_game_state: Account<'info, crate::utils::vrf::VrfGameState>,
+    
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+    
+    // The player, or one of their session keys
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+    
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: The account's data is validated manually within the handler.
+    pub randomness_account_data: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// Settle aviator game (Phase 2: Reveal crash point and pay if the target was reached)
+#[derive(Accounts)]
+#[instruction(game_nonce: u64, epoch: u64)]
+pub struct SettleAviatorGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+    
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"game_state", [GameType::Aviator as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = casino_state.fee_vault_bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = FeeEpoch::DISCRIMINATOR.len() + FeeEpoch::INIT_SPACE,
+        seeds = [b"fee_epoch", epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub fee_epoch: Account<'info, FeeEpoch>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"vrf_game_state", user.key().as_ref(), &game_nonce.to_le_bytes()],
+        bump = vrf_game_state.bump
+    )]
+    pub vrf_game_state: Account<'info, crate::utils::vrf::VrfGameState>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+    
+    #[account(
+        seeds = [b"vip_config"],
+        bump = vip_config.bump
+    )]
+    pub vip_config: Option<Account<'info, VipConfig>>,
+    
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+    
+    /// CHECK: The player's Referral PDA, credited only once the player has set a referrer.
+    #[account(
+        seeds = [b"referral", user.key().as_ref()],
+        bump
+    )]
+    pub referral: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Option<Account<'info, Tournament>>,
+    
+    #[account(
+        mut,
+        seeds = [b"tournament_entry", tournament_entry.tournament.as_ref(), user.key().as_ref()],
+        bump = tournament_entry.bump
+    )]
+    pub tournament_entry: Option<Account<'info, TournamentEntry>>,
+    
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = GameHistory::DISCRIMINATOR.len() + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", user.key().as_ref()],
+        bump
+    )]
+    pub game_history: Account<'info, GameHistory>,
+    
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+    
+    // The player, or one of their session keys
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"session_key", user.key().as_ref(), signer.key().as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+    
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: The account's data is validated manually within the handler.
+    pub randomness_account_data: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> RequestAviatorGame<'info> {
+    pub fn request_aviator_game(
+        &mut self,
+        bet_amount: u64,
+        target_multiplier: u64,
+        randomness_account: Pubkey,
+        bumps: &RequestAviatorGameBumps
+    ) -> Result<()> {
+        let casino_state = &mut self.casino_state;
+        let game_state = &mut self.game_state;
+        let vrf_game_state = &mut self.vrf_game_state;
+        let user_stats = &mut self.user_stats;
+        
+        // Validate casino and game are active
+        require!(casino_state.is_active, CasinoError::CasinoNotActive);
+        require!(game_state.is_active, CasinoError::GameNotActive);
+        
+        // Validate user has room for another open game
+        require!(
+            user_stats.pending_games < casino_state.max_pending_games,
+            CasinoError::TooManyPendingGames
+        );
+        
+        // Validate bet amount (the max bet is raised by the player's VIP tier)
+        user_stats.user = self.user.key();
+        let vip_tier = crate::instructions::vip::refresh_vip_tier(user_stats, self.vip_config.as_deref(), Clock::get()?.unix_timestamp)?;
+        validate_bet_amount(bet_amount, game_state.min_bet, crate::utils::vip::vip_max_bet(game_state.max_bet, vip_tier.as_ref())?)?;
+        
+        // Validate auto-cashout target (fixed-point, 100 = 1.00x)
+        require!(
+            (AVIATOR_MIN_TARGET_MULTIPLIER..=AVIATOR_MAX_TARGET_MULTIPLIER).contains(&target_multiplier),
+            CasinoError::InvalidAviatorCashout
+        );
+        let max_payout = mul_div(bet_amount, target_multiplier, AVIATOR_MULTIPLIER_SCALE)?;
+        
+        // Validate the vault can cover the worst case alongside every other pending game
+        crate::utils::bankroll::validate_exposure(
+            casino_state.outstanding_liabilities,
+            max_payout,
+            self.casino_vault.amount,
+            casino_state.max_exposure_bps
+        )?;
+        
+        // Parse and validate Switchboard randomness data
+        let randomness_data = crate::utils::vrf::parse_randomness_data(&self.randomness_account_data)?;
+        let clock = Clock::get()?;
+        
+        // Validate the signer is the player or a session key allowed this game and stake
+        crate::instructions::session::authorize_play(&self.user, &self.signer, self.session_key.as_mut(), GameType::Aviator, bet_amount)?;
+        
+        // Validate the stake against the player's responsible-gaming limits
+        crate::instructions::limits::enforce_player_limits(&self.player_limits, bet_amount, clock.unix_timestamp)?;
+        
+        // Ensure randomness is from the previous slot
+        require!(
+            randomness_data.seed_slot == clock.slot - 1,
+            CasinoError::VrfRequestFailed
+        );
+        
+        // Transfer bet amount from user to casino vault
+        crate::instructions::session::take_player_stake(
+            &self.user,
+            self.session_key.as_ref(),
+            &self.user_token_account,
+            &self.casino_vault,
+            &self.usdc_mint,
+            &self.token_program,
+            bet_amount,
+        )?;
+        
+        // Initialize VRF game state with the committed target
+        vrf_game_state.set_inner(crate::utils::vrf::VrfGameState {
+            user: self.user.key(),
+            nonce: user_stats.game_nonce,
+            game_type: GameType::Aviator,
+            bet_amount,
+            max_payout,
+            is_free_spin: false,
+            jackpot_contribution: 0,
+            rtp_bps: game_state.rtp_bps,
+            randomness_account,
+            commit_slot: randomness_data.seed_slot,
+            game_phase: crate::utils::vrf::GamePhase::Requested,
+            game_data: target_multiplier.to_le_bytes().to_vec(),
+            payout: 0,
+            is_complete: false,
+            bump: bumps.vrf_game_state,
+        });
+        
         // Update user stats
-        if final_payout > 0 {
-            user_stats.total_wins += final_payout;
-            user_stats.loyalty_points += vrf_game_state.bet_amount / 100;
-        } else {
-            user_stats.total_losses += vrf_game_state.bet_amount;
-        }
+        user_stats.total_bets += bet_amount;
+        crate::instructions::vip::record_volume(user_stats, bet_amount, clock.unix_timestamp);
+        user_stats.games_played += 1;
+        user_stats.game_nonce += 1;
+        user_stats.pending_games += 1;
+        user_stats.bump = bumps.user_stats;
+        user_stats.layout_version = crate::state::migration::USER_STATS_VERSION;
         
         // Update casino stats
-        casino_state.total_payouts += final_payout;
-        
-        // Transfer payout to user if they won
-        if final_payout > 0 {
-            let payout_instruction = TransferChecked {
-                from: self.casino_vault.to_account_info(),
-                to: self.user_token_account.to_account_info(),
-                authority: casino_state.to_account_info(),
-                mint: self.usdc_mint.to_account_info(),
-            };
-            
-            let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[self.casino_state.casino_state_bump]]];
-            let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), payout_instruction, seeds);
-            
-            transfer_checked(cpi_ctx, final_payout, self.usdc_mint.decimals)?;
-        }
+        casino_state.total_games_played += 1;
+        casino_state.total_volume += bet_amount;
+        casino_state.outstanding_liabilities += max_payout;
+        casino_state.pending_stakes += bet_amount;
+        
+        // Update game stats
+        game_state.total_games += 1;
+        game_state.total_bets += bet_amount;
+        
+        emit!(GameRequested {
+            version: EVENT_VERSION,
+            slot: clock.slot,
+            user: self.user.key(),
+            game: vrf_game_state.key(),
+            game_type: GameType::Aviator,
+            nonce: vrf_game_state.nonce,
+            bet_amount,
+            max_payout,
+            randomness_account,
+        });
+        Ok(())
+    }
+}
+
+impl <'info> SettleAviatorGame<'info> {
+    pub fn settle_aviator_game(&mut self, _game_nonce: u64, epoch: u64, bumps: &SettleAviatorGameBumps, remaining_accounts: &[AccountInfo]) -> Result<()> {
+        let vrf_game_state = &mut self.vrf_game_state;
+        
+        // Validate game state
+        require!(
+            vrf_game_state.game_phase == crate::utils::vrf::GamePhase::Requested,
+            CasinoError::InvalidGameState
+        );
+        
+        require!(
+            vrf_game_state.randomness_account == self.randomness_account_data.key(),
+            CasinoError::VrfRequestFailed
+        );
+        
+        // Parse and validate Switchboard randomness data
+        let randomness_data = crate::utils::vrf::parse_randomness_data(&self.randomness_account_data)?;
+        
+        // Validate randomness timing
+        crate::utils::vrf::validate_randomness_timing(&randomness_data, vrf_game_state.commit_slot)?;
+        
+        // Get revealed randomness
+        let randomness = crate::utils::vrf::get_revealed_randomness(&randomness_data)?;
+        
+        // Derive the outcome from the committed inputs exactly as an off-chain verifier would
+        let record = crate::verify::GameRecord {
+            game: vrf_game_state.key(),
+            nonce: vrf_game_state.nonce,
+            bet_amount: vrf_game_state.bet_amount,
+            inputs: crate::verify::decode_inputs(&vrf_game_state.game_type, &vrf_game_state.game_data, vrf_game_state.rtp_bps)?,
+            randomness,
+        };
+        
+        // Draw the crash point (it carries the house edge, so the payout is not scaled by RTP again)
+        let target_multiplier = match &record.inputs {
+            crate::verify::GameInputs::Aviator { target_multiplier, .. } => *target_multiplier,
+            _ => return err!(CasinoError::InvalidGameState),
+        };
+        let crate::verify::Outcome::Aviator { crash_multiplier, payout: final_payout } =
+            crate::verify::verify_outcome(&record, &randomness, None)? else {
+            return err!(CasinoError::InvalidGameState);
+        };
         
-        msg!("Roulette game settled: Winning Number: {}, Payout: {}", winning_number, final_payout);
+        // Update VRF game state
+        vrf_game_state.payout = final_payout;
+        vrf_game_state.game_phase = crate::utils::vrf::GamePhase::Settled;
+        vrf_game_state.is_complete = true;
+        
+        // Book the game: stats, rakeback, limits, tournament, payout, fees and proof
+        let settled = crate::instructions::settle::SettledGame {
+            game_type: vrf_game_state.game_type.clone(),
+            wagered: vrf_game_state.wagered(),
+            payout: final_payout,
+            max_payout: vrf_game_state.max_payout,
+            rtp_bps: vrf_game_state.rtp_bps,
+            record,
+        };
+        let fee = crate::instructions::settle::SettleAccounts {
+            casino_state: &mut self.casino_state,
+            casino_vault: &self.casino_vault,
+            game_state: &mut self.game_state,
+            fee_vault: &self.fee_vault,
+            fee_epoch: &mut self.fee_epoch,
+            user_stats: &mut self.user_stats,
+            vip_config: self.vip_config.as_deref(),
+            player_limits: &self.player_limits,
+            referral: &self.referral,
+            tournament: self.tournament.as_mut(),
+            tournament_entry: self.tournament_entry.as_mut(),
+            game_history: &mut self.game_history,
+            user: &self.user,
+            signer: &self.signer,
+            session_key: self.session_key.as_ref(),
+            user_token_account: &self.user_token_account,
+            usdc_mint: &self.usdc_mint,
+            token_program: &self.token_program,
+            epoch,
+            fee_epoch_bump: bumps.fee_epoch,
+            game_history_bump: bumps.game_history,
+        }.finish_settle(settled, remaining_accounts)?;
+        
+        emit!(AviatorSettled {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            game: vrf_game_state.key(),
+            nonce: vrf_game_state.nonce,
+            bet_amount: vrf_game_state.bet_amount,
+            target_multiplier,
+            crash_multiplier,
+            payout: final_payout,
+            fee,
+        });
         Ok(())
     }
 }
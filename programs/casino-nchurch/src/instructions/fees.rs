@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::casino::*;
+use crate::state::liquidity::FEE_DISTRIBUTION_EPOCH;
+use crate::errors::*;
+
+// Platform fee collection.
+// Every settled game moves its platform fee (see `calculate_game_fee`) from the casino vault
+// into the fee vault and books it on the FeeEpoch account of the current epoch, which
+// distribute_platform_fees sweeps once the epoch has ended. The referral share of the fee is
+// not booked to the epoch; it stays in the fee vault until the referrers claim it.
+
+/// Fee epoch containing the current cluster time
+pub fn current_fee_epoch() -> Result<u64> {
+    Ok((Clock::get()?.unix_timestamp / FEE_DISTRIBUTION_EPOCH) as u64)
+}
+
+/// Validate the settle books into the current epoch and stamp the (possibly new) FeeEpoch account
+pub fn open_fee_epoch(fee_epoch: &mut FeeEpoch, epoch: u64, bump: u8) -> Result<()> {
+    require!(epoch == current_fee_epoch()?, CasinoError::InvalidFeeEpoch);
+    fee_epoch.epoch = epoch;
+    fee_epoch.bump = bump;
+    Ok(())
+}
+
+/// Move a settled game's platform fee into the fee vault and record it
+pub fn collect_platform_fee<'info>(
+    casino_state: &mut Account<'info, CasinoState>,
+    fee_epoch: &mut FeeEpoch,
+    casino_vault: &InterfaceAccount<'info, TokenAccount>,
+    fee_vault: &InterfaceAccount<'info, TokenAccount>,
+    usdc_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    fee: u64,
+) -> Result<()> {
+    if fee > 0 {
+        let fee_instruction = TransferChecked {
+            from: casino_vault.to_account_info(),
+            to: fee_vault.to_account_info(),
+            authority: casino_state.to_account_info(),
+            mint: usdc_mint.to_account_info(),
+        };
+
+        let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[casino_state.casino_state_bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), fee_instruction, seeds);
+        transfer_checked(cpi_ctx, fee, usdc_mint.decimals)?;
+    }
+
+    casino_state.total_fees_collected = casino_state.total_fees_collected.checked_add(fee).ok_or(CasinoError::MathOverflow)?;
+    fee_epoch.total_fees = fee_epoch.total_fees.checked_add(fee).ok_or(CasinoError::MathOverflow)?;
+    fee_epoch.games_settled = fee_epoch.games_settled.checked_add(1).ok_or(CasinoError::MathOverflow)?;
+    Ok(())
+}
+
+/// Keep a settled game's referral share out of its fee epoch (it stays in the fee vault for the referrers)
+pub fn withhold_referral_share(fee_epoch: &mut FeeEpoch, referral_share: u64) -> Result<()> {
+    fee_epoch.total_fees = fee_epoch.total_fees.checked_sub(referral_share).ok_or(CasinoError::MathOverflow)?;
+    Ok(())
+}
@@ -0,0 +1,328 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::casino::*;
+use crate::state::jackpot::*;
+use crate::events::*;
+use crate::errors::*;
+use crate::utils::jackpot::*;
+
+// Progressive slots jackpot. Slots request and settle contexts take the ProgressiveJackpot
+// and its vault: once the jackpot is initialized, requests must move the jackpot's slice of
+// the stake from the casino vault into the jackpot vault, and settles of spins that
+// contributed pay the pot above the reseed amount on the top combination or a jackpot roll.
+// Spins placed while the jackpot is paused neither contribute nor can win. Refunds and voids
+// hand a spin's contribution back out of the jackpot vault.
+
+// Create the jackpot and its vault
+#[derive(Accounts)]
+pub struct InitializeJackpot<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ProgressiveJackpot::DISCRIMINATOR.len() + ProgressiveJackpot::INIT_SPACE,
+        seeds = [b"progressive_jackpot"],
+        bump
+    )]
+    pub jackpot: Account<'info, ProgressiveJackpot>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = casino_state,
+        token::token_program = token_program,
+        seeds = [b"jackpot_vault"],
+        bump
+    )]
+    pub jackpot_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> InitializeJackpot<'info> {
+    pub fn initialize_jackpot(
+        &mut self,
+        contribution_bps: u16,
+        reseed_amount: u64,
+        jackpot_symbol: u8,
+        jackpot_run: u8,
+        roll_odds: u64,
+        bumps: &InitializeJackpotBumps
+    ) -> Result<()> {
+        validate_jackpot_config(contribution_bps, jackpot_symbol, jackpot_run)?;
+
+        self.jackpot.set_inner(ProgressiveJackpot {
+            vault: self.jackpot_vault.key(),
+            contribution_bps,
+            reseed_amount,
+            jackpot_symbol,
+            jackpot_run,
+            roll_odds,
+            total_contributed: 0,
+            total_paid: 0,
+            total_wins: 0,
+            recent_wins: Vec::new(),
+            is_active: true,
+            bump: bumps.jackpot,
+            vault_bump: bumps.jackpot_vault,
+        });
+
+        emit_config_updated(&self.jackpot, self.authority.key())
+    }
+}
+
+// Change the jackpot parameters or pause contributions
+#[derive(Accounts)]
+pub struct UpdateJackpot<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        seeds = [b"progressive_jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, ProgressiveJackpot>,
+
+    pub authority: Signer<'info>,
+}
+
+impl <'info> UpdateJackpot<'info> {
+    pub fn update_jackpot(
+        &mut self,
+        contribution_bps: u16,
+        reseed_amount: u64,
+        jackpot_symbol: u8,
+        jackpot_run: u8,
+        roll_odds: u64,
+        is_active: bool
+    ) -> Result<()> {
+        validate_jackpot_config(contribution_bps, jackpot_symbol, jackpot_run)?;
+
+        let jackpot = &mut self.jackpot;
+        jackpot.contribution_bps = contribution_bps;
+        jackpot.reseed_amount = reseed_amount;
+        jackpot.jackpot_symbol = jackpot_symbol;
+        jackpot.jackpot_run = jackpot_run;
+        jackpot.roll_odds = roll_odds;
+        jackpot.is_active = is_active;
+
+        emit_config_updated(&self.jackpot, self.authority.key())
+    }
+}
+
+// Top up the pot (seed money or promotions)
+#[derive(Accounts)]
+pub struct FundJackpot<'info> {
+    #[account(
+        seeds = [b"progressive_jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, ProgressiveJackpot>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot_vault"],
+        bump = jackpot.vault_bump
+    )]
+    pub jackpot_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = funder,
+        token::token_program = token_program,
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl <'info> FundJackpot<'info> {
+    pub fn fund_jackpot(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, CasinoError::InvalidBetAmount);
+
+        let fund_instruction = TransferChecked {
+            from: self.funder_token_account.to_account_info(),
+            to: self.jackpot_vault.to_account_info(),
+            authority: self.funder.to_account_info(),
+            mint: self.usdc_mint.to_account_info(),
+        };
+        transfer_checked(CpiContext::new(self.token_program.to_account_info(), fund_instruction), amount, self.usdc_mint.decimals)?;
+
+        emit!(JackpotFunded {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            funder: self.funder.key(),
+            amount,
+            pot: self.jackpot_vault.amount + amount,
+        });
+        Ok(())
+    }
+}
+
+fn emit_config_updated(jackpot: &ProgressiveJackpot, authority: Pubkey) -> Result<()> {
+    emit!(JackpotConfigUpdated {
+        version: EVENT_VERSION,
+        slot: Clock::get()?.slot,
+        authority,
+        contribution_bps: jackpot.contribution_bps,
+        reseed_amount: jackpot.reseed_amount,
+        jackpot_symbol: jackpot.jackpot_symbol,
+        jackpot_run: jackpot.jackpot_run,
+        roll_odds: jackpot.roll_odds,
+        is_active: jackpot.is_active,
+    });
+    Ok(())
+}
+
+fn load_jackpot(jackpot: &AccountInfo) -> Result<Option<ProgressiveJackpot>> {
+    // Before the jackpot is initialized there is no account at the PDA
+    if jackpot.owner != &crate::ID || jackpot.data_is_empty() {
+        return Ok(None);
+    }
+    let data = jackpot.try_borrow_data()?;
+    Ok(Some(ProgressiveJackpot::try_deserialize(&mut &data[..])?))
+}
+
+fn store_jackpot(jackpot_info: &AccountInfo, jackpot: &ProgressiveJackpot) -> Result<()> {
+    let mut data = jackpot_info.try_borrow_mut_data()?;
+    jackpot.try_serialize(&mut &mut data[..])
+}
+
+/// Move the jackpot's slice of a slots stake from the casino vault into the jackpot vault.
+/// Once the jackpot is initialized its vault must be passed; returns the amount contributed
+/// (0 before initialization or while the jackpot is paused).
+pub fn contribute_to_jackpot<'info>(
+    casino_state: &Account<'info, CasinoState>,
+    jackpot_info: &AccountInfo<'info>,
+    jackpot_vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+    casino_vault: &InterfaceAccount<'info, TokenAccount>,
+    usdc_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    wagered: u64,
+) -> Result<u64> {
+    let Some(mut jackpot) = load_jackpot(jackpot_info)? else {
+        return Ok(0);
+    };
+    if !jackpot.is_active {
+        return Ok(0);
+    }
+    let Some(jackpot_vault) = jackpot_vault.filter(|vault| vault.key() == jackpot.vault) else {
+        return err!(CasinoError::InvalidJackpotVault);
+    };
+
+    let contribution = jackpot_contribution(wagered, jackpot.contribution_bps)?;
+    if contribution > 0 {
+        let contribution_instruction = TransferChecked {
+            from: casino_vault.to_account_info(),
+            to: jackpot_vault.to_account_info(),
+            authority: casino_state.to_account_info(),
+            mint: usdc_mint.to_account_info(),
+        };
+
+        let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[casino_state.casino_state_bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), contribution_instruction, seeds);
+        transfer_checked(cpi_ctx, contribution, usdc_mint.decimals)?;
+
+        jackpot.total_contributed += contribution;
+        store_jackpot(jackpot_info, &jackpot)?;
+    }
+    Ok(contribution)
+}
+
+/// Return a refunded spin's contribution from the jackpot vault. The jackpot accounts are
+/// required whenever the spin contributed; if a win has since drained the pot below the
+/// contribution, only what is left comes back from it. Returns the amount returned.
+pub fn return_jackpot_contribution<'info>(
+    casino_state: &Account<'info, CasinoState>,
+    jackpot: Option<&mut Account<'info, ProgressiveJackpot>>,
+    jackpot_vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+    user_token_account: &InterfaceAccount<'info, TokenAccount>,
+    usdc_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    contribution: u64,
+) -> Result<u64> {
+    if contribution == 0 {
+        return Ok(0);
+    }
+    let (Some(jackpot), Some(jackpot_vault)) = (jackpot, jackpot_vault) else {
+        return err!(CasinoError::InvalidJackpotVault);
+    };
+    require!(jackpot_vault.key() == jackpot.vault, CasinoError::InvalidJackpotVault);
+
+    let returned = contribution.min(jackpot_vault.amount);
+    if returned > 0 {
+        let refund_instruction = TransferChecked {
+            from: jackpot_vault.to_account_info(),
+            to: user_token_account.to_account_info(),
+            authority: casino_state.to_account_info(),
+            mint: usdc_mint.to_account_info(),
+        };
+
+        let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[casino_state.casino_state_bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), refund_instruction, seeds);
+        transfer_checked(cpi_ctx, returned, usdc_mint.decimals)?;
+
+        jackpot.total_contributed = jackpot.total_contributed.saturating_sub(returned);
+    }
+    Ok(returned)
+}
+
+/// Pay `win.amount` from the jackpot vault to the winner and record the win on-chain
+pub fn pay_jackpot<'info>(
+    casino_state: &Account<'info, CasinoState>,
+    jackpot: &mut Account<'info, ProgressiveJackpot>,
+    jackpot_vault: &InterfaceAccount<'info, TokenAccount>,
+    user_token_account: &InterfaceAccount<'info, TokenAccount>,
+    usdc_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    win: JackpotWin,
+) -> Result<()> {
+    let payout_instruction = TransferChecked {
+        from: jackpot_vault.to_account_info(),
+        to: user_token_account.to_account_info(),
+        authority: casino_state.to_account_info(),
+        mint: usdc_mint.to_account_info(),
+    };
+
+    let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[casino_state.casino_state_bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), payout_instruction, seeds);
+    transfer_checked(cpi_ctx, win.amount, usdc_mint.decimals)?;
+
+    jackpot.total_paid += win.amount;
+    jackpot.total_wins += 1;
+    record_jackpot_win(&mut jackpot.recent_wins, win.clone());
+
+    emit!(JackpotWon {
+        version: EVENT_VERSION,
+        slot: win.slot,
+        user: win.user,
+        game: win.game,
+        amount: win.amount,
+        trigger: win.trigger,
+        pot_after: jackpot_vault.amount - win.amount,
+    });
+    Ok(())
+}
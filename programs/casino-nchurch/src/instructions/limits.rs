@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use crate::state::limits::*;
+use crate::events::*;
+use crate::errors::*;
+use crate::utils::limits::*;
+
+// Responsible gaming. Every request instruction takes the player's PlayerLimits PDA as an
+// unchecked account: when the player has created it the stake is checked and booked against
+// their limits, and settles and refunds credit payouts back. Players who never opted in
+// pass the empty PDA address and play without limits.
+
+// Opt in to responsible-gaming limits
+#[derive(Accounts)]
+pub struct InitializePlayerLimits<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = PlayerLimits::DISCRIMINATOR.len() + PlayerLimits::INIT_SPACE,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: Account<'info, PlayerLimits>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> InitializePlayerLimits<'info> {
+    pub fn initialize_player_limits(&mut self, limits: LimitSettings, bumps: &InitializePlayerLimitsBumps) -> Result<()> {
+        self.player_limits.set_inner(PlayerLimits {
+            user: self.user.key(),
+            limits,
+            pending_limits: None,
+            pending_effective_at: 0,
+            daily_usage: LimitUsage::default(),
+            weekly_usage: LimitUsage::default(),
+            monthly_usage: LimitUsage::default(),
+            session_started_at: 0,
+            excluded_until: 0,
+            bump: bumps.player_limits,
+        });
+
+        emit!(PlayerLimitsUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            limits: self.player_limits.limits.clone(),
+            pending_limits: None,
+            pending_effective_at: 0,
+        });
+        Ok(())
+    }
+}
+
+// Change limits: tighter values apply at once, looser ones after LIMIT_LOOSEN_DELAY
+#[derive(Accounts)]
+pub struct UpdatePlayerLimits<'info> {
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump = player_limits.bump
+    )]
+    pub player_limits: Account<'info, PlayerLimits>,
+
+    pub user: Signer<'info>,
+}
+
+impl <'info> UpdatePlayerLimits<'info> {
+    pub fn set_player_limits(&mut self, limits: LimitSettings) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let player_limits = &mut self.player_limits;
+
+        activate_pending_limits(player_limits, now);
+        request_limits(player_limits, limits, now);
+
+        emit!(PlayerLimitsUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            limits: player_limits.limits.clone(),
+            pending_limits: player_limits.pending_limits.clone(),
+            pending_effective_at: player_limits.pending_effective_at,
+        });
+        Ok(())
+    }
+
+    // Exclude the player for `duration` seconds, or permanently with None. An exclusion
+    // can only be extended, never shortened or lifted.
+    pub fn self_exclude(&mut self, duration: Option<i64>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let excluded_until = match duration {
+            Some(duration) => {
+                require!(duration > 0, LimitsError::InvalidExclusionPeriod);
+                now.saturating_add(duration)
+            },
+            None => PERMANENT_EXCLUSION,
+        };
+
+        let player_limits = &mut self.player_limits;
+        player_limits.excluded_until = player_limits.excluded_until.max(excluded_until);
+
+        emit!(PlayerSelfExcluded {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            excluded_until: player_limits.excluded_until,
+        });
+        Ok(())
+    }
+}
+
+fn load_player_limits(player_limits: &AccountInfo) -> Result<Option<PlayerLimits>> {
+    // Players who never opted in have no account at the PDA
+    if player_limits.owner != &crate::ID || player_limits.data_is_empty() {
+        return Ok(None);
+    }
+    let data = player_limits.try_borrow_data()?;
+    Ok(Some(PlayerLimits::try_deserialize(&mut &data[..])?))
+}
+
+fn store_player_limits(player_limits: &AccountInfo, limits: &PlayerLimits) -> Result<()> {
+    let mut data = player_limits.try_borrow_mut_data()?;
+    limits.try_serialize(&mut &mut data[..])
+}
+
+/// Check a new stake against the player's limits and book it (free spins stake 0)
+pub fn enforce_player_limits(player_limits: &AccountInfo, stake: u64, now: i64) -> Result<()> {
+    let Some(mut limits) = load_player_limits(player_limits)? else {
+        return Ok(());
+    };
+
+    check_and_record_stake(&mut limits, stake, now)?;
+    store_player_limits(player_limits, &limits)
+}
+
+/// Credit a payout or refund back to the player's net-loss totals
+pub fn credit_player_limits(player_limits: &AccountInfo, stake_reversed: u64, returned: u64, now: i64) -> Result<()> {
+    let Some(mut limits) = load_player_limits(player_limits)? else {
+        return Ok(());
+    };
+
+    record_return(&mut limits, stake_reversed, returned, now);
+    store_player_limits(player_limits, &limits)
+}
@@ -1,8 +1,14 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo};
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo, Burn};
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
+use crate::state::casino::{CasinoState, FeeEpoch};
 use crate::state::liquidity::*;
+use crate::instructions::fees::current_fee_epoch;
+use crate::events::*;
 use crate::errors::*;
+use crate::utils::bankroll::validate_exposure;
+use crate::utils::liquidity::{calculate_lp_fee_share, house_equity, lp_redemption_amount, lp_tokens_for_deposit};
 
 // Initialize liquidity pool
 #[derive(Accounts)]
@@ -55,7 +61,7 @@ impl<'info> InitializeLiquidityPool<'info> {
             total_liquidity: 0,
             lp_token_supply: 0,
             platform_fee_share_bps: DEFAULT_PLATFORM_FEE_SHARE_BPS,
-            staking_rewards_apr: DEFAULT_STAKING_REWARDS_APR,
+            staking_rewards_apr: 0,
             total_fees_distributed: 0,
             total_staking_rewards: 0,
             status: LPStatus::Active,
@@ -63,12 +69,18 @@ impl<'info> InitializeLiquidityPool<'info> {
             lp_vault_bump: bumps.lp_vault,
         });
         
-        msg!("Liquidity pool initialized successfully");
+        emit!(LiquidityPoolInitialized {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            liquidity_pool: liquidity_pool.key(),
+            lp_token_mint: self.lp_token_mint.key(),
+        });
         Ok(())
     }
 }
 
-// Deposit liquidity
+// Deposit liquidity into the casino bankroll at the current LP share price
 #[derive(Accounts)]
 pub struct DepositLiquidity<'info> {
     #[account(
@@ -78,19 +90,24 @@ pub struct DepositLiquidity<'info> {
     )]
     pub liquidity_pool: Account<'info, LiquidityPool>,
     
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+    
     #[account(
         mut,
-        seeds = [b"lp_token_mint"],
-        bump
+        address = casino_state.vault
     )]
-    pub lp_token_mint: Account<'info, Mint>,
+    pub casino_vault: Account<'info, TokenAccount>,
     
     #[account(
         mut,
-        seeds = [b"lp_vault"],
+        seeds = [b"lp_token_mint"],
         bump
     )]
-    pub lp_vault: Account<'info, TokenAccount>,
+    pub lp_token_mint: Account<'info, Mint>,
     
     #[account(
         init_if_needed,
@@ -131,10 +148,15 @@ impl<'info> DepositLiquidity<'info> {
         // Validate deposit amount
         require!(amount >= 1000000, LiquidityError::InvalidLPTokenAmount); // Min 1 USDC
         
-        // Transfer USDC from user to LP vault
+        // Price the deposit against the bankroll before it grows
+        let house_equity = house_equity(self.casino_vault.amount, self.casino_state.pending_stakes + self.casino_state.rakeback_owed);
+        let (lp_tokens_to_mint, dead_shares) = lp_tokens_for_deposit(amount, house_equity, liquidity_pool.lp_token_supply)?;
+        require!(lp_tokens_to_mint > 0, LiquidityError::InvalidLPTokenAmount);
+        
+        // Transfer USDC from user to the casino vault
         let transfer_instruction = Transfer {
             from: self.user_token_account.to_account_info(),
-            to: self.lp_vault.to_account_info(),
+            to: self.casino_vault.to_account_info(),
             authority: self.user.to_account_info(),
         };
         
@@ -145,9 +167,6 @@ impl<'info> DepositLiquidity<'info> {
         
         token::transfer(cpi_ctx, amount)?;
         
-        // Calculate LP tokens to mint (1:1 ratio for simplicity)
-        let lp_tokens_to_mint = amount;
-        
         // Mint LP tokens to user
         let mint_instruction = MintTo {
             mint: self.lp_token_mint.to_account_info(),
@@ -156,22 +175,184 @@ impl<'info> DepositLiquidity<'info> {
         };
         
         let liquidity_pool_bump = liquidity_pool.bump;
-        let seeds : &[&[&[u8]]] = &[&[b"lp_vault", &[liquidity_pool_bump]]];
+        let seeds : &[&[&[u8]]] = &[&[b"liquidity_pool", &[liquidity_pool_bump]]];
         let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), mint_instruction, seeds);
         
         token::mint_to(cpi_ctx, lp_tokens_to_mint)?;
         
         // Update liquidity pool
-        liquidity_pool.total_liquidity += amount;
-        liquidity_pool.lp_token_supply += lp_tokens_to_mint;
+        liquidity_pool.total_liquidity = house_equity + amount;
+        liquidity_pool.lp_token_supply += lp_tokens_to_mint + dead_shares;
         
         // Update user stats
         lp_user_stats.user = self.user.key();
         lp_user_stats.total_lp_tokens += lp_tokens_to_mint;
         lp_user_stats.bump = bumps.lp_user_stats;
         
-        msg!("Liquidity deposited: User: {}, Amount: {}, LP Tokens: {}", 
-            self.user.key(), amount, lp_tokens_to_mint);
+        emit!(LiquidityDeposited {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            amount,
+            lp_tokens: lp_tokens_to_mint,
+            total_liquidity: liquidity_pool.total_liquidity,
+            lp_token_supply: liquidity_pool.lp_token_supply,
+        });
+        Ok(())
+    }
+}
+
+// Withdraw liquidity: burn LP tokens for their share of the bankroll
+#[derive(Accounts)]
+pub struct WithdrawLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool"],
+        bump = liquidity_pool.bump
+    )]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+    
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+    
+    #[account(
+        mut,
+        address = casino_state.vault
+    )]
+    pub casino_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"lp_token_mint"],
+        bump
+    )]
+    pub lp_token_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lp_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_lp_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"lp_user_stats", user.key().as_ref()],
+        bump = lp_user_stats.bump
+    )]
+    pub lp_user_stats: Account<'info, LPUserStats>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawLiquidity<'info> {
+    pub fn withdraw_liquidity(&mut self, lp_tokens: u64) -> Result<()> {
+        let liquidity_pool = &mut self.liquidity_pool;
+        let lp_user_stats = &mut self.lp_user_stats;
+        
+        // Validate withdrawal amount
+        require!(lp_tokens > 0, LiquidityError::InvalidLPTokenAmount);
+        require!(self.user_lp_token_account.amount >= lp_tokens, LiquidityError::InsufficientLPTokens);
+        
+        // Redeem at the current share price
+        let house_equity = house_equity(self.casino_vault.amount, self.casino_state.pending_stakes + self.casino_state.rakeback_owed);
+        let amount = lp_redemption_amount(lp_tokens, house_equity, liquidity_pool.lp_token_supply)?;
+            
+        // Validate the remaining bankroll still covers every pending game
+        validate_exposure(
+            self.casino_state.outstanding_liabilities,
+            0,
+            self.casino_vault.amount - amount,
+            self.casino_state.max_exposure_bps
+        )?;
+        
+        // Burn the redeemed LP tokens
+        let burn_instruction = Burn {
+            mint: self.lp_token_mint.to_account_info(),
+            from: self.user_lp_token_account.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), burn_instruction);
+        
+        token::burn(cpi_ctx, lp_tokens)?;
+        
+        // Transfer USDC from the casino vault to the user
+        let transfer_instruction = Transfer {
+            from: self.casino_vault.to_account_info(),
+            to: self.user_token_account.to_account_info(),
+            authority: self.casino_state.to_account_info(),
+        };
+        
+        let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[self.casino_state.casino_state_bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), transfer_instruction, seeds);
+        
+        token::transfer(cpi_ctx, amount)?;
+        
+        // Update liquidity pool
+        liquidity_pool.total_liquidity = house_equity - amount;
+        liquidity_pool.lp_token_supply = liquidity_pool.lp_token_supply.saturating_sub(lp_tokens);
+        
+        // Update user stats
+        lp_user_stats.total_lp_tokens = lp_user_stats.total_lp_tokens.saturating_sub(lp_tokens);
+        
+        emit!(LiquidityWithdrawn {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            amount,
+            lp_tokens,
+            total_liquidity: liquidity_pool.total_liquidity,
+            lp_token_supply: liquidity_pool.lp_token_supply,
+        });
+        Ok(())
+    }
+}
+
+// Sync total liquidity with the bankroll so it reflects house P&L since the last LP action
+#[derive(Accounts)]
+pub struct SyncLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool"],
+        bump = liquidity_pool.bump
+    )]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+    
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+    
+    #[account(address = casino_state.vault)]
+    pub casino_vault: Account<'info, TokenAccount>,
+}
+
+impl<'info> SyncLiquidity<'info> {
+    pub fn sync_liquidity(&mut self) -> Result<()> {
+        let liquidity_pool = &mut self.liquidity_pool;
+        
+        let house_equity = house_equity(self.casino_vault.amount, self.casino_state.pending_stakes + self.casino_state.rakeback_owed);
+        let old_total_liquidity = liquidity_pool.total_liquidity;
+        liquidity_pool.total_liquidity = house_equity;
+        
+        // House P&L since the last sync is new_total_liquidity - old_total_liquidity
+        emit!(LiquiditySynced {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            old_total_liquidity,
+            new_total_liquidity: house_equity,
+        });
         Ok(())
     }
 }
@@ -269,15 +450,24 @@ impl<'info> StakeLPTokens<'info> {
         lp_user_stats.total_staked += amount;
         lp_user_stats.staking_count += 1;
         
-        msg!("LP tokens staked successfully");
+        emit!(LPTokensStaked {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            staking: lp_staking.key(),
+            amount,
+            staking_end_time: lp_staking.staking_end_time,
+        });
         Ok(())
     }
 }
 
-// Claim LP rewards
+// Distribute platform fees collected during an ended epoch: the LP share moves from the
+// fee vault back into the casino vault, where it raises the LP share price, and the platform
+// share stays in the fee vault until the authority takes it with withdraw_platform_fees
 #[derive(Accounts)]
-#[instruction(staking_counter: u64)]
-pub struct ClaimLPRewards<'info> {
+#[instruction(epoch: u64)]
+pub struct DistributePlatformFees<'info> {
     #[account(
         mut,
         seeds = [b"liquidity_pool"],
@@ -286,105 +476,30 @@ pub struct ClaimLPRewards<'info> {
     pub liquidity_pool: Account<'info, LiquidityPool>,
     
     #[account(
-        mut,
-        seeds = [b"lp_vault"],
-        bump = liquidity_pool.lp_vault_bump
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
     )]
-    pub lp_vault: Account<'info, TokenAccount>,
+    pub casino_state: Account<'info, CasinoState>,
     
     #[account(
         mut,
-        seeds = [b"lp_staking", user.key().as_ref(), &staking_counter.to_le_bytes()],
-        bump = lp_staking.bump
+        seeds = [b"fee_vault"],
+        bump = casino_state.fee_vault_bump
     )]
-    pub lp_staking: Account<'info, LPStaking>,
+    pub fee_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
     
     #[account(
         mut,
-        seeds = [b"lp_user_stats", user.key().as_ref()],
-        bump = lp_user_stats.bump
+        seeds = [b"fee_epoch", epoch.to_le_bytes().as_ref()],
+        bump = fee_epoch.bump
     )]
-    pub lp_user_stats: Account<'info, LPUserStats>,
+    pub fee_epoch: Account<'info, FeeEpoch>,
     
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
-}
-
-impl<'info> ClaimLPRewards<'info> {
-    pub fn claim_lp_rewards(&mut self, staking_counter: u64, bumps: &ClaimLPRewardsBumps) -> Result<()> {
-        let liquidity_pool = &mut self.liquidity_pool;
-        let lp_staking = &mut self.lp_staking;
-        let lp_user_stats = &mut self.lp_user_stats;
-        
-        // Validate staking is active
-        require!(lp_staking.is_active, LiquidityError::StakingNotActive);
-        
-        // Calculate rewards
-        let current_time = Clock::get().unwrap().unix_timestamp;
-        let time_elapsed = current_time - lp_staking.last_claim_time;
-        
-        let staking_multiplier = match lp_staking.staking_period {
-            StakingPeriod::Short => SHORT_STAKING_MULTIPLIER,
-            StakingPeriod::Medium => MEDIUM_STAKING_MULTIPLIER,
-            StakingPeriod::Long => LONG_STAKING_MULTIPLIER,
-            StakingPeriod::Ultra => ULTRA_STAKING_MULTIPLIER,
-        };
-        
-        let base_apr = liquidity_pool.staking_rewards_apr;
-        let adjusted_apr = (base_apr * staking_multiplier) / 100;
-        
-        let rewards = calculate_staking_rewards(
-            lp_staking.lp_tokens_staked,
-            adjusted_apr,
-            time_elapsed
-        );
-        
-        require!(rewards > 0, LiquidityError::NoRewardsToClaim);
-        
-        // Transfer rewards to user
-        let transfer_instruction = Transfer {
-            from: self.lp_vault.to_account_info(),
-            to: self.user_token_account.to_account_info(),
-            authority: liquidity_pool.to_account_info(),
-        };
-        
-        let liquidity_pool_bump = liquidity_pool.bump;
-        let seeds : &[&[&[u8]]] = &[&[b"lp_vault", &[liquidity_pool_bump]]];
-        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), transfer_instruction, seeds);
-        
-        token::transfer(cpi_ctx, rewards)?;
-        
-        // Update staking
-        lp_staking.rewards_earned += rewards;
-        lp_staking.last_claim_time = current_time;
-        
-        // Update user stats
-        lp_user_stats.total_rewards_claimed += rewards;
-        
-        // Update liquidity pool
-        liquidity_pool.total_staking_rewards += rewards;
-        
-        msg!("LP rewards claimed: User: {}, Amount: {}", 
-            self.user.key(), rewards);
-        Ok(())
-    }
-}
-
-// Distribute platform fees
-#[derive(Accounts)]
-#[instruction(epoch: u64)]
-pub struct DistributePlatformFees<'info> {
     #[account(
         mut,
-        seeds = [b"liquidity_pool"],
-        bump = liquidity_pool.bump
+        address = casino_state.vault
     )]
-    pub liquidity_pool: Account<'info, LiquidityPool>,
+    pub casino_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
     
     #[account(
         init,
@@ -398,6 +513,9 @@ pub struct DistributePlatformFees<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    pub usdc_mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -405,50 +523,169 @@ impl<'info> DistributePlatformFees<'info> {
     pub fn distribute_platform_fees(&mut self, epoch: u64, bumps: &DistributePlatformFeesBumps) -> Result<()> {
         let liquidity_pool = &mut self.liquidity_pool;
         let fee_distribution = &mut self.fee_distribution;
+        let fee_epoch = &mut self.fee_epoch;
         
         // Validate liquidity pool is active
         require!(liquidity_pool.status == LPStatus::Active, LiquidityError::LiquidityPoolNotActive);
         
-        // Calculate platform fees to distribute (placeholder - in production, this would come from actual platform fees)
-        let total_platform_fees = 1000000; // 1 USDC placeholder
+        // Validate the epoch is over and has not been swept
+        require!(epoch < current_fee_epoch()?, LiquidityError::FeeEpochNotEnded);
+        require!(!fee_epoch.is_distributed, LiquidityError::FeesAlreadyDistributed);
+        
+        // Platform fees booked by every game settled in the epoch
+        let total_platform_fees = fee_epoch.total_fees;
         
         // Calculate LP fee share
-        let lp_fee_share = calculate_lp_fee_share(total_platform_fees, liquidity_pool.platform_fee_share_bps);
+        let lp_fee_share = calculate_lp_fee_share(total_platform_fees, liquidity_pool.platform_fee_share_bps)?;
         let platform_fee_share = total_platform_fees - lp_fee_share;
+        
+        // Move the LP share into the bankroll
+        if lp_fee_share > 0 {
+            let transfer_instruction = TransferChecked {
+                from: self.fee_vault.to_account_info(),
+                to: self.casino_vault.to_account_info(),
+                authority: self.casino_state.to_account_info(),
+                mint: self.usdc_mint.to_account_info(),
+            };
+            
+            let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[self.casino_state.casino_state_bump]]];
+            let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), transfer_instruction, seeds);
+            
+            token_interface::transfer_checked(cpi_ctx, lp_fee_share, self.usdc_mint.decimals)?;
+        }
 
         fee_distribution.set_inner(FeeDistribution {
-            epoch: (Clock::get().unwrap().unix_timestamp / FEE_DISTRIBUTION_EPOCH) as u64,
-            total_platform_fees: total_platform_fees,
-            lp_fee_share: lp_fee_share,
-            platform_fee_share: platform_fee_share,
-            distribution_complete: false,
+            epoch,
+            total_platform_fees,
+            lp_fee_share,
+            platform_fee_share,
+            distribution_complete: true,
             bump: bumps.fee_distribution,
         });
         
-        // Initialize fee distribution
+        fee_epoch.is_distributed = true;
         
         // Update liquidity pool
         liquidity_pool.total_fees_distributed += lp_fee_share;
         
-        msg!("Platform fees distributed: Total: {}, LP Share: {}, Platform Share: {}", 
-            total_platform_fees, lp_fee_share, platform_fee_share);
+        emit!(PlatformFeesDistributed {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            epoch,
+            total_platform_fees,
+            lp_fee_share,
+            platform_fee_share,
+        });
         Ok(())
     }
 }
 
-// Helper function to calculate staking rewards
-fn calculate_staking_rewards(staked_amount: u64, apr_bps: u16, time_elapsed: i64) -> u64 {
-    let seconds_per_year = 365 * 24 * 60 * 60;
-    let time_elapsed_seconds = time_elapsed as u64;
-    
-    if time_elapsed_seconds >= seconds_per_year {
-        (staked_amount * apr_bps as u64) / 10000
-    } else {
-        (staked_amount * apr_bps as u64 * time_elapsed_seconds) / (10000 * seconds_per_year)
+// Withdraw the platform share of a swept epoch to the casino authority (casino authority only).
+// Only the share distribute_platform_fees left in the fee vault can leave: the referral cut was
+// withheld from the epoch before the sweep, so unclaimed referral earnings are never touched,
+// and the fees of epochs not yet swept stay put.
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct WithdrawPlatformFees<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = casino_state.fee_vault_bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_distribution", epoch.to_le_bytes().as_ref()],
+        bump = fee_distribution.bump
+    )]
+    pub fee_distribution: Account<'info, FeeDistribution>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::token_program = token_program,
+    )]
+    pub destination: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    pub usdc_mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawPlatformFees<'info> {
+    pub fn withdraw_platform_fees(&mut self, epoch: u64, amount: u64) -> Result<()> {
+        // Validate the withdrawal against the epoch's unwithdrawn platform share
+        draw_platform_fee_share(&mut self.fee_distribution, amount)?;
+        
+        let transfer_instruction = TransferChecked {
+            from: self.fee_vault.to_account_info(),
+            to: self.destination.to_account_info(),
+            authority: self.casino_state.to_account_info(),
+            mint: self.usdc_mint.to_account_info(),
+        };
+        
+        let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[self.casino_state.casino_state_bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), transfer_instruction, seeds);
+        
+        token_interface::transfer_checked(cpi_ctx, amount, self.usdc_mint.decimals)?;
+        
+        emit!(PlatformFeesWithdrawn {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            epoch,
+            amount,
+            remaining: self.fee_distribution.platform_fee_share,
+        });
+        Ok(())
     }
 }
 
-// Helper function to calculate LP fee share
-fn calculate_lp_fee_share(total_fees: u64, lp_share_bps: u16) -> u64 {
-    (total_fees * lp_share_bps as u64) / 10000
+// Take `amount` out of a swept epoch's unwithdrawn platform share
+fn draw_platform_fee_share(fee_distribution: &mut FeeDistribution, amount: u64) -> Result<()> {
+    fee_distribution.platform_fee_share = fee_distribution
+        .platform_fee_share
+        .checked_sub(amount)
+        .ok_or(LiquidityError::InsufficientPlatformFees)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn platform_fee_withdrawals_drain_only_the_swept_platform_share() {
+        // 1_000 swept with a 30% LP share; the referral cut was withheld before the sweep
+        let mut fee_distribution = FeeDistribution {
+            epoch: 7,
+            total_platform_fees: 1_000,
+            lp_fee_share: 300,
+            platform_fee_share: 700,
+            distribution_complete: true,
+            bump: 0,
+        };
+
+        draw_platform_fee_share(&mut fee_distribution, 450).unwrap();
+        assert_eq!(fee_distribution.platform_fee_share, 250);
+        assert!(draw_platform_fee_share(&mut fee_distribution, 251).is_err());
+
+        draw_platform_fee_share(&mut fee_distribution, 250).unwrap();
+        assert_eq!(fee_distribution.platform_fee_share, 0);
+        assert!(draw_platform_fee_share(&mut fee_distribution, 1).is_err());
+
+        // The sweep record itself is untouched
+        assert_eq!(fee_distribution.total_platform_fees - fee_distribution.lp_fee_share, 700);
+    }
 }
@@ -0,0 +1,439 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{burn, mint_to, transfer_checked, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::state::casino::*;
+use crate::state::loyalty::*;
+use crate::events::*;
+use crate::errors::*;
+use crate::utils::bankroll::validate_exposure;
+
+// Loyalty points accrue on every settled bet (UserStats.loyalty_points) and are spent on the
+// admin-managed redemption catalog. Points can optionally be tokenized into the SPL points
+// mint so they show up in wallets; tokenized points are burned when redeemed.
+
+// Create the loyalty config
+#[derive(Accounts)]
+pub struct InitializeLoyalty<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = LoyaltyConfig::DISCRIMINATOR.len() + LoyaltyConfig::INIT_SPACE,
+        seeds = [b"loyalty_config"],
+        bump
+    )]
+    pub loyalty_config: Account<'info, LoyaltyConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> InitializeLoyalty<'info> {
+    pub fn initialize_loyalty(&mut self, bumps: &InitializeLoyaltyBumps) -> Result<()> {
+        self.loyalty_config.set_inner(LoyaltyConfig {
+            points_mint: None,
+            total_points_redeemed: 0,
+            total_points_tokenized: 0,
+            points_mint_bump: 0,
+            bump: bumps.loyalty_config,
+        });
+        Ok(())
+    }
+}
+
+// Create the SPL points mint (authority: loyalty_config)
+#[derive(Accounts)]
+pub struct EnablePointsMint<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        seeds = [b"loyalty_config"],
+        bump = loyalty_config.bump
+    )]
+    pub loyalty_config: Account<'info, LoyaltyConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"points_mint"],
+        bump,
+        mint::decimals = POINTS_MINT_DECIMALS,
+        mint::authority = loyalty_config,
+        mint::token_program = token_program,
+    )]
+    pub points_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> EnablePointsMint<'info> {
+    pub fn enable_points_mint(&mut self, bumps: &EnablePointsMintBumps) -> Result<()> {
+        self.loyalty_config.points_mint = Some(self.points_mint.key());
+        self.loyalty_config.points_mint_bump = bumps.points_mint;
+
+        emit!(PointsMintEnabled {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            points_mint: self.points_mint.key(),
+        });
+        Ok(())
+    }
+}
+
+// Add an entry to the redemption catalog
+#[derive(Accounts)]
+#[instruction(item_id: u64)]
+pub struct CreateLoyaltyItem<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = LoyaltyItem::DISCRIMINATOR.len() + LoyaltyItem::INIT_SPACE,
+        seeds = [b"loyalty_item", item_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub loyalty_item: Account<'info, LoyaltyItem>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> CreateLoyaltyItem<'info> {
+    pub fn create_loyalty_item(&mut self, item_id: u64, reward: LoyaltyReward, cost_points: u64, stock: Option<u32>, bumps: &CreateLoyaltyItemBumps) -> Result<()> {
+        validate_loyalty_item(&reward, cost_points)?;
+
+        self.loyalty_item.set_inner(LoyaltyItem {
+            item_id,
+            reward,
+            cost_points,
+            stock,
+            times_redeemed: 0,
+            is_active: true,
+            bump: bumps.loyalty_item,
+        });
+
+        emit_item_updated(&self.loyalty_item, self.authority.key())
+    }
+}
+
+// Reprice, restock or retire a catalog entry
+#[derive(Accounts)]
+#[instruction(item_id: u64)]
+pub struct UpdateLoyaltyItem<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        seeds = [b"loyalty_item", item_id.to_le_bytes().as_ref()],
+        bump = loyalty_item.bump
+    )]
+    pub loyalty_item: Account<'info, LoyaltyItem>,
+
+    pub authority: Signer<'info>,
+}
+
+impl <'info> UpdateLoyaltyItem<'info> {
+    pub fn update_loyalty_item(&mut self, _item_id: u64, cost_points: u64, stock: Option<u32>, is_active: bool) -> Result<()> {
+        validate_loyalty_item(&self.loyalty_item.reward, cost_points)?;
+
+        self.loyalty_item.cost_points = cost_points;
+        self.loyalty_item.stock = stock;
+        self.loyalty_item.is_active = is_active;
+
+        emit_item_updated(&self.loyalty_item, self.authority.key())
+    }
+}
+
+// Move points from UserStats into the user's points token account
+#[derive(Accounts)]
+pub struct TokenizeLoyaltyPoints<'info> {
+    #[account(
+        mut,
+        seeds = [b"loyalty_config"],
+        bump = loyalty_config.bump
+    )]
+    pub loyalty_config: Account<'info, LoyaltyConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"points_mint"],
+        bump = loyalty_config.points_mint_bump,
+        mint::token_program = token_program,
+    )]
+    pub points_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = points_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_points_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl <'info> TokenizeLoyaltyPoints<'info> {
+    pub fn tokenize_loyalty_points(&mut self, amount: u64) -> Result<()> {
+        // Validate the mint is enabled and the user holds the points
+        require!(
+            self.loyalty_config.points_mint == Some(self.points_mint.key()),
+            LoyaltyError::PointsMintNotEnabled
+        );
+        require!(amount > 0, LoyaltyError::InsufficientLoyaltyPoints);
+        require!(self.user_stats.loyalty_points >= amount, LoyaltyError::InsufficientLoyaltyPoints);
+
+        self.user_stats.loyalty_points -= amount;
+        self.loyalty_config.total_points_tokenized += amount;
+
+        let mint_instruction = MintTo {
+            mint: self.points_mint.to_account_info(),
+            to: self.user_points_account.to_account_info(),
+            authority: self.loyalty_config.to_account_info(),
+        };
+
+        let seeds : &[&[&[u8]]] = &[&[b"loyalty_config", &[self.loyalty_config.bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), mint_instruction, seeds);
+        mint_to(cpi_ctx, amount)?;
+
+        emit!(LoyaltyPointsTokenized {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            amount,
+            points_remaining: self.user_stats.loyalty_points,
+        });
+        Ok(())
+    }
+}
+
+// Spend points on a catalog entry. Points come from UserStats, or are burned from the
+// user's points token account when `from_tokens` is set.
+#[derive(Accounts)]
+#[instruction(item_id: u64)]
+pub struct RedeemLoyalty<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"loyalty_config"],
+        bump = loyalty_config.bump
+    )]
+    pub loyalty_config: Account<'info, LoyaltyConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"loyalty_item", item_id.to_le_bytes().as_ref()],
+        bump = loyalty_item.bump
+    )]
+    pub loyalty_item: Account<'info, LoyaltyItem>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub points_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub user_points_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl <'info> RedeemLoyalty<'info> {
+    pub fn redeem_loyalty(&mut self, _item_id: u64, from_tokens: bool) -> Result<()> {
+        let item = &self.loyalty_item;
+        let cost_points = item.cost_points;
+
+        // Validate the item can be redeemed
+        require!(item.is_active, LoyaltyError::LoyaltyItemNotActive);
+        require!(item.stock != Some(0), LoyaltyError::LoyaltyItemOutOfStock);
+
+        // Burn the points
+        if from_tokens {
+            let (Some(points_mint), Some(user_points_account)) = (&self.points_mint, &self.user_points_account) else {
+                return err!(LoyaltyError::PointsMintNotEnabled);
+            };
+            require!(
+                self.loyalty_config.points_mint == Some(points_mint.key()),
+                LoyaltyError::PointsMintNotEnabled
+            );
+            require!(user_points_account.amount >= cost_points, LoyaltyError::InsufficientLoyaltyPoints);
+
+            let burn_instruction = Burn {
+                mint: points_mint.to_account_info(),
+                from: user_points_account.to_account_info(),
+                authority: self.user.to_account_info(),
+            };
+            burn(CpiContext::new(self.token_program.to_account_info(), burn_instruction), cost_points)?;
+        } else {
+            require!(self.user_stats.loyalty_points >= cost_points, LoyaltyError::InsufficientLoyaltyPoints);
+            self.user_stats.loyalty_points -= cost_points;
+        }
+
+        // Deliver the reward
+        let reward = self.loyalty_item.reward.clone();
+        match reward {
+            LoyaltyReward::FreeSpins { count, bet_amount } => {
+                let user_stats = &mut self.user_stats;
+                require!(
+                    user_stats.free_spins == 0 || user_stats.free_spin_bet == bet_amount,
+                    LoyaltyError::FreeSpinBetMismatch
+                );
+                user_stats.free_spins = user_stats.free_spins.checked_add(count).ok_or(CasinoError::MathOverflow)?;
+                user_stats.free_spin_bet = bet_amount;
+            },
+            LoyaltyReward::UsdcCashback { amount } => {
+                // Validate the remaining bankroll still covers every pending game
+                let remaining = self.casino_vault.amount
+                    .checked_sub(amount)
+                    .ok_or(CasinoError::InsufficientBankroll)?;
+                validate_exposure(
+                    self.casino_state.outstanding_liabilities,
+                    0,
+                    remaining,
+                    self.casino_state.max_exposure_bps
+                )?;
+
+                let cashback_instruction = TransferChecked {
+                    from: self.casino_vault.to_account_info(),
+                    to: self.user_token_account.to_account_info(),
+                    authority: self.casino_state.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                };
+
+                let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[self.casino_state.casino_state_bump]]];
+                let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cashback_instruction, seeds);
+                transfer_checked(cpi_ctx, amount, self.usdc_mint.decimals)?;
+            },
+            LoyaltyReward::QuestBoost { boost_bps, duration } => {
+                let current_time = Clock::get()?.unix_timestamp;
+                self.user_stats.quest_boost_bps = boost_bps;
+                self.user_stats.quest_boost_expires_at = current_time + duration;
+            },
+        }
+
+        // Update catalog stats
+        let item = &mut self.loyalty_item;
+        item.stock = item.stock.map(|stock| stock - 1);
+        item.times_redeemed += 1;
+        self.loyalty_config.total_points_redeemed += cost_points;
+
+        emit!(LoyaltyRedeemed {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            item: item.key(),
+            item_id: item.item_id,
+            reward: item.reward.clone(),
+            cost_points,
+            from_tokens,
+        });
+        Ok(())
+    }
+}
+
+fn validate_loyalty_item(reward: &LoyaltyReward, cost_points: u64) -> Result<()> {
+    require!(cost_points > 0, LoyaltyError::InvalidLoyaltyReward);
+
+    let is_valid = match reward {
+        LoyaltyReward::FreeSpins { count, bet_amount } => *count > 0 && *bet_amount > 0,
+        LoyaltyReward::UsdcCashback { amount } => *amount > 0,
+        LoyaltyReward::QuestBoost { boost_bps, duration } => {
+            *boost_bps > 0 && *boost_bps <= MAX_QUEST_BOOST_BPS && *duration > 0
+        },
+    };
+    require!(is_valid, LoyaltyError::InvalidLoyaltyReward);
+    Ok(())
+}
+
+fn emit_item_updated(item: &Account<LoyaltyItem>, authority: Pubkey) -> Result<()> {
+    emit!(LoyaltyItemUpdated {
+        version: EVENT_VERSION,
+        slot: Clock::get()?.slot,
+        authority,
+        item: item.key(),
+        item_id: item.item_id,
+        reward: item.reward.clone(),
+        cost_points: item.cost_points,
+        stock: item.stock,
+        is_active: item.is_active,
+    });
+    Ok(())
+}
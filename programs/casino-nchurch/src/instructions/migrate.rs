@@ -0,0 +1,436 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::casino::*;
+use crate::state::quest::*;
+use crate::state::migration::*;
+use crate::events::*;
+use crate::errors::*;
+
+// Account layout migrations.
+// CasinoState, UserStats and QuestCampaign grew after launch, so accounts created on an older
+// layout no longer deserialize. Each migration reads the old layout, grows the account to the
+// current size (the payer tops up rent), and rewrites it with the fields it had and defaults
+// for the rest. Players' stats and campaigns migrate one account at a time and anyone may pay
+// for it.
+
+// Upgrade the casino state and create the fee vault it now points at
+#[derive(Accounts)]
+pub struct MigrateCasinoState<'info> {
+    /// CHECK: The casino state PDA on an older layout; the handler checks the owner, layout and authority.
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump
+    )]
+    pub casino_state: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = casino_state,
+        token::token_program = token_program,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> MigrateCasinoState<'info> {
+    pub fn migrate_casino_state(&mut self, bumps: &MigrateCasinoStateBumps) -> Result<()> {
+        let legacy = load_legacy::<CasinoStateV1>(&self.casino_state, CasinoState::DISCRIMINATOR)?;
+        require_keys_eq!(legacy.authority, self.authority.key(), CasinoError::Unauthorized);
+
+        let casino_state = migrated_casino_state(legacy, self.fee_vault.key(), bumps.fee_vault);
+        resize_account(
+            &self.casino_state,
+            &self.authority,
+            &self.system_program,
+            CasinoState::DISCRIMINATOR.len() + CasinoState::INIT_SPACE
+        )?;
+        casino_state.try_serialize(&mut &mut self.casino_state.try_borrow_mut_data()?[..])?;
+
+        emit!(AccountLayoutMigrated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            account: self.casino_state.key(),
+            old_layout_version: 1,
+            new_layout_version: CASINO_STATE_VERSION,
+        });
+        Ok(())
+    }
+}
+
+// Upgrade one player's stats
+#[derive(Accounts)]
+pub struct MigrateUserStats<'info> {
+    /// CHECK: The player's UserStats PDA on an older layout; the handler checks the owner and layout.
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: UncheckedAccount<'info>,
+
+    /// CHECK: Only seeds the UserStats PDA.
+    pub user: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> MigrateUserStats<'info> {
+    pub fn migrate_user_stats(&mut self) -> Result<()> {
+        let legacy = load_legacy::<UserStatsV1>(&self.user_stats, UserStats::DISCRIMINATOR)?;
+        require_keys_eq!(legacy.user, self.user.key(), CasinoError::UnsupportedAccountLayout);
+
+        let user_stats = migrated_user_stats(legacy);
+        resize_account(
+            &self.user_stats,
+            &self.payer,
+            &self.system_program,
+            UserStats::DISCRIMINATOR.len() + UserStats::INIT_SPACE
+        )?;
+        user_stats.try_serialize(&mut &mut self.user_stats.try_borrow_mut_data()?[..])?;
+
+        emit!(AccountLayoutMigrated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            account: self.user_stats.key(),
+            old_layout_version: 1,
+            new_layout_version: USER_STATS_VERSION,
+        });
+        Ok(())
+    }
+}
+
+// Upgrade one quest campaign. The campaign's completed participations may be passed as
+// remaining accounts to rebuild its completion counters.
+#[derive(Accounts)]
+#[instruction(campaign_counter: u64)]
+pub struct MigrateQuestCampaign<'info> {
+    /// CHECK: The QuestCampaign PDA on an older layout; the handler checks the owner and layout.
+    #[account(
+        mut,
+        seeds = [b"quest_campaign", creator.key().as_ref(), &campaign_counter.to_le_bytes()],
+        bump
+    )]
+    pub quest_campaign: UncheckedAccount<'info>,
+
+    /// CHECK: Only seeds the QuestCampaign PDA.
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> MigrateQuestCampaign<'info> {
+    pub fn migrate_quest_campaign(&mut self, _campaign_counter: u64, remaining_accounts: &[AccountInfo]) -> Result<()> {
+        let legacy = load_legacy::<QuestCampaignV1>(&self.quest_campaign, QuestCampaign::DISCRIMINATOR)?;
+        require_keys_eq!(legacy.creator, self.creator.key(), CasinoError::UnsupportedAccountLayout);
+
+        // Count the completed participations passed in (in ascending key order, so each counts once)
+        let mut completions = Vec::with_capacity(remaining_accounts.len());
+        let mut previous: Option<Pubkey> = None;
+        for account in remaining_accounts {
+            if let Some(previous) = previous {
+                require!(previous < account.key(), CasinoError::UnsupportedAccountLayout);
+            }
+            previous = Some(account.key());
+
+            require_keys_eq!(*account.owner, crate::ID, CasinoError::UnsupportedAccountLayout);
+            let participation = QuestParticipation::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+            require_keys_eq!(participation.campaign, self.quest_campaign.key(), CasinoError::UnsupportedAccountLayout);
+            completions.push((participation.completion_status.clone(), participation.reward_amount));
+        }
+
+        let quest_campaign = migrated_quest_campaign(legacy, &completions)?;
+        resize_account(
+            &self.quest_campaign,
+            &self.payer,
+            &self.system_program,
+            QuestCampaign::DISCRIMINATOR.len() + QuestCampaign::INIT_SPACE
+        )?;
+        quest_campaign.try_serialize(&mut &mut self.quest_campaign.try_borrow_mut_data()?[..])?;
+
+        emit!(AccountLayoutMigrated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            account: self.quest_campaign.key(),
+            old_layout_version: 1,
+            new_layout_version: QUEST_CAMPAIGN_VERSION,
+        });
+        Ok(())
+    }
+}
+
+// Version 1 accounts are told apart by their length, so they must be shorter than any later layout
+const _: () = assert!(CasinoStateV1::INIT_SPACE < CasinoState::INIT_SPACE);
+const _: () = assert!(UserStatsV1::INIT_SPACE < UserStats::INIT_SPACE);
+const _: () = assert!(QuestCampaignV1::INIT_SPACE < QuestCampaign::INIT_SPACE);
+
+// Deserialize a program account that is exactly the size of the version 1 layout `T`
+fn load_legacy<T: AnchorDeserialize + Space>(account: &AccountInfo, discriminator: &[u8]) -> Result<T> {
+    require_keys_eq!(*account.owner, crate::ID, CasinoError::UnsupportedAccountLayout);
+
+    let data = account.try_borrow_data()?;
+    require!(data.len() == discriminator.len() + T::INIT_SPACE, CasinoError::UnsupportedAccountLayout);
+    require!(data.starts_with(discriminator), CasinoError::UnsupportedAccountLayout);
+
+    T::deserialize(&mut &data[discriminator.len()..]).map_err(|_| error!(CasinoError::UnsupportedAccountLayout))
+}
+
+// Grow an account to `new_len`, topping up rent from the payer
+fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_due = Rent::get()?.minimum_balance(new_len).saturating_sub(account.lamports());
+    if rent_due > 0 {
+        crate::utils::vrf::transfer_funds(
+            system_program.to_account_info(),
+            payer.to_account_info(),
+            account.clone(),
+            rent_due,
+            None,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Current casino state for a version 1 one: counters and RTPs carry over, everything the
+/// old layout lacked starts at its initialize_casino value
+pub fn migrated_casino_state(legacy: CasinoStateV1, fee_vault: Pubkey, fee_vault_bump: u8) -> CasinoState {
+    CasinoState {
+        authority: legacy.authority,
+        pending_authority: None,
+        vault: legacy.vault,
+        fee_vault,
+        total_games_played: legacy.total_games_played,
+        total_volume: legacy.total_volume,
+        total_payouts: legacy.total_payouts,
+        total_fees_collected: 0,
+        outstanding_liabilities: 0,
+        pending_stakes: 0,
+        rakeback_owed: 0,
+        house_edge_config: HouseEdgeConfig {
+            slots_rtp_bps: legacy.house_edge_config.slots_rtp_bps,
+            roulette_rtp_bps: crate::utils::roulette::ROULETTE_RTP_BPS,
+            aviator_rtp_bps: legacy.house_edge_config.aviator_rtp_bps,
+            blackjack_rtp_bps: legacy.house_edge_config.blackjack_rtp_bps,
+            platform_fee_bps: legacy.house_edge_config.platform_fee_bps,
+            fee_basis: DEFAULT_HOUSE_EDGE_CONFIG.fee_basis,
+        },
+        referral_config: crate::state::referral::ReferralConfig::default(),
+        is_active: legacy.is_active,
+        max_pending_games: DEFAULT_MAX_PENDING_GAMES,
+        max_exposure_bps: DEFAULT_MAX_EXPOSURE_BPS,
+        fee_vault_bump,
+        casino_state_bump: legacy.casino_state_bump,
+        layout_version: CASINO_STATE_VERSION,
+    }
+}
+
+/// Current stats for a version 1 account: lifetime totals carry over, the rest starts empty
+pub fn migrated_user_stats(legacy: UserStatsV1) -> UserStats {
+    UserStats {
+        user: legacy.user,
+        total_bets: legacy.total_bets,
+        total_wins: legacy.total_wins,
+        total_losses: legacy.total_losses,
+        loyalty_points: legacy.loyalty_points,
+        games_played: legacy.games_played,
+        game_nonce: 0,
+        pending_games: 0,
+        free_spins: 0,
+        free_spin_bet: 0,
+        quest_boost_bps: 0,
+        quest_boost_expires_at: 0,
+        vip_tier: 0,
+        daily_volume: [0; 30],
+        last_volume_day: 0,
+        rakeback_accrued: 0,
+        active_tournament: None,
+        tournament_ends_at: 0,
+        bump: legacy.bump,
+        layout_version: USER_STATS_VERSION,
+    }
+}
+
+/// Current campaign for a version 1 one, given the (status, reward) of completed participations.
+/// Version 1 paid every completion exactly reward_pool / max_participants, so leaving one out
+/// reserves the same pool for the others as counting it: the counters stay safe if incomplete.
+pub fn migrated_quest_campaign(legacy: QuestCampaignV1, completions: &[(CompletionStatus, u64)]) -> Result<QuestCampaign> {
+    let mut completed_participants = 0u32;
+    let mut rewards_committed = 0u64;
+    for (status, reward_amount) in completions {
+        if *status == CompletionStatus::Pending {
+            continue;
+        }
+        completed_participants = completed_participants.checked_add(1).ok_or(CasinoError::MathOverflow)?;
+        rewards_committed = rewards_committed.checked_add(*reward_amount).ok_or(CasinoError::MathOverflow)?;
+    }
+    require!(
+        completed_participants <= legacy.current_participants && rewards_committed <= legacy.reward_pool,
+        CasinoError::UnsupportedAccountLayout
+    );
+
+    Ok(QuestCampaign {
+        creator: legacy.creator,
+        title: legacy.title,
+        description: legacy.description,
+        quest_type: legacy.quest_type,
+        reward_pool: legacy.reward_pool,
+        max_participants: legacy.max_participants,
+        current_participants: legacy.current_participants,
+        start_time: legacy.start_time,
+        end_time: legacy.end_time,
+        status: legacy.status,
+        completion_criteria: legacy.completion_criteria,
+        verification_method: legacy.verification_method,
+        is_active: legacy.is_active,
+        completed_participants,
+        rewards_committed,
+        campaign_counter: legacy.campaign_counter,
+        bump: legacy.bump,
+        layout_version: QUEST_CAMPAIGN_VERSION,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_casino_state() -> CasinoStateV1 {
+        CasinoStateV1 {
+            authority: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            total_games_played: 12,
+            total_volume: 3_400,
+            total_payouts: 3_100,
+            house_edge_config: HouseEdgeConfigV1 {
+                slots_rtp_bps: 9_400,
+                roulette_rtp_bps: 9_730,
+                aviator_rtp_bps: 9_700,
+                blackjack_rtp_bps: 9_900,
+                platform_fee_bps: 150,
+            },
+            is_active: true,
+            casino_state_bump: 254,
+        }
+    }
+
+    #[test]
+    fn legacy_layouts_serialize_to_their_space() {
+        assert_eq!(legacy_casino_state().try_to_vec().unwrap().len(), CasinoStateV1::INIT_SPACE);
+        let stats = UserStatsV1 { user: Pubkey::new_unique(), total_bets: 1, total_wins: 2, total_losses: 3, loyalty_points: 4, games_played: 5, bump: 6 };
+        assert_eq!(stats.try_to_vec().unwrap().len(), UserStatsV1::INIT_SPACE);
+    }
+
+    #[test]
+    fn casino_state_keeps_its_totals_and_config() {
+        let legacy = legacy_casino_state();
+        let fee_vault = Pubkey::new_unique();
+        let migrated = migrated_casino_state(legacy.clone(), fee_vault, 7);
+
+        assert_eq!(migrated.authority, legacy.authority);
+        assert_eq!(migrated.vault, legacy.vault);
+        assert_eq!(migrated.fee_vault, fee_vault);
+        assert_eq!(migrated.fee_vault_bump, 7);
+        assert_eq!(migrated.casino_state_bump, 254);
+        assert_eq!((migrated.total_games_played, migrated.total_volume, migrated.total_payouts), (12, 3_400, 3_100));
+        assert_eq!(migrated.house_edge_config.slots_rtp_bps, 9_400);
+        assert_eq!(migrated.house_edge_config.platform_fee_bps, 150);
+        assert_eq!(migrated.layout_version, CASINO_STATE_VERSION);
+
+        // The migrated account fits the space it is resized to
+        let mut data = Vec::new();
+        migrated.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= CasinoState::DISCRIMINATOR.len() + CasinoState::INIT_SPACE);
+        assert_eq!(CasinoState::try_deserialize(&mut data.as_slice()).unwrap().total_volume, 3_400);
+    }
+
+    #[test]
+    fn user_stats_keep_lifetime_totals() {
+        let legacy = UserStatsV1 { user: Pubkey::new_unique(), total_bets: 10, total_wins: 4, total_losses: 6, loyalty_points: 90, games_played: 10, bump: 250 };
+        let migrated = migrated_user_stats(legacy.clone());
+
+        assert_eq!(migrated.user, legacy.user);
+        assert_eq!((migrated.total_bets, migrated.total_wins, migrated.total_losses), (10, 4, 6));
+        assert_eq!(migrated.loyalty_points, 90);
+        assert_eq!(migrated.games_played, 10);
+        assert_eq!(migrated.bump, 250);
+        assert_eq!(migrated.pending_games, 0);
+        assert_eq!(migrated.layout_version, USER_STATS_VERSION);
+
+        let mut data = Vec::new();
+        migrated.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= UserStats::DISCRIMINATOR.len() + UserStats::INIT_SPACE);
+        assert_eq!(UserStats::try_deserialize(&mut data.as_slice()).unwrap().loyalty_points, 90);
+    }
+
+    fn legacy_quest_campaign() -> QuestCampaignV1 {
+        QuestCampaignV1 {
+            creator: Pubkey::new_unique(),
+            title: "Follow the chapel".to_string(),
+            description: "Follow and retweet".to_string(),
+            quest_type: QuestType::Social,
+            reward_pool: 9_800_000,
+            max_participants: 10,
+            current_participants: 4,
+            start_time: 1_700_000_000,
+            end_time: 1_702_592_000,
+            status: QuestStatus::Active,
+            completion_criteria: "Complete the quest requirements".to_string(),
+            verification_method: "Manual verification".to_string(),
+            is_active: true,
+            campaign_counter: 3,
+            bump: 253,
+        }
+    }
+
+    #[test]
+    fn quest_campaign_rebuilds_its_completion_counters() {
+        let legacy = legacy_quest_campaign();
+        let completions = [
+            (CompletionStatus::Rewarded, 980_000),
+            (CompletionStatus::Completed, 980_000),
+            (CompletionStatus::Pending, 0),
+        ];
+        let migrated = migrated_quest_campaign(legacy.clone(), &completions).unwrap();
+
+        assert_eq!(migrated.creator, legacy.creator);
+        assert_eq!(migrated.title, legacy.title);
+        assert_eq!((migrated.reward_pool, migrated.max_participants, migrated.current_participants), (9_800_000, 10, 4));
+        assert_eq!((migrated.campaign_counter, migrated.bump), (3, 253));
+        assert_eq!(migrated.completed_participants, 2);
+        assert_eq!(migrated.rewards_committed, 1_960_000);
+        assert_eq!(migrated.layout_version, QUEST_CAMPAIGN_VERSION);
+
+        // The version 1 layout is told apart by its allocated length, not its serialized one
+        assert!(legacy.try_to_vec().unwrap().len() <= QuestCampaignV1::INIT_SPACE);
+        let mut data = Vec::new();
+        migrated.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= QuestCampaign::DISCRIMINATOR.len() + QuestCampaign::INIT_SPACE);
+        assert_eq!(QuestCampaign::try_deserialize(&mut data.as_slice()).unwrap().rewards_committed, 1_960_000);
+
+        // More completions than participants cannot be real
+        let too_many = vec![(CompletionStatus::Completed, 980_000); 5];
+        assert!(migrated_quest_campaign(legacy, &too_many).is_err());
+    }
+}
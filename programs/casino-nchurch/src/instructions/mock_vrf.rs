@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::utils::vrf::MockRandomness;
+
+// Localnet/test randomness backend (`mock-vrf` feature only).
+// Test code writes the committed seed slot, the reveal slot and the exact bytes a game will
+// settle with, then passes the account wherever a Switchboard randomness account is expected.
+// Rewriting the same account moves it from "committed" to "revealed".
+#[derive(Accounts)]
+#[instruction(mock_id: u64)]
+pub struct WriteMockRandomness<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MockRandomness::DISCRIMINATOR.len() + MockRandomness::INIT_SPACE,
+        seeds = [b"mock_randomness", authority.key().as_ref(), mock_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub mock_randomness: Account<'info, MockRandomness>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> WriteMockRandomness<'info> {
+    pub fn write_mock_randomness(
+        &mut self,
+        mock_id: u64,
+        seed_slot: u64,
+        reveal_slot: u64,
+        value: [u8; 32],
+        strict_reveal: bool,
+        bumps: &WriteMockRandomnessBumps
+    ) -> Result<()> {
+        self.mock_randomness.set_inner(MockRandomness {
+            authority: self.authority.key(),
+            mock_id,
+            seed_slot,
+            reveal_slot,
+            value,
+            strict_reveal,
+            bump: bumps.mock_randomness,
+        });
+
+        Ok(())
+    }
+}
@@ -1,9 +1,43 @@
 // pub mod casino;
 pub mod casino_vrf;
+pub mod admin;
+pub mod bankroll;
+pub mod fees;
+pub mod settle;
+pub mod refund;
+pub mod blackjack;
+pub mod slots;
 pub mod quest;
 pub mod liquidity;
+pub mod loyalty;
+pub mod vip;
+pub mod limits;
+pub mod referral;
+pub mod session;
+pub mod jackpot;
+pub mod tournament;
+pub mod migrate;
+#[cfg(feature = "mock-vrf")]
+pub mod mock_vrf;
 
 // pub use casino::*;
 pub use casino_vrf::*;
+pub use admin::*;
+pub use bankroll::*;
+pub use fees::*;
+pub use settle::*;
+pub use refund::*;
+pub use blackjack::*;
+pub use slots::*;
 pub use quest::*;
 pub use liquidity::*;
+pub use loyalty::*;
+pub use vip::*;
+pub use limits::*;
+pub use referral::*;
+pub use session::*;
+pub use jackpot::*;
+pub use tournament::*;
+pub use migrate::*;
+#[cfg(feature = "mock-vrf")]
+pub use mock_vrf::*;
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::quest::*;
+use crate::state::casino::UserStats;
+use crate::events::*;
 use crate::errors::*;
+use crate::utils::math::{calculate_platform_fee, mul_bps};
 
 // Create quest campaign
 #[derive(Accounts)]
@@ -82,7 +85,7 @@ impl<'info> CreateQuestCampaign<'info> {
         require!(max_participants <= 10000, QuestError::InvalidCompletionCriteria);
         
         // Calculate platform fee
-        let platform_fee = calculate_platform_fee(reward_pool, quest_factory.platform_fee_bps);
+        let platform_fee = calculate_platform_fee(reward_pool, quest_factory.platform_fee_bps)?;
         let net_reward_pool = reward_pool - platform_fee;
         
         // Transfer reward pool from creator to quest vault
@@ -113,8 +116,11 @@ impl<'info> CreateQuestCampaign<'info> {
             completion_criteria: "Complete the quest requirements".to_string(),
             verification_method: "Manual verification".to_string(),
             is_active: true,
+            completed_participants: 0,
+            rewards_committed: 0,
             campaign_counter,
             bump: bumps.quest_campaign,
+            layout_version: crate::state::migration::QUEST_CAMPAIGN_VERSION,
         });
 
         
@@ -133,8 +139,17 @@ impl<'info> CreateQuestCampaign<'info> {
         quest_factory.total_campaigns += 1;
         quest_factory.total_rewards_distributed += platform_fee;
         
-        msg!("Quest campaign created: Title: {}, Reward Pool: {}, Max Participants: {}", 
-            title, net_reward_pool, max_participants);
+        emit!(QuestCreated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            creator: self.creator.key(),
+            campaign: quest_campaign.key(),
+            campaign_counter,
+            reward_pool: net_reward_pool,
+            platform_fee,
+            max_participants,
+            end_time: quest_campaign.end_time,
+        });
         Ok(())
     }
 
@@ -199,8 +214,13 @@ impl<'info> ParticipateInQuest<'info> {
         // Update quest campaign
         quest_campaign.current_participants += 1;
         
-        msg!("User participated in quest: User: {}, Campaign: {}", 
-            self.user.key(), quest_campaign.key());
+        emit!(QuestJoined {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            campaign: quest_campaign.key(),
+            participants: quest_campaign.current_participants,
+        });
         Ok(())
     }
 }
@@ -223,6 +243,13 @@ pub struct CompleteQuest<'info> {
     )]
     pub quest_participation: Account<'info, QuestParticipation>,
     
+    // Only needed to apply a quest boost redeemed from loyalty points
+    #[account(
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Option<Account<'info, UserStats>>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -251,15 +278,38 @@ impl <'info> CompleteQuest<'info> {
         // Calculate reward amount
         let reward_per_participant = quest_campaign.reward_pool / (quest_campaign.max_participants as u64);
         
+        // Apply an active quest boost, paid only from pool left over after every other
+        // participant who can still complete is covered
+        let boost_bps = match &self.user_stats {
+            Some(user_stats) if user_stats.quest_boost_expires_at >= current_time => user_stats.quest_boost_bps,
+            _ => 0,
+        };
+        let boosted_reward = reward_per_participant + mul_bps(reward_per_participant, boost_bps as u64)?;
+        let reserved_for_others = reward_per_participant
+            * quest_campaign.max_participants.saturating_sub(quest_campaign.completed_participants + 1) as u64;
+        let available = quest_campaign.reward_pool
+            .saturating_sub(quest_campaign.rewards_committed)
+            .saturating_sub(reserved_for_others);
+        let reward_amount = boosted_reward.min(available);
+        
+        // Update quest campaign
+        quest_campaign.completed_participants += 1;
+        quest_campaign.rewards_committed += reward_amount;
+        
         // Update quest participation
         quest_participation.completion_status = CompletionStatus::Completed;
         quest_participation.completion_time = Some(current_time);
-        quest_participation.reward_amount = reward_per_participant;
+        quest_participation.reward_amount = reward_amount;
         quest_participation.verification_data = "Quest completed successfully".to_string();
         quest_participation.is_verified = true;
         
-        msg!("Quest completed: User: {}, Campaign: {}, Reward: {}", 
-            self.user.key(), quest_campaign.key(), reward_per_participant);
+        emit!(QuestCompleted {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            campaign: quest_campaign.key(),
+            reward_amount,
+        });
         Ok(())
     }
 }
@@ -355,14 +405,16 @@ impl<'info> DistributeQuestRewards<'info> {
             quest_campaign.status = QuestStatus::Completed;
         }
         
-        msg!("Quest rewards distributed: User: {}, Amount: {}", 
-            self.user_token_account.key(), quest_participation.reward_amount);
+        emit!(QuestRewardPaid {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: quest_participation.user,
+            campaign: quest_campaign.key(),
+            recipient_token_account: self.user_token_account.key(),
+            amount: quest_participation.reward_amount,
+            distribution_complete: quest_rewards.distribution_complete,
+        });
         Ok(())
     }
 
 }
-
-// Helper function to calculate platform fee
-fn calculate_platform_fee(amount: u64, fee_bps: u16) -> u64 {
-    (amount * fee_bps as u64) / 10000
-}
@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::casino::*;
+use crate::state::referral::*;
+use crate::events::*;
+use crate::errors::*;
+use crate::utils::referral::*;
+
+// Referrals. A player sets their referrer once and inherits the referrer's own upline, up to
+// MAX_REFERRAL_LEVELS deep. Settle handlers take the player's Referral PDA as an unchecked
+// account and, when it exists, the upline's ReferrerStats accounts as remaining accounts in
+// upline order; each level's share is carved out of the game's platform fee and left in the
+// fee vault until the referrer claims it.
+
+// Set the player's referrer (once)
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct SetReferrer<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = Referral::DISCRIMINATOR.len() + Referral::INIT_SPACE,
+        seeds = [b"referral", user.key().as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    /// CHECK: The referrer's own Referral PDA, read only when the referrer was referred themselves.
+    #[account(
+        seeds = [b"referral", referrer.as_ref()],
+        bump
+    )]
+    pub referrer_referral: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ReferrerStats::DISCRIMINATOR.len() + ReferrerStats::INIT_SPACE,
+        seeds = [b"referrer_stats", referrer.as_ref()],
+        bump
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> SetReferrer<'info> {
+    pub fn set_referrer(&mut self, referrer: Pubkey, bumps: &SetReferrerBumps) -> Result<()> {
+        // Validate the referrer is someone else and the link does not close a loop
+        let referrer_upline = load_referral(&self.referrer_referral)?
+            .map(|referral| referral.upline)
+            .unwrap_or_default();
+        require!(referrer != self.user.key(), ReferralError::InvalidReferrer);
+        require!(!referrer_upline.contains(&self.user.key()), ReferralError::InvalidReferrer);
+
+        let mut upline = vec![referrer];
+        upline.extend(referrer_upline.into_iter().take(MAX_REFERRAL_LEVELS - 1));
+
+        self.referral.set_inner(Referral {
+            user: self.user.key(),
+            upline: upline.clone(),
+            created_at: Clock::get()?.unix_timestamp,
+            bump: bumps.referral,
+        });
+
+        let referrer_stats = &mut self.referrer_stats;
+        referrer_stats.referrer = referrer;
+        referrer_stats.direct_referrals += 1;
+        referrer_stats.bump = bumps.referrer_stats;
+
+        emit!(ReferrerSet {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            referrer,
+            upline,
+        });
+        Ok(())
+    }
+}
+
+// Pay out a referrer's credited earnings from the fee vault
+#[derive(Accounts)]
+pub struct ClaimReferralEarnings<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = casino_state.fee_vault_bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer_stats", referrer.key().as_ref()],
+        bump = referrer_stats.bump
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = referrer,
+        token::token_program = token_program,
+    )]
+    pub referrer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl <'info> ClaimReferralEarnings<'info> {
+    pub fn claim_referral_earnings(&mut self) -> Result<()> {
+        let amount = self.referrer_stats.claimable;
+        require!(amount > 0, ReferralError::NoReferralEarnings);
+
+        self.referrer_stats.claimable = 0;
+        self.referrer_stats.total_claimed += amount;
+
+        let claim_instruction = TransferChecked {
+            from: self.fee_vault.to_account_info(),
+            to: self.referrer_token_account.to_account_info(),
+            authority: self.casino_state.to_account_info(),
+            mint: self.usdc_mint.to_account_info(),
+        };
+
+        let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[self.casino_state.casino_state_bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), claim_instruction, seeds);
+        transfer_checked(cpi_ctx, amount, self.usdc_mint.decimals)?;
+
+        emit!(ReferralEarningsClaimed {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            referrer: self.referrer.key(),
+            amount,
+            total_claimed: self.referrer_stats.total_claimed,
+        });
+        Ok(())
+    }
+}
+
+fn load_referral(referral: &AccountInfo) -> Result<Option<Referral>> {
+    // Players who were never referred have no account at the PDA
+    if referral.owner != &crate::ID || referral.data_is_empty() {
+        return Ok(None);
+    }
+    let data = referral.try_borrow_data()?;
+    Ok(Some(Referral::try_deserialize(&mut &data[..])?))
+}
+
+/// Credit the player's upline its share of a settled game's platform fee. `referrer_stats`
+/// are the settle's remaining accounts, one ReferrerStats per upline level in order. Returns
+/// the total credited, which stays in the fee vault instead of being booked to the fee epoch.
+pub fn credit_referrers(
+    referral: &AccountInfo,
+    referrer_stats: &[AccountInfo],
+    referral_config: &ReferralConfig,
+    wagered: u64,
+    payout: u64,
+    fee: u64,
+) -> Result<u64> {
+    let Some(referral) = load_referral(referral)? else {
+        return Ok(0);
+    };
+    require!(referrer_stats.len() >= referral.upline.len(), ReferralError::InvalidReferrerStats);
+
+    let shares = calculate_referral_shares(referral_config, referral.upline.len(), wagered, payout, fee)?;
+    let mut total_credited = 0;
+    for (level, (referrer, stats_info)) in referral.upline.iter().zip(referrer_stats).enumerate() {
+        // Validate the account is the upline member's ReferrerStats (created only at its PDA)
+        require!(stats_info.owner == &crate::ID && stats_info.is_writable, ReferralError::InvalidReferrerStats);
+        let mut stats = ReferrerStats::try_deserialize(&mut &stats_info.try_borrow_data()?[..])?;
+        require!(stats.referrer == *referrer, ReferralError::InvalidReferrerStats);
+
+        let amount = shares[level];
+        stats.referred_games += 1;
+        stats.referred_volume += wagered;
+        stats.total_earned += amount;
+        stats.claimable += amount;
+        stats.try_serialize(&mut &mut stats_info.try_borrow_mut_data()?[..])?;
+        total_credited += amount;
+
+        if amount > 0 {
+            emit!(ReferralCredited {
+                version: EVENT_VERSION,
+                slot: Clock::get()?.slot,
+                user: referral.user,
+                referrer: *referrer,
+                level: level as u8 + 1,
+                amount,
+            });
+        }
+    }
+    Ok(total_credited)
+}
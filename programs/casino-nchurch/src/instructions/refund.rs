@@ -0,0 +1,549 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::state::casino::*;
+use crate::state::jackpot::*;
+use crate::utils::vrf::*;
+use crate::events::*;
+use crate::errors::*;
+
+
+// Refund a game whose randomness was never revealed (casino authority only, after the timeout,
+// as long as the committed value is still unrevealed). The player cannot call this themselves:
+// they can read the oracle's reveal off-chain before it lands, so a player-callable refund would
+// let them withhold every losing reveal and take their stake back.
+#[derive(Accounts)]
+#[instruction(game_nonce: u64)]
+pub struct RefundExpiredGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"vrf_game_state", user.key().as_ref(), &game_nonce.to_le_bytes()],
+        bump = vrf_game_state.bump
+    )]
+    pub vrf_game_state: Account<'info, VrfGameState>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state", [vrf_game_state.game_type.clone() as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"progressive_jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Account<'info, ProgressiveJackpot>>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot_vault"],
+        bump
+    )]
+    pub jackpot_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The account's data is validated manually within the handler.
+    pub randomness_account_data: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl <'info> RefundExpiredGame<'info> {
+    pub fn refund_expired_game(&mut self, _game_nonce: u64) -> Result<()> {
+        // Validate game is still waiting on randomness
+        require!(
+            self.vrf_game_state.game_phase == GamePhase::Requested,
+            CasinoError::InvalidGameState
+        );
+
+        // Validate the reveal window has passed
+        validate_request_expired(self.vrf_game_state.commit_slot)?;
+
+        // Validate the committed randomness was never revealed (a seen loss must be settled)
+        require!(
+            self.vrf_game_state.randomness_account == self.randomness_account_data.key(),
+            CasinoError::VrfRequestFailed
+        );
+        let randomness_data = parse_randomness_data(&self.randomness_account_data)?;
+        validate_randomness_unrevealed(&randomness_data, self.vrf_game_state.commit_slot)?;
+
+        // A free spin is refunded as the spin itself
+        let wagered = self.vrf_game_state.wagered();
+        if self.vrf_game_state.is_free_spin {
+            self.user_stats.free_spins += 1;
+        } else {
+            // The jackpot's slice comes back from the jackpot vault, the rest from the casino vault
+            let returned = crate::instructions::jackpot::return_jackpot_contribution(
+                &self.casino_state,
+                self.jackpot.as_mut(),
+                self.jackpot_vault.as_ref(),
+                &self.user_token_account,
+                &self.usdc_mint,
+                &self.token_program,
+                self.vrf_game_state.jackpot_contribution,
+            )?;
+            refund_bet(
+                &self.casino_state,
+                &self.casino_vault,
+                &self.user_token_account,
+                &self.usdc_mint,
+                &self.token_program,
+                wagered - returned,
+            )?;
+        }
+
+        reverse_game_accounting(&mut self.casino_state, &mut self.game_state, &mut self.user_stats, wagered, self.vrf_game_state.max_payout)?;
+        crate::instructions::limits::credit_player_limits(&self.player_limits, wagered, wagered, Clock::get()?.unix_timestamp)?;
+
+        self.vrf_game_state.game_phase = GamePhase::Refunded;
+        self.vrf_game_state.is_complete = true;
+
+        emit!(GameRefunded {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            game: self.vrf_game_state.key(),
+            game_type: self.vrf_game_state.game_type.clone(),
+            nonce: self.vrf_game_state.nonce,
+            amount: wagered,
+            voided: false,
+        });
+        Ok(())
+    }
+}
+
+// Void a game whose randomness account is known to be broken (casino authority only)
+#[derive(Accounts)]
+#[instruction(game_nonce: u64)]
+pub struct VoidGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"vrf_game_state", user.key().as_ref(), &game_nonce.to_le_bytes()],
+        bump = vrf_game_state.bump
+    )]
+    pub vrf_game_state: Account<'info, VrfGameState>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state", [vrf_game_state.game_type.clone() as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"progressive_jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Account<'info, ProgressiveJackpot>>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot_vault"],
+        bump
+    )]
+    pub jackpot_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl <'info> VoidGame<'info> {
+    pub fn void_game(&mut self, _game_nonce: u64) -> Result<()> {
+        // Validate game has not been settled
+        require!(
+            self.vrf_game_state.game_phase == GamePhase::Requested,
+            CasinoError::InvalidGameState
+        );
+
+        // A free spin is refunded as the spin itself
+        let wagered = self.vrf_game_state.wagered();
+        if self.vrf_game_state.is_free_spin {
+            self.user_stats.free_spins += 1;
+        } else {
+            // The jackpot's slice comes back from the jackpot vault, the rest from the casino vault
+            let returned = crate::instructions::jackpot::return_jackpot_contribution(
+                &self.casino_state,
+                self.jackpot.as_mut(),
+                self.jackpot_vault.as_ref(),
+                &self.user_token_account,
+                &self.usdc_mint,
+                &self.token_program,
+                self.vrf_game_state.jackpot_contribution,
+            )?;
+            refund_bet(
+                &self.casino_state,
+                &self.casino_vault,
+                &self.user_token_account,
+                &self.usdc_mint,
+                &self.token_program,
+                wagered - returned,
+            )?;
+        }
+
+        reverse_game_accounting(&mut self.casino_state, &mut self.game_state, &mut self.user_stats, wagered, self.vrf_game_state.max_payout)?;
+        crate::instructions::limits::credit_player_limits(&self.player_limits, wagered, wagered, Clock::get()?.unix_timestamp)?;
+
+        self.vrf_game_state.game_phase = GamePhase::Voided;
+        self.vrf_game_state.is_complete = true;
+
+        emit!(GameRefunded {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            game: self.vrf_game_state.key(),
+            game_type: self.vrf_game_state.game_type.clone(),
+            nonce: self.vrf_game_state.nonce,
+            amount: wagered,
+            voided: true,
+        });
+        Ok(())
+    }
+}
+
+// Refund a blackjack round whose opening deal was never revealed (casino authority only, for
+// the same reason as RefundExpiredGame). Later draws are resolved by expire_blackjack_round.
+#[derive(Accounts)]
+#[instruction(game_nonce: u64)]
+pub struct RefundExpiredBlackjack<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"blackjack_game", user.key().as_ref(), &game_nonce.to_le_bytes()],
+        bump = blackjack_game.bump
+    )]
+    pub blackjack_game: Account<'info, BlackjackGame>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state", [GameType::Blackjack as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: The account's data is validated manually within the handler.
+    pub randomness_account_data: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl <'info> RefundExpiredBlackjack<'info> {
+    pub fn refund_expired_blackjack(&mut self, _game_nonce: u64) -> Result<()> {
+        // Validate no card has been dealt yet; once the player has acted on their cards the
+        // stakes are no longer refundable
+        require!(
+            self.blackjack_game.phase == BlackjackPhase::Dealing,
+            CasinoError::InvalidGameState
+        );
+
+        // Validate the reveal window has passed
+        validate_request_expired(self.blackjack_game.commit_slot)?;
+
+        // Validate the opening deal was never revealed (seen cards must be settled)
+        require!(
+            self.blackjack_game.randomness_account == self.randomness_account_data.key(),
+            CasinoError::VrfRequestFailed
+        );
+        let randomness_data = parse_randomness_data(&self.randomness_account_data)?;
+        validate_randomness_unrevealed(&randomness_data, self.blackjack_game.commit_slot)?;
+
+        refund_bet(
+            &self.casino_state,
+            &self.casino_vault,
+            &self.user_token_account,
+            &self.usdc_mint,
+            &self.token_program,
+            self.blackjack_game.total_wagered,
+        )?;
+
+        reverse_game_accounting(&mut self.casino_state, &mut self.game_state, &mut self.user_stats, self.blackjack_game.total_wagered, self.blackjack_game.max_payout)?;
+        crate::instructions::limits::credit_player_limits(&self.player_limits, self.blackjack_game.total_wagered, self.blackjack_game.total_wagered, Clock::get()?.unix_timestamp)?;
+
+        self.blackjack_game.phase = BlackjackPhase::Finished;
+        self.blackjack_game.is_complete = true;
+
+        emit!(GameRefunded {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            game: self.blackjack_game.key(),
+            game_type: GameType::Blackjack,
+            nonce: self.blackjack_game.nonce,
+            amount: self.blackjack_game.total_wagered,
+            voided: false,
+        });
+        Ok(())
+    }
+}
+
+// Stand a blackjack round the player abandoned mid-hand (casino authority only): either its
+// pending draw expired unrevealed, or it sat in PlayerTurn past the timeout. Every hand still in
+// play stands, and the dealer plays out on a fresh draw committed by the authority, who can then
+// settle the round to pay it out and release its liability.
+#[derive(Accounts)]
+#[instruction(game_nonce: u64)]
+pub struct ExpireBlackjackRound<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        seeds = [b"blackjack_game", user.key().as_ref(), &game_nonce.to_le_bytes()],
+        bump = blackjack_game.bump
+    )]
+    pub blackjack_game: Account<'info, BlackjackGame>,
+
+    pub authority: Signer<'info>,
+
+    pub user: SystemAccount<'info>,
+
+    /// CHECK: The expired pending draw, required while the round is in ActionPending. Validated manually within the handler.
+    pub pending_randomness_account_data: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The account's data is validated manually within the handler.
+    pub randomness_account_data: AccountInfo<'info>,
+}
+
+impl <'info> ExpireBlackjackRound<'info> {
+    pub fn expire_blackjack_round(&mut self, _game_nonce: u64, randomness_account: Pubkey) -> Result<()> {
+        let game = &mut self.blackjack_game;
+
+        // Validate the round is waiting on the player or on a draw
+        require!(
+            game.phase == BlackjackPhase::PlayerTurn || game.phase == BlackjackPhase::ActionPending,
+            CasinoError::InvalidGameState
+        );
+
+        // Validate the timeout has passed since the round's last commit
+        validate_request_expired(game.commit_slot)?;
+
+        // Validate a pending draw was never revealed (a revealed one is settled as committed)
+        if game.phase == BlackjackPhase::ActionPending {
+            let pending_randomness = self.pending_randomness_account_data.as_ref().ok_or(CasinoError::VrfRequestFailed)?;
+            require!(
+                game.randomness_account == pending_randomness.key(),
+                CasinoError::VrfRequestFailed
+            );
+            let pending_data = parse_randomness_data(pending_randomness)?;
+            validate_randomness_unrevealed(&pending_data, game.commit_slot)?;
+        }
+
+        // Validate the fresh randomness is from the previous slot
+        require!(
+            self.randomness_account_data.key() == randomness_account,
+            CasinoError::VrfRequestFailed
+        );
+        let randomness_data = parse_randomness_data(&self.randomness_account_data)?;
+        let clock = Clock::get()?;
+        require!(
+            randomness_data.seed_slot == clock.slot - 1,
+            CasinoError::VrfRequestFailed
+        );
+
+        // Stand every open hand; the next settle plays out the dealer and resolves the round
+        let dropped_action = crate::instructions::blackjack::stand_abandoned_hands(game);
+        game.pending_action = Some(BlackjackAction::Stand);
+        game.randomness_account = randomness_account;
+        game.commit_slot = randomness_data.seed_slot;
+        game.phase = BlackjackPhase::ActionPending;
+
+        emit!(BlackjackRoundExpired {
+            version: EVENT_VERSION,
+            slot: clock.slot,
+            user: self.user.key(),
+            game: game.key(),
+            nonce: game.nonce,
+            dropped_action,
+            randomness_account,
+        });
+        Ok(())
+    }
+}
+
+// Return the escrowed bet from the casino vault to the player
+fn refund_bet<'info>(
+    casino_state: &Account<'info, CasinoState>,
+    casino_vault: &InterfaceAccount<'info, TokenAccount>,
+    user_token_account: &InterfaceAccount<'info, TokenAccount>,
+    usdc_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let refund_instruction = TransferChecked {
+        from: casino_vault.to_account_info(),
+        to: user_token_account.to_account_info(),
+        authority: casino_state.to_account_info(),
+        mint: usdc_mint.to_account_info(),
+    };
+
+    let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[casino_state.casino_state_bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), refund_instruction, seeds);
+
+    transfer_checked(cpi_ctx, amount, usdc_mint.decimals)
+}
+
+// Undo the counters bumped when the game was requested and release its reserved liability
+fn reverse_game_accounting(casino_state: &mut CasinoState, game_state: &mut GameState, user_stats: &mut UserStats, wagered: u64, max_payout: u64) -> Result<()> {
+    user_stats.total_bets = user_stats.total_bets.saturating_sub(wagered);
+    crate::instructions::vip::remove_volume(user_stats, wagered, Clock::get()?.unix_timestamp);
+    user_stats.games_played = user_stats.games_played.saturating_sub(1);
+    user_stats.pending_games = user_stats.pending_games.saturating_sub(1);
+
+    casino_state.total_games_played = casino_state.total_games_played.saturating_sub(1);
+    casino_state.total_volume = casino_state.total_volume.saturating_sub(wagered);
+    casino_state.outstanding_liabilities = casino_state.outstanding_liabilities.saturating_sub(max_payout);
+    casino_state.pending_stakes = casino_state.pending_stakes.saturating_sub(wagered);
+
+    game_state.total_games = game_state.total_games.saturating_sub(1);
+    game_state.total_bets = game_state.total_bets.saturating_sub(wagered);
+    Ok(())
+}
@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    approve, revoke, transfer_checked, Approve, Mint, Revoke, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::casino::GameType;
+use crate::state::session::*;
+use crate::events::*;
+use crate::errors::*;
+
+// Session keys. Game contexts take the player as `user`, whoever signs as `signer` and an
+// optional SessionKey PDA ([b"session_key", user, signer]). When the player signs, play is
+// unchanged; otherwise the session key must be present, live and allow the game, and stakes
+// are pulled from the player's token account through the token delegation granted to the
+// SessionKey PDA when it was created. SPL token accounts hold a single delegate, so creating
+// a new session key replaces the allowance of the previous one.
+
+// Authorize an ephemeral key to play on the player's behalf
+#[derive(Accounts)]
+#[instruction(session_signer: Pubkey)]
+pub struct CreateSessionKey<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = SessionKey::DISCRIMINATOR.len() + SessionKey::INIT_SPACE,
+        seeds = [b"session_key", user.key().as_ref(), session_signer.as_ref()],
+        bump
+    )]
+    pub session_key: Account<'info, SessionKey>,
+
+    #[account(mut, address = session_signer)]
+    pub session_signer_account: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> CreateSessionKey<'info> {
+    pub fn create_session_key(
+        &mut self,
+        session_signer: Pubkey,
+        expires_at: i64,
+        max_wager: u64,
+        allowed_games: Vec<GameType>,
+        fee_lamports: u64,
+        bumps: &CreateSessionKeyBumps
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        // Validate session parameters
+        require!(session_signer != self.user.key(), SessionError::InvalidSessionParameters);
+        require!(
+            expires_at > now && expires_at <= now.saturating_add(MAX_SESSION_DURATION),
+            SessionError::InvalidSessionParameters
+        );
+        require!(max_wager > 0, SessionError::InvalidSessionParameters);
+        require!(
+            !allowed_games.is_empty() && allowed_games.len() <= MAX_SESSION_GAMES,
+            SessionError::InvalidSessionParameters
+        );
+
+        self.session_key.set_inner(SessionKey {
+            user: self.user.key(),
+            session_signer,
+            expires_at,
+            max_wager,
+            wagered: 0,
+            allowed_games,
+            bump: bumps.session_key,
+        });
+
+        // Delegate the session's wager cap to the SessionKey PDA
+        let approve_instruction = Approve {
+            to: self.user_token_account.to_account_info(),
+            delegate: self.session_key.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        approve(CpiContext::new(self.token_program.to_account_info(), approve_instruction), max_wager)?;
+
+        // Fund the ephemeral key for transaction fees and game account rent
+        if fee_lamports > 0 {
+            crate::utils::vrf::transfer_funds(
+                self.system_program.to_account_info(),
+                self.user.to_account_info(),
+                self.session_signer_account.to_account_info(),
+                fee_lamports,
+                None,
+            )?;
+        }
+
+        emit!(SessionKeyCreated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            session_key: self.session_key.key(),
+            session_signer,
+            expires_at,
+            max_wager,
+            allowed_games: self.session_key.allowed_games.clone(),
+        });
+        Ok(())
+    }
+}
+
+// Revoke a session key and its token delegation
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"session_key", user.key().as_ref(), session_key.session_signer.as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Account<'info, SessionKey>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl <'info> RevokeSessionKey<'info> {
+    pub fn revoke_session_key(&mut self) -> Result<()> {
+        // Only clear the delegation if it still belongs to this session
+        if self.user_token_account.delegate == Some(self.session_key.key()).into() {
+            let revoke_instruction = Revoke {
+                source: self.user_token_account.to_account_info(),
+                authority: self.user.to_account_info(),
+            };
+            revoke(CpiContext::new(self.token_program.to_account_info(), revoke_instruction))?;
+        }
+
+        emit!(SessionKeyRevoked {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            session_key: self.session_key.key(),
+            session_signer: self.session_key.session_signer,
+            wagered: self.session_key.wagered,
+        });
+        Ok(())
+    }
+}
+
+/// Validate the signer may place `stake` on `game_type` for the player, charging it to the session
+pub fn authorize_play(
+    user: &AccountInfo,
+    signer: &Signer,
+    session_key: Option<&mut Account<SessionKey>>,
+    game_type: GameType,
+    stake: u64,
+) -> Result<()> {
+    if signer.key() == user.key() {
+        return Ok(());
+    }
+
+    let session_key = session_key.ok_or(SessionError::UnauthorizedSigner)?;
+    require!(Clock::get()?.unix_timestamp < session_key.expires_at, SessionError::SessionExpired);
+    require!(session_key.allowed_games.contains(&game_type), SessionError::GameNotAllowed);
+
+    let wagered = session_key.wagered.checked_add(stake).ok_or(CasinoError::MathOverflow)?;
+    require!(wagered <= session_key.max_wager, SessionError::SessionWagerCapReached);
+    session_key.wagered = wagered;
+    Ok(())
+}
+
+/// Validate the signer may settle for the player. Payouts only ever go to the player, so any
+/// of their session keys may settle, expired or not.
+pub fn authorize_settle(user: &AccountInfo, signer: &Signer, session_key: Option<&Account<SessionKey>>) -> Result<()> {
+    require!(
+        signer.key() == user.key() || session_key.is_some(),
+        SessionError::UnauthorizedSigner
+    );
+    Ok(())
+}
+
+/// Take a stake from the player's token account: signed by the player for direct play, or by
+/// the SessionKey PDA spending its delegation for session play
+pub fn take_player_stake<'info>(
+    user: &AccountInfo<'info>,
+    session_key: Option<&Account<'info, SessionKey>>,
+    user_token_account: &InterfaceAccount<'info, TokenAccount>,
+    casino_vault: &InterfaceAccount<'info, TokenAccount>,
+    usdc_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let transfer_instruction = TransferChecked {
+        from: user_token_account.to_account_info(),
+        to: casino_vault.to_account_info(),
+        mint: usdc_mint.to_account_info(),
+        authority: match session_key {
+            Some(session_key) => session_key.to_account_info(),
+            None => user.clone(),
+        },
+    };
+
+    match session_key {
+        Some(session_key) => {
+            let seeds : &[&[&[u8]]] = &[&[
+                b"session_key",
+                session_key.user.as_ref(),
+                session_key.session_signer.as_ref(),
+                &[session_key.bump],
+            ]];
+            let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), transfer_instruction, seeds);
+            transfer_checked(cpi_ctx, amount, usdc_mint.decimals)
+        },
+        None => {
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), transfer_instruction);
+            transfer_checked(cpi_ctx, amount, usdc_mint.decimals)
+        },
+    }
+}
@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::casino::*;
+use crate::state::history::GameHistory;
+use crate::state::tournament::{Tournament, TournamentEntry};
+use crate::state::loyalty::LOYALTY_POINTS_DIVISOR;
+use crate::state::session::SessionKey;
+use crate::state::vip::VipConfig;
+use crate::utils::math::calculate_game_fee;
+use crate::events::*;
+
+// Settle bookkeeping shared by every game.
+// Each settle instruction draws its own outcome, then hands the stake and payout to
+// finish_settle, which books the game the same way for slots, roulette, aviator and blackjack.
+
+/// Accounts a settled game is booked against, borrowed from the settle instruction
+pub struct SettleAccounts<'a, 'info> {
+    pub casino_state: &'a mut Account<'info, CasinoState>,
+    pub casino_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub game_state: &'a mut Account<'info, GameState>,
+    pub fee_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub fee_epoch: &'a mut Account<'info, FeeEpoch>,
+    pub user_stats: &'a mut Account<'info, UserStats>,
+    pub vip_config: Option<&'a VipConfig>,
+    pub player_limits: &'a AccountInfo<'info>,
+    pub referral: &'a AccountInfo<'info>,
+    pub tournament: Option<&'a mut Account<'info, Tournament>>,
+    pub tournament_entry: Option<&'a mut Account<'info, TournamentEntry>>,
+    pub game_history: &'a mut Account<'info, GameHistory>,
+    pub user: &'a AccountInfo<'info>,
+    pub signer: &'a Signer<'info>,
+    pub session_key: Option<&'a Account<'info, SessionKey>>,
+    pub user_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub usdc_mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub epoch: u64,
+    pub fee_epoch_bump: u8,
+    pub game_history_bump: u8,
+}
+
+/// A finished game: what was staked, what it paid and the draw that decided it
+pub struct SettledGame {
+    pub game_type: GameType,
+    pub wagered: u64,        // Stake at risk (0 for a free spin)
+    pub payout: u64,
+    pub max_payout: u64,     // Liability reserved for the game
+    pub rtp_bps: u16,        // RTP the rakeback is accrued at
+    pub record: crate::verify::GameRecord,
+}
+
+/// Validate the signer is the player, one of their session keys, or the casino authority.
+/// Settling only applies an already revealed draw and pays the player, so the authority may
+/// close out a game the player walked away from.
+pub fn authorize_settle_signer(
+    casino_state: &CasinoState,
+    user: &AccountInfo,
+    signer: &Signer,
+    session_key: Option<&Account<SessionKey>>,
+) -> Result<()> {
+    if signer.key() == casino_state.authority {
+        return Ok(());
+    }
+    crate::instructions::session::authorize_settle(user, signer, session_key)
+}
+
+/// Keep a draw's proof in the player's history, where it outlives the closed game account
+pub fn keep_game_proof(
+    game_history: &mut GameHistory,
+    user: Pubkey,
+    bump: u8,
+    record: crate::verify::GameRecord,
+    payout: u64,
+) -> Result<()> {
+    let proof = crate::verify::stored_proof(&record, payout, Clock::get()?.slot)?;
+    crate::utils::history::store_game_proof(game_history, user, bump, proof);
+
+    emit!(GameProof {
+        version: EVENT_VERSION,
+        slot: Clock::get()?.slot,
+        user,
+        record,
+    });
+    Ok(())
+}
+
+impl<'a, 'info> SettleAccounts<'a, 'info> {
+    /// Book a finished game: release its reservation, pay the player, update their stats, VIP
+    /// rakeback, limits and tournament, collect the platform fee less the referral share and
+    /// keep the proof. Returns the platform fee.
+    pub fn finish_settle(self, game: SettledGame, referrer_stats: &[AccountInfo]) -> Result<u64> {
+        let SettledGame { game_type, wagered, payout, max_payout, rtp_bps, record } = game;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Validate the signer may settle for the player
+        authorize_settle_signer(self.casino_state, self.user, self.signer, self.session_key)?;
+
+        // Validate a registered player passes their running tournament
+        crate::instructions::tournament::validate_tournament_accounts(self.user_stats, self.tournament.as_deref(), self.tournament_entry.as_deref())?;
+
+        // Validate fees are booked into the current epoch
+        crate::instructions::fees::open_fee_epoch(self.fee_epoch, self.epoch, self.fee_epoch_bump)?;
+
+        // Update user stats (loyalty points accrue on every stake, win or lose)
+        let user_stats = self.user_stats;
+        user_stats.pending_games -= 1;
+        user_stats.loyalty_points += wagered / LOYALTY_POINTS_DIVISOR;
+        if payout > 0 {
+            user_stats.total_wins += payout;
+        } else {
+            user_stats.total_losses += wagered;
+        }
+
+        // Accrue VIP rakeback on the stake and move the player between tiers
+        let vip_tier = crate::instructions::vip::refresh_vip_tier(user_stats, self.vip_config, now)?;
+        crate::instructions::vip::accrue_rakeback(self.casino_state, user_stats, vip_tier.as_ref(), wagered, rtp_bps)?;
+
+        // Credit the payout back against the player's loss limits
+        crate::instructions::limits::credit_player_limits(self.player_limits, 0, payout, now)?;
+
+        // Score the game for the player's running tournament
+        crate::instructions::tournament::record_tournament_game(
+            user_stats,
+            self.tournament,
+            self.tournament_entry,
+            game_type,
+            wagered,
+            payout
+        )?;
+
+        // Update casino and game stats
+        let casino_state = self.casino_state;
+        casino_state.total_payouts += payout;
+        casino_state.outstanding_liabilities = casino_state.outstanding_liabilities.saturating_sub(max_payout);
+        casino_state.pending_stakes = casino_state.pending_stakes.saturating_sub(wagered);
+        self.game_state.total_payouts += payout;
+
+        // Transfer payout to user if they won
+        if payout > 0 {
+            let payout_instruction = TransferChecked {
+                from: self.casino_vault.to_account_info(),
+                to: self.user_token_account.to_account_info(),
+                authority: casino_state.to_account_info(),
+                mint: self.usdc_mint.to_account_info(),
+            };
+
+            let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[casino_state.casino_state_bump]]];
+            let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), payout_instruction, seeds);
+            transfer_checked(cpi_ctx, payout, self.usdc_mint.decimals)?;
+        }
+
+        // Collect the platform fee, carving out the player's referral share
+        let fee = calculate_game_fee(&casino_state.house_edge_config, wagered, payout)?;
+        let referral_share = crate::instructions::referral::credit_referrers(
+            self.referral,
+            referrer_stats,
+            &casino_state.referral_config,
+            wagered,
+            payout,
+            fee
+        )?;
+        crate::instructions::fees::collect_platform_fee(
+            casino_state,
+            self.fee_epoch,
+            self.casino_vault,
+            self.fee_vault,
+            self.usdc_mint,
+            self.token_program,
+            fee
+        )?;
+        crate::instructions::fees::withhold_referral_share(self.fee_epoch, referral_share)?;
+
+        keep_game_proof(self.game_history, self.user.key(), self.game_history_bump, record, payout)?;
+        Ok(fee)
+    }
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use crate::state::casino::*;
+use crate::utils::slots::*;
+use crate::events::*;
+use crate::errors::*;
+
+// Create a slots machine. The RTP of every payline is computed from the reel strips and
+// paytable and must sit within SLOTS_RTP_TOLERANCE_BPS of the slots GameState RTP.
+#[derive(Accounts)]
+#[instruction(machine_id: u64)]
+pub struct InitializeSlotsMachine<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        seeds = [b"game_state", [GameType::Slots as u8].as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = SlotsMachineConfig::DISCRIMINATOR.len() + SlotsMachineConfig::INIT_SPACE,
+        seeds = [b"slots_machine", machine_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub slots_machine: Account<'info, SlotsMachineConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> InitializeSlotsMachine<'info> {
+    pub fn initialize_slots_machine(
+        &mut self,
+        machine_id: u64,
+        reel_strips: Vec<ReelStrip>,
+        paylines: Vec<Payline>,
+        paytable: Vec<SlotsPayout>,
+        bumps: &InitializeSlotsMachineBumps
+    ) -> Result<()> {
+        validate_machine_config(&reel_strips, &paylines, &paytable)?;
+
+        // Validate every payline returns the configured RTP
+        let target_rtp_bps = self.game_state.rtp_bps;
+        let payline_rtps = calculate_payline_rtps(&reel_strips, &paylines, &paytable)?;
+        for rtp_bps in payline_rtps.iter() {
+            require!(
+                rtp_bps.abs_diff(target_rtp_bps as u64) <= SLOTS_RTP_TOLERANCE_BPS as u64,
+                CasinoError::SlotsRtpMismatch
+            );
+        }
+        let rtp_bps = (payline_rtps.iter().sum::<u64>() / payline_rtps.len() as u64) as u16;
+
+        self.slots_machine.set_inner(SlotsMachineConfig {
+            machine_id,
+            reel_count: reel_strips.len() as u8,
+            reel_strips,
+            paylines,
+            paytable,
+            rtp_bps,
+            is_active: true,
+            bump: bumps.slots_machine,
+        });
+
+        emit!(SlotsMachineInitialized {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            machine: self.slots_machine.key(),
+            machine_id,
+            rtp_bps,
+        });
+        Ok(())
+    }
+}
+
+// Take a slots machine offline or back online (the configuration itself is immutable)
+#[derive(Accounts)]
+#[instruction(machine_id: u64)]
+pub struct SetSlotsMachineActive<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        seeds = [b"slots_machine", machine_id.to_le_bytes().as_ref()],
+        bump = slots_machine.bump
+    )]
+    pub slots_machine: Account<'info, SlotsMachineConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+impl <'info> SetSlotsMachineActive<'info> {
+    pub fn set_slots_machine_active(&mut self, machine_id: u64, is_active: bool) -> Result<()> {
+        self.slots_machine.is_active = is_active;
+
+        emit!(SlotsMachineActiveChanged {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            machine: self.slots_machine.key(),
+            machine_id,
+            is_active,
+        });
+        Ok(())
+    }
+}
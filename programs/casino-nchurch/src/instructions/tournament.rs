@@ -0,0 +1,373 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::state::casino::*;
+use crate::state::tournament::*;
+use crate::events::*;
+use crate::errors::*;
+use crate::utils::tournament::*;
+
+// Tournaments. A player registers in one running tournament at a time; UserStats remembers
+// it until its end time, and while it runs every settle of that player must pass the
+// Tournament and the player's TournamentEntry (optional accounts otherwise) so losing games
+// cannot be left off the score. Entry fees make up the prize pool, held in the tournament
+// vault until anyone finalizes the tournament after its end time.
+
+// Create a tournament and its prize vault
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct CreateTournament<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Tournament::DISCRIMINATOR.len() + Tournament::INIT_SPACE,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = casino_state,
+        token::token_program = token_program,
+        seeds = [b"tournament_vault", tournament.key().as_ref()],
+        bump
+    )]
+    pub tournament_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> CreateTournament<'info> {
+    pub fn create_tournament(&mut self, tournament_id: u64, config: TournamentConfig, bumps: &CreateTournamentBumps) -> Result<()> {
+        validate_tournament_config(&config)?;
+        require!(config.end_time > Clock::get()?.unix_timestamp, CasinoError::InvalidTournamentConfig);
+
+        self.tournament.set_inner(Tournament {
+            tournament_id,
+            config,
+            leaderboard: Vec::new(),
+            prize_pool: 0,
+            participants: 0,
+            is_finalized: false,
+            bump: bumps.tournament,
+            vault_bump: bumps.tournament_vault,
+        });
+
+        emit!(TournamentCreated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            tournament: self.tournament.key(),
+            tournament_id,
+            config: self.tournament.config.clone(),
+        });
+        Ok(())
+    }
+}
+
+// Register for a tournament by paying its entry fee into the prize pool
+#[derive(Accounts)]
+pub struct JoinTournament<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament_vault", tournament.key().as_ref()],
+        bump = tournament.vault_bump
+    )]
+    pub tournament_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        space = TournamentEntry::DISCRIMINATOR.len() + TournamentEntry::INIT_SPACE,
+        seeds = [b"tournament_entry", tournament.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::DISCRIMINATOR.len() + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: The player's PlayerLimits PDA, enforced only once the player has created it.
+    #[account(
+        mut,
+        seeds = [b"player_limits", user.key().as_ref()],
+        bump
+    )]
+    pub player_limits: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> JoinTournament<'info> {
+    pub fn join_tournament(&mut self, bumps: &JoinTournamentBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let tournament = &mut self.tournament;
+        let user_stats = &mut self.user_stats;
+
+        // Validate the tournament is still open and the player is not in another running one
+        require!(now < tournament.config.end_time, TournamentError::TournamentEnded);
+        require!(
+            user_stats.active_tournament.is_none() || now >= user_stats.tournament_ends_at,
+            TournamentError::AlreadyInTournament
+        );
+
+        // Validate the entry fee against the player's responsible-gaming limits
+        crate::instructions::limits::enforce_player_limits(&self.player_limits, tournament.config.entry_fee, now)?;
+
+        if tournament.config.entry_fee > 0 {
+            let entry_instruction = TransferChecked {
+                from: self.user_token_account.to_account_info(),
+                to: self.tournament_vault.to_account_info(),
+                authority: self.user.to_account_info(),
+                mint: self.usdc_mint.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), entry_instruction);
+            transfer_checked(cpi_ctx, tournament.config.entry_fee, self.usdc_mint.decimals)?;
+        }
+
+        self.tournament_entry.set_inner(TournamentEntry {
+            tournament: tournament.key(),
+            user: self.user.key(),
+            score: 0,
+            games_played: 0,
+            wagered: 0,
+            payout: 0,
+            bump: bumps.tournament_entry,
+        });
+
+        tournament.prize_pool += tournament.config.entry_fee;
+        tournament.participants += 1;
+
+        user_stats.user = self.user.key();
+        user_stats.active_tournament = Some(tournament.key());
+        user_stats.tournament_ends_at = tournament.config.end_time;
+        user_stats.bump = bumps.user_stats;
+        user_stats.layout_version = crate::state::migration::USER_STATS_VERSION;
+
+        emit!(TournamentJoined {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            tournament: tournament.key(),
+            user: self.user.key(),
+            entry_fee: tournament.config.entry_fee,
+            prize_pool: tournament.prize_pool,
+        });
+        Ok(())
+    }
+}
+
+// Pay the prize pool along the payout curve once the tournament has ended (anyone may call).
+// Remaining accounts are the winners' token accounts in leaderboard order.
+#[derive(Accounts)]
+pub struct FinalizeTournament<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament_vault", tournament.key().as_ref()],
+        bump = tournament.vault_bump
+    )]
+    pub tournament_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl <'info> FinalizeTournament<'info> {
+    pub fn finalize_tournament(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let tournament = &self.tournament;
+        require!(!tournament.is_finalized, TournamentError::TournamentAlreadyFinalized);
+        require!(Clock::get()?.unix_timestamp >= tournament.config.end_time, TournamentError::TournamentNotEnded);
+
+        let prizes = calculate_prizes(tournament.prize_pool, &tournament.config.payout_curve_bps, tournament.leaderboard.len())?;
+        require!(remaining_accounts.len() >= prizes.len(), TournamentError::InvalidWinnerAccount);
+
+        let mut winners = Vec::with_capacity(prizes.len());
+        for ((entry, winner_account), prize) in tournament.leaderboard.iter().zip(remaining_accounts).zip(&prizes) {
+            // Validate the account is a token account of the winner for the casino mint
+            require!(
+                winner_account.owner == &self.token_program.key() && winner_account.is_writable,
+                TournamentError::InvalidWinnerAccount
+            );
+            let token_account = TokenAccount::try_deserialize(&mut &winner_account.try_borrow_data()?[..])?;
+            require!(
+                token_account.owner == entry.user && token_account.mint == self.usdc_mint.key(),
+                TournamentError::InvalidWinnerAccount
+            );
+
+            if *prize > 0 {
+                self.pay_from_vault(winner_account.clone(), *prize)?;
+            }
+            winners.push(entry.user);
+        }
+
+        // Nobody scored: the pool goes to the house
+        let unclaimed = if prizes.is_empty() { self.tournament.prize_pool } else { 0 };
+        if unclaimed > 0 {
+            self.pay_from_vault(self.casino_vault.to_account_info(), unclaimed)?;
+        }
+
+        self.tournament.is_finalized = true;
+
+        emit!(TournamentFinalized {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            tournament: self.tournament.key(),
+            prize_pool: self.tournament.prize_pool,
+            winners,
+            prizes,
+            unclaimed,
+        });
+        Ok(())
+    }
+
+    fn pay_from_vault(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let prize_instruction = TransferChecked {
+            from: self.tournament_vault.to_account_info(),
+            to,
+            authority: self.casino_state.to_account_info(),
+            mint: self.usdc_mint.to_account_info(),
+        };
+
+        let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[self.casino_state.casino_state_bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), prize_instruction, seeds);
+        transfer_checked(cpi_ctx, amount, self.usdc_mint.decimals)
+    }
+}
+
+/// Reject a settle that omits the player's running tournament. While the player is registered
+/// in a tournament that has not ended, its Tournament and TournamentEntry must be passed, so
+/// losing games cannot be left off the board. Called before the settle touches any state.
+pub fn validate_tournament_accounts(
+    user_stats: &UserStats,
+    tournament: Option<&Account<Tournament>>,
+    tournament_entry: Option<&Account<TournamentEntry>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let Some(active_tournament) = running_tournament(user_stats.active_tournament, user_stats.tournament_ends_at, now) else {
+        return Ok(());
+    };
+
+    // Validate the accounts are the player's registration in that tournament
+    let (Some(tournament), Some(entry)) = (tournament, tournament_entry) else {
+        return err!(TournamentError::InvalidTournamentAccounts);
+    };
+    require!(
+        tournament.key() == active_tournament && entry.tournament == active_tournament && entry.user == user_stats.user,
+        TournamentError::InvalidTournamentAccounts
+    );
+    Ok(())
+}
+
+/// Score a settled game for the player's running tournament (accounts checked by
+/// `validate_tournament_accounts`); games outside the start time or of a game type the
+/// tournament does not count are ignored.
+pub fn record_tournament_game(
+    user_stats: &UserStats,
+    tournament: Option<&mut Account<Tournament>>,
+    tournament_entry: Option<&mut Account<TournamentEntry>>,
+    game_type: GameType,
+    wagered: u64,
+    payout: u64,
+) -> Result<()> {
+    validate_tournament_accounts(user_stats, tournament.as_deref(), tournament_entry.as_deref())?;
+    let now = Clock::get()?.unix_timestamp;
+    let (Some(tournament), Some(entry)) = (tournament, tournament_entry) else {
+        return Ok(());
+    };
+    if running_tournament(user_stats.active_tournament, user_stats.tournament_ends_at, now).is_none() {
+        return Ok(());
+    }
+
+    if now < tournament.config.start_time || !tournament.config.eligible_games.contains(&game_type) {
+        return Ok(());
+    }
+
+    entry.score = score_game(tournament.config.metric, entry.score, wagered, payout)?;
+    entry.games_played += 1;
+    entry.wagered += wagered;
+    entry.payout += payout;
+
+    let leaderboard_size = tournament.config.leaderboard_size as usize;
+    update_leaderboard(&mut tournament.leaderboard, leaderboard_size, entry.user, entry.score);
+    let rank = tournament
+        .leaderboard
+        .iter()
+        .position(|leader| leader.user == entry.user)
+        .map(|position| position as u8 + 1);
+
+    emit!(TournamentScoreUpdated {
+        version: EVENT_VERSION,
+        slot: Clock::get()?.slot,
+        tournament: tournament.key(),
+        user: entry.user,
+        score: entry.score,
+        rank,
+    });
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::casino::*;
+use crate::state::vip::*;
+use crate::events::*;
+use crate::errors::*;
+use crate::utils::bankroll::validate_exposure;
+use crate::utils::vip::*;
+
+// VIP tiers. Request and settle handlers take the (optional) VipConfig account: requests
+// refresh the player's tier and cap the bet at the tier's max bet, settles accrue rakeback
+// at the tier's share of the house edge. Rakeback is paid from the casino vault.
+
+// Create the VIP tier table
+#[derive(Accounts)]
+pub struct InitializeVipConfig<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = VipConfig::DISCRIMINATOR.len() + VipConfig::INIT_SPACE,
+        seeds = [b"vip_config"],
+        bump
+    )]
+    pub vip_config: Account<'info, VipConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl <'info> InitializeVipConfig<'info> {
+    pub fn initialize_vip_config(&mut self, volume_basis: VipVolumeBasis, tiers: Vec<VipTier>, bumps: &InitializeVipConfigBumps) -> Result<()> {
+        validate_vip_tiers(&tiers)?;
+
+        self.vip_config.set_inner(VipConfig {
+            volume_basis,
+            tiers,
+            bump: bumps.vip_config,
+        });
+
+        emit!(VipConfigUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            volume_basis,
+            tiers: self.vip_config.tiers.clone(),
+        });
+        Ok(())
+    }
+}
+
+// Replace the VIP tier table (players move tiers on their next request or settle)
+#[derive(Accounts)]
+pub struct UpdateVipConfig<'info> {
+    #[account(
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump,
+        has_one = authority @ CasinoError::Unauthorized
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        seeds = [b"vip_config"],
+        bump = vip_config.bump
+    )]
+    pub vip_config: Account<'info, VipConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+impl <'info> UpdateVipConfig<'info> {
+    pub fn update_vip_config(&mut self, volume_basis: VipVolumeBasis, tiers: Vec<VipTier>) -> Result<()> {
+        validate_vip_tiers(&tiers)?;
+
+        self.vip_config.volume_basis = volume_basis;
+        self.vip_config.tiers = tiers;
+
+        emit!(VipConfigUpdated {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            authority: self.authority.key(),
+            volume_basis,
+            tiers: self.vip_config.tiers.clone(),
+        });
+        Ok(())
+    }
+}
+
+// Pay out the player's accrued rakeback
+#[derive(Accounts)]
+pub struct ClaimRakeback<'info> {
+    #[account(
+        mut,
+        seeds = [b"casino_state"],
+        bump = casino_state.casino_state_bump
+    )]
+    pub casino_state: Account<'info, CasinoState>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = casino_state,
+        associated_token::token_program = token_program,
+    )]
+    pub casino_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl <'info> ClaimRakeback<'info> {
+    pub fn claim_rakeback(&mut self) -> Result<()> {
+        let amount = self.user_stats.rakeback_accrued;
+        require!(amount > 0, CasinoError::NoRakebackToClaim);
+
+        // Validate the remaining bankroll still covers every pending game
+        let remaining = self.casino_vault.amount
+            .checked_sub(amount)
+            .ok_or(CasinoError::InsufficientBankroll)?;
+        validate_exposure(
+            self.casino_state.outstanding_liabilities,
+            0,
+            remaining,
+            self.casino_state.max_exposure_bps
+        )?;
+
+        self.user_stats.rakeback_accrued = 0;
+        self.casino_state.rakeback_owed = self.casino_state.rakeback_owed.saturating_sub(amount);
+
+        let rakeback_instruction = TransferChecked {
+            from: self.casino_vault.to_account_info(),
+            to: self.user_token_account.to_account_info(),
+            authority: self.casino_state.to_account_info(),
+            mint: self.usdc_mint.to_account_info(),
+        };
+
+        let seeds : &[&[&[u8]]] = &[&[b"casino_state", &[self.casino_state.casino_state_bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), rakeback_instruction, seeds);
+        transfer_checked(cpi_ctx, amount, self.usdc_mint.decimals)?;
+
+        emit!(RakebackClaimed {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: self.user.key(),
+            amount,
+            vip_tier: self.user_stats.vip_tier,
+        });
+        Ok(())
+    }
+}
+
+fn current_day(now: i64) -> i64 {
+    now.div_euclid(SECONDS_PER_DAY)
+}
+
+/// Add a stake to the player's rolling daily volume
+pub fn record_volume(user_stats: &mut UserStats, amount: u64, now: i64) {
+    let today = current_day(now);
+    roll_daily_volume(&mut user_stats.daily_volume, user_stats.last_volume_day, today);
+    user_stats.last_volume_day = user_stats.last_volume_day.max(today);
+
+    let bucket = &mut user_stats.daily_volume[today.rem_euclid(VIP_ROLLING_DAYS as i64) as usize];
+    *bucket = bucket.saturating_add(amount);
+}
+
+/// Take a refunded stake back out of today's volume
+pub fn remove_volume(user_stats: &mut UserStats, amount: u64, now: i64) {
+    let today = current_day(now);
+    roll_daily_volume(&mut user_stats.daily_volume, user_stats.last_volume_day, today);
+    user_stats.last_volume_day = user_stats.last_volume_day.max(today);
+
+    let bucket = &mut user_stats.daily_volume[today.rem_euclid(VIP_ROLLING_DAYS as i64) as usize];
+    *bucket = bucket.saturating_sub(amount);
+}
+
+/// Recompute the player's tier from the table and return it (None without a table or tier)
+pub fn refresh_vip_tier(user_stats: &mut UserStats, vip_config: Option<&VipConfig>, now: i64) -> Result<Option<VipTier>> {
+    let Some(vip_config) = vip_config else {
+        return Ok(None);
+    };
+
+    let volume = match vip_config.volume_basis {
+        VipVolumeBasis::Lifetime => user_stats.total_bets,
+        VipVolumeBasis::Rolling30Days => {
+            let today = current_day(now);
+            roll_daily_volume(&mut user_stats.daily_volume, user_stats.last_volume_day, today);
+            user_stats.last_volume_day = user_stats.last_volume_day.max(today);
+            rolling_volume(&user_stats.daily_volume)
+        },
+    };
+
+    let tier = tier_for_volume(&vip_config.tiers, volume);
+    if tier != user_stats.vip_tier {
+        emit!(VipTierChanged {
+            version: EVENT_VERSION,
+            slot: Clock::get()?.slot,
+            user: user_stats.user,
+            old_tier: user_stats.vip_tier,
+            new_tier: tier,
+            volume,
+        });
+        user_stats.vip_tier = tier;
+    }
+
+    Ok(tier.checked_sub(1).map(|index| vip_config.tiers[index as usize].clone()))
+}
+
+/// Accrue the tier's rakeback on a settled stake
+pub fn accrue_rakeback(casino_state: &mut CasinoState, user_stats: &mut UserStats, tier: Option<&VipTier>, wagered: u64, rtp_bps: u16) -> Result<()> {
+    let Some(tier) = tier else {
+        return Ok(());
+    };
+
+    let rakeback = calculate_rakeback(wagered, rtp_bps, tier.rakeback_bps)?;
+    user_stats.rakeback_accrued += rakeback;
+    casino_state.rakeback_owed += rakeback;
+    Ok(())
+}
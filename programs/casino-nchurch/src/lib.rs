@@ -5,6 +5,11 @@ pub mod state;
 pub mod instructions;
 pub mod utils;
 pub mod errors;
+pub mod events;
+pub mod replay;
+pub mod verify;
+#[cfg(not(target_os = "solana"))]
+pub mod certify;
 
 use state::*;
 use instructions::*;
@@ -21,22 +26,147 @@ pub mod casino_nchurch {
         ctx.accounts.initialize_casino(&ctx.bumps)
     }
 
+    // Account layout migrations
+    pub fn migrate_casino_state(ctx: Context<MigrateCasinoState>) -> Result<()> {
+        ctx.accounts.migrate_casino_state(&ctx.bumps)
+    }
+
+    pub fn migrate_user_stats(ctx: Context<MigrateUserStats>) -> Result<()> {
+        ctx.accounts.migrate_user_stats()
+    }
+
+    pub fn migrate_quest_campaign(ctx: Context<MigrateQuestCampaign>, campaign_counter: u64) -> Result<()> {
+        ctx.accounts.migrate_quest_campaign(campaign_counter, ctx.remaining_accounts)
+    }
+
+    // Admin Instructions
+    pub fn initialize_game(ctx: Context<InitializeGame>, game_type: GameType) -> Result<()> {
+        ctx.accounts.initialize_game(game_type, &ctx.bumps)
+    }
+
+    pub fn update_game(ctx: Context<UpdateGame>, game_type: GameType, min_bet: u64, max_bet: u64, is_active: bool) -> Result<()> {
+        ctx.accounts.update_game(game_type, min_bet, max_bet, is_active)
+    }
+
+    pub fn update_rtp(ctx: Context<UpdateGame>, game_type: GameType, rtp_bps: u16) -> Result<()> {
+        ctx.accounts.update_rtp(game_type, rtp_bps)
+    }
+
+    pub fn update_platform_fee(ctx: Context<UpdateCasinoConfig>, fee_bps: u16) -> Result<()> {
+        ctx.accounts.update_platform_fee(fee_bps)
+    }
+
+    pub fn set_fee_basis(ctx: Context<UpdateCasinoConfig>, fee_basis: FeeBasis) -> Result<()> {
+        ctx.accounts.set_fee_basis(fee_basis)
+    }
+
+    pub fn set_max_pending_games(ctx: Context<UpdateCasinoConfig>, max_pending_games: u8) -> Result<()> {
+        ctx.accounts.set_max_pending_games(max_pending_games)
+    }
+
+    pub fn set_max_exposure(ctx: Context<UpdateCasinoConfig>, max_exposure_bps: u16) -> Result<()> {
+        ctx.accounts.set_max_exposure(max_exposure_bps)
+    }
+
+    pub fn pause_casino(ctx: Context<UpdateCasinoConfig>) -> Result<()> {
+        ctx.accounts.set_casino_active(false)
+    }
+
+    pub fn resume_casino(ctx: Context<UpdateCasinoConfig>) -> Result<()> {
+        ctx.accounts.set_casino_active(true)
+    }
+
+    pub fn propose_authority(ctx: Context<UpdateCasinoConfig>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.propose_authority(new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        ctx.accounts.accept_authority()
+    }
+
+    pub fn quote_max_bet(ctx: Context<QuoteMaxBet>, game_type: GameType, max_payout_bps: u64) -> Result<u64> {
+        ctx.accounts.quote_max_bet(game_type, max_payout_bps)
+    }
+
     // Slots Game (VRF-based)
-    pub fn request_slots_game(ctx: Context<RequestSlotsGame>, bet_amount: u64, randomness_account: Pubkey) -> Result<()> {
-        ctx.accounts.request_slots_game(bet_amount, randomness_account, &ctx.bumps)
+    pub fn initialize_slots_machine(ctx: Context<InitializeSlotsMachine>, machine_id: u64, reel_strips: Vec<ReelStrip>, paylines: Vec<Payline>, paytable: Vec<SlotsPayout>) -> Result<()> {
+        ctx.accounts.initialize_slots_machine(machine_id, reel_strips, paylines, paytable, &ctx.bumps)
+    }
+
+    pub fn set_slots_machine_active(ctx: Context<SetSlotsMachineActive>, machine_id: u64, is_active: bool) -> Result<()> {
+        ctx.accounts.set_slots_machine_active(machine_id, is_active)
+    }
+
+    pub fn request_slots_game(ctx: Context<RequestSlotsGame>, bet_amount: u64, active_paylines: u32, randomness_account: Pubkey) -> Result<()> {
+        ctx.accounts.request_slots_game(bet_amount, active_paylines, randomness_account, &ctx.bumps)
+    }
+
+    pub fn request_free_spin(ctx: Context<RequestSlotsGame>, active_paylines: u32, randomness_account: Pubkey) -> Result<()> {
+        ctx.accounts.request_free_spin(active_paylines, randomness_account, &ctx.bumps)
     }
 
-    pub fn settle_slots_game(ctx: Context<SettleSlotsGame>) -> Result<()> {
-        ctx.accounts.settle_slots_game(&ctx.bumps)
+    pub fn settle_slots_game(ctx: Context<SettleSlotsGame>, game_nonce: u64, epoch: u64) -> Result<()> {
+        ctx.accounts.settle_slots_game(game_nonce, epoch, &ctx.bumps, ctx.remaining_accounts)
     }
 
     // Roulette Game (VRF-based)
-    pub fn request_roulette_game(ctx: Context<RequestRouletteGame>, bet_amount: u64, bet_type: RouletteBetType, numbers: Vec<u8>, randomness_account: Pubkey) -> Result<()> {
-        ctx.accounts.request_roulette_game(bet_amount, bet_type, numbers, randomness_account, &ctx.bumps)
+    pub fn request_roulette_game(ctx: Context<RequestRouletteGame>, total_amount: u64, bets: Vec<RouletteBet>, randomness_account: Pubkey) -> Result<()> {
+        ctx.accounts.request_roulette_game(total_amount, bets, randomness_account, &ctx.bumps)
+    }
+
+    pub fn settle_roulette_game(ctx: Context<SettleRouletteGame>, game_nonce: u64, epoch: u64) -> Result<()> {
+        ctx.accounts.settle_roulette_game(game_nonce, epoch, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    // Aviator Game (VRF-based)
+    pub fn request_aviator_game(ctx: Context<RequestAviatorGame>, bet_amount: u64, target_multiplier: u64, randomness_account: Pubkey) -> Result<()> {
+        ctx.accounts.request_aviator_game(bet_amount, target_multiplier, randomness_account, &ctx.bumps)
+    }
+
+    pub fn settle_aviator_game(ctx: Context<SettleAviatorGame>, game_nonce: u64, epoch: u64) -> Result<()> {
+        ctx.accounts.settle_aviator_game(game_nonce, epoch, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    // Blackjack (multi-step, VRF-based)
+    pub fn initialize_blackjack_table(ctx: Context<InitializeBlackjackTable>, table_id: u64, rules: BlackjackRules) -> Result<()> {
+        ctx.accounts.initialize_blackjack_table(table_id, rules, &ctx.bumps)
+    }
+
+    pub fn update_blackjack_table(ctx: Context<UpdateBlackjackTable>, table_id: u64, rules: BlackjackRules, is_active: bool) -> Result<()> {
+        ctx.accounts.update_blackjack_table(table_id, rules, is_active)
+    }
+
+    pub fn request_blackjack_deal(ctx: Context<RequestBlackjackDeal>, bet_amount: u64, randomness_account: Pubkey) -> Result<()> {
+        ctx.accounts.request_blackjack_deal(bet_amount, randomness_account, &ctx.bumps)
+    }
+
+    pub fn request_blackjack_action(ctx: Context<RequestBlackjackAction>, game_nonce: u64, action: BlackjackAction, randomness_account: Pubkey) -> Result<()> {
+        ctx.accounts.request_blackjack_action(game_nonce, action, randomness_account)
+    }
+
+    pub fn place_blackjack_insurance(ctx: Context<PlaceBlackjackInsurance>, game_nonce: u64) -> Result<()> {
+        ctx.accounts.place_blackjack_insurance(game_nonce)
+    }
+
+    pub fn settle_blackjack(ctx: Context<SettleBlackjack>, game_nonce: u64, epoch: u64) -> Result<()> {
+        ctx.accounts.settle_blackjack(game_nonce, epoch, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    // Expired / broken game recovery
+    pub fn refund_expired_game(ctx: Context<RefundExpiredGame>, game_nonce: u64) -> Result<()> {
+        ctx.accounts.refund_expired_game(game_nonce)
+    }
+
+    pub fn refund_expired_blackjack(ctx: Context<RefundExpiredBlackjack>, game_nonce: u64) -> Result<()> {
+        ctx.accounts.refund_expired_blackjack(game_nonce)
     }
 
-    pub fn settle_roulette_game(ctx: Context<SettleRouletteGame>) -> Result<()> {
-        ctx.accounts.settle_roulette_game(&ctx.bumps)
+    pub fn expire_blackjack_round(ctx: Context<ExpireBlackjackRound>, game_nonce: u64, randomness_account: Pubkey) -> Result<()> {
+        ctx.accounts.expire_blackjack_round(game_nonce, randomness_account)
+    }
+
+    pub fn void_game(ctx: Context<VoidGame>, game_nonce: u64) -> Result<()> {
+        ctx.accounts.void_game(game_nonce)
     }
 
     // Quest Instructions
@@ -71,15 +201,128 @@ pub mod casino_nchurch {
         ctx.accounts.deposit_liquidity(amount, &ctx.bumps)
     }
 
-    pub fn stake_lp_tokens(ctx: Context<StakeLPTokens>, amount: u64, staking_counter: u64) -> Result<()> {
-        ctx.accounts.stake_lp_tokens(amount, staking_counter, &ctx.bumps)
+    pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>, lp_tokens: u64) -> Result<()> {
+        ctx.accounts.withdraw_liquidity(lp_tokens)
     }
 
-    pub fn claim_lp_rewards(ctx: Context<ClaimLPRewards>, staking_counter: u64) -> Result<()> {
-        ctx.accounts.claim_lp_rewards(staking_counter, &ctx.bumps)
+    pub fn sync_liquidity(ctx: Context<SyncLiquidity>) -> Result<()> {
+        ctx.accounts.sync_liquidity()
+    }
+
+    pub fn stake_lp_tokens(ctx: Context<StakeLPTokens>, amount: u64, staking_counter: u64) -> Result<()> {
+        ctx.accounts.stake_lp_tokens(amount, staking_counter, &ctx.bumps)
     }
 
     pub fn distribute_platform_fees(ctx: Context<DistributePlatformFees>, epoch: u64) -> Result<()> {
         ctx.accounts.distribute_platform_fees(epoch, &ctx.bumps)
     }
+
+    pub fn withdraw_platform_fees(ctx: Context<WithdrawPlatformFees>, epoch: u64, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_platform_fees(epoch, amount)
+    }
+
+    // Loyalty Instructions
+    pub fn initialize_loyalty(ctx: Context<InitializeLoyalty>) -> Result<()> {
+        ctx.accounts.initialize_loyalty(&ctx.bumps)
+    }
+
+    pub fn enable_points_mint(ctx: Context<EnablePointsMint>) -> Result<()> {
+        ctx.accounts.enable_points_mint(&ctx.bumps)
+    }
+
+    pub fn create_loyalty_item(ctx: Context<CreateLoyaltyItem>, item_id: u64, reward: LoyaltyReward, cost_points: u64, stock: Option<u32>) -> Result<()> {
+        ctx.accounts.create_loyalty_item(item_id, reward, cost_points, stock, &ctx.bumps)
+    }
+
+    pub fn update_loyalty_item(ctx: Context<UpdateLoyaltyItem>, item_id: u64, cost_points: u64, stock: Option<u32>, is_active: bool) -> Result<()> {
+        ctx.accounts.update_loyalty_item(item_id, cost_points, stock, is_active)
+    }
+
+    pub fn tokenize_loyalty_points(ctx: Context<TokenizeLoyaltyPoints>, amount: u64) -> Result<()> {
+        ctx.accounts.tokenize_loyalty_points(amount)
+    }
+
+    pub fn redeem_loyalty(ctx: Context<RedeemLoyalty>, item_id: u64, from_tokens: bool) -> Result<()> {
+        ctx.accounts.redeem_loyalty(item_id, from_tokens)
+    }
+
+    // VIP Instructions
+    pub fn initialize_vip_config(ctx: Context<InitializeVipConfig>, volume_basis: VipVolumeBasis, tiers: Vec<VipTier>) -> Result<()> {
+        ctx.accounts.initialize_vip_config(volume_basis, tiers, &ctx.bumps)
+    }
+
+    pub fn update_vip_config(ctx: Context<UpdateVipConfig>, volume_basis: VipVolumeBasis, tiers: Vec<VipTier>) -> Result<()> {
+        ctx.accounts.update_vip_config(volume_basis, tiers)
+    }
+
+    pub fn claim_rakeback(ctx: Context<ClaimRakeback>) -> Result<()> {
+        ctx.accounts.claim_rakeback()
+    }
+
+    // Responsible Gaming Instructions
+    pub fn initialize_player_limits(ctx: Context<InitializePlayerLimits>, limits: LimitSettings) -> Result<()> {
+        ctx.accounts.initialize_player_limits(limits, &ctx.bumps)
+    }
+
+    pub fn set_player_limits(ctx: Context<UpdatePlayerLimits>, limits: LimitSettings) -> Result<()> {
+        ctx.accounts.set_player_limits(limits)
+    }
+
+    pub fn self_exclude(ctx: Context<UpdatePlayerLimits>, duration: Option<i64>) -> Result<()> {
+        ctx.accounts.self_exclude(duration)
+    }
+
+    // Session Key Instructions
+    pub fn create_session_key(ctx: Context<CreateSessionKey>, session_signer: Pubkey, expires_at: i64, max_wager: u64, allowed_games: Vec<GameType>, fee_lamports: u64) -> Result<()> {
+        ctx.accounts.create_session_key(session_signer, expires_at, max_wager, allowed_games, fee_lamports, &ctx.bumps)
+    }
+
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        ctx.accounts.revoke_session_key()
+    }
+
+    // Referral Instructions
+    pub fn set_referral_config(ctx: Context<UpdateCasinoConfig>, referral_config: ReferralConfig) -> Result<()> {
+        ctx.accounts.set_referral_config(referral_config)
+    }
+
+    pub fn set_referrer(ctx: Context<SetReferrer>, referrer: Pubkey) -> Result<()> {
+        ctx.accounts.set_referrer(referrer, &ctx.bumps)
+    }
+
+    pub fn claim_referral_earnings(ctx: Context<ClaimReferralEarnings>) -> Result<()> {
+        ctx.accounts.claim_referral_earnings()
+    }
+
+    // Jackpot Instructions
+    pub fn initialize_jackpot(ctx: Context<InitializeJackpot>, contribution_bps: u16, reseed_amount: u64, jackpot_symbol: u8, jackpot_run: u8, roll_odds: u64) -> Result<()> {
+        ctx.accounts.initialize_jackpot(contribution_bps, reseed_amount, jackpot_symbol, jackpot_run, roll_odds, &ctx.bumps)
+    }
+
+    pub fn update_jackpot(ctx: Context<UpdateJackpot>, contribution_bps: u16, reseed_amount: u64, jackpot_symbol: u8, jackpot_run: u8, roll_odds: u64, is_active: bool) -> Result<()> {
+        ctx.accounts.update_jackpot(contribution_bps, reseed_amount, jackpot_symbol, jackpot_run, roll_odds, is_active)
+    }
+
+    pub fn fund_jackpot(ctx: Context<FundJackpot>, amount: u64) -> Result<()> {
+        ctx.accounts.fund_jackpot(amount)
+    }
+
+    // Tournament Instructions
+    pub fn create_tournament(ctx: Context<CreateTournament>, tournament_id: u64, config: TournamentConfig) -> Result<()> {
+        ctx.accounts.create_tournament(tournament_id, config, &ctx.bumps)
+    }
+
+    pub fn join_tournament(ctx: Context<JoinTournament>) -> Result<()> {
+        ctx.accounts.join_tournament(&ctx.bumps)
+    }
+
+    pub fn finalize_tournament<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeTournament<'info>>) -> Result<()> {
+        ctx.accounts.finalize_tournament(ctx.remaining_accounts)
+    }
+
+    // Mock randomness (localnet/tests only)
+    #[cfg(feature = "mock-vrf")]
+    pub fn write_mock_randomness(ctx: Context<WriteMockRandomness>, mock_id: u64, seed_slot: u64, reveal_slot: u64, value: [u8; 32], strict_reveal: bool) -> Result<()> {
+        ctx.accounts.write_mock_randomness(mock_id, seed_slot, reveal_slot, value, strict_reveal, &ctx.bumps)
+    }
 }
@@ -0,0 +1,368 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt;
+use sha2::{Digest, Sha256};
+
+// Replay core.
+// Derives every game outcome from the committed bet inputs and one revealed randomness value:
+// the hash-expanded draw stream, the reel stops, winning number, crash point and cards, and
+// the payouts. It depends on `core`, `alloc` and `sha2` only (no anchor_lang, Solana hashing
+// or program errors), so the file builds unchanged into a no_std verifier. `verify` adapts
+// the program's records and accounts to these inputs, and settlement goes through it.
+
+pub const DOMAIN_SLOTS_REEL: &[u8] = b"casino-nchurch/slots/reel";
+pub const DOMAIN_SLOTS_JACKPOT: &[u8] = b"casino-nchurch/slots/jackpot";
+pub const DOMAIN_ROULETTE_SPIN: &[u8] = b"casino-nchurch/roulette/spin";
+pub const DOMAIN_AVIATOR_CRASH: &[u8] = b"casino-nchurch/aviator/crash";
+pub const DOMAIN_BLACKJACK_CARD: &[u8] = b"casino-nchurch/blackjack/card";
+
+pub const SLOTS_ROWS: usize = 3;                     // Visible rows per reel
+pub const SLOTS_MULTIPLIER_SCALE: u64 = 100;         // 100 = 1x line bet
+pub const AVIATOR_MULTIPLIER_SCALE: u64 = 100;       // 100 = 1.00x
+pub const ROULETTE_POCKETS: u64 = 37;                // 0-36
+pub const CARD_RANKS: u64 = 13;                      // Ace (1) to King (13)
+const CRASH_DRAW_BITS: u32 = 52;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    InvalidInputs,
+    MathOverflow,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::InvalidInputs => f.write_str("game inputs do not describe a playable game"),
+            ReplayError::MathOverflow => f.write_str("payout overflows u64"),
+        }
+    }
+}
+
+pub type ReplayResult<T> = core::result::Result<T, ReplayError>;
+
+// Randomness derivation.
+// Every draw is hash-expanded as sha256(domain || game || randomness || draw_index || attempt),
+// so different games, accounts and draw positions never share bytes, and any number of values
+// can be drawn from a single reveal. Ranges are sampled by rejection, so each value in 0..n is
+// exactly equally likely, and draw `i` does not depend on how many draws came before it.
+
+pub struct RandomnessStream<'a> {
+    domain: &'a [u8],
+    game: [u8; 32],
+    randomness: &'a [u8],
+    next_index: u32,
+}
+
+impl<'a> RandomnessStream<'a> {
+    /// `game` is the key of the account the draws belong to (VrfGameState or BlackjackGame)
+    pub fn new(domain: &'a [u8], game: [u8; 32], randomness: &'a [u8]) -> Self {
+        Self { domain, game, randomness, next_index: 0 }
+    }
+
+    /// 64 uniformly distributed bits for one draw position and rejection attempt
+    pub fn expand(&self, draw_index: u32, attempt: u32) -> u64 {
+        let digest = Sha256::new()
+            .chain_update(self.domain)
+            .chain_update(self.game)
+            .chain_update(self.randomness)
+            .chain_update(draw_index.to_le_bytes())
+            .chain_update(attempt.to_le_bytes())
+            .finalize();
+        let mut value_bytes = [0u8; 8];
+        value_bytes.copy_from_slice(&digest[..8]);
+        u64::from_le_bytes(value_bytes)
+    }
+
+    /// Uniform value in `0..upper` for draw position `draw_index`
+    pub fn uniform(&self, draw_index: u32, upper: u64) -> u64 {
+        assert!(upper > 0, "empty range");
+        let mut attempt = 0u32;
+        loop {
+            if let Some(value) = accept_candidate(self.expand(draw_index, attempt), upper) {
+                return value;
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Uniform value in `0..upper` for the next draw position
+    pub fn next_uniform(&mut self, upper: u64) -> u64 {
+        let value = self.uniform(self.next_index, upper);
+        self.next_index += 1;
+        value
+    }
+
+    /// Number of draw positions consumed so far
+    pub fn draws_taken(&self) -> u32 {
+        self.next_index
+    }
+
+    /// `count` independent uniform values in `0..upper` (e.g. one per reel)
+    pub fn draws(&mut self, count: usize, upper: u64) -> Vec<u64> {
+        (0..count).map(|_| self.next_uniform(upper)).collect()
+    }
+
+    /// Uniform permutation of `0..len` (e.g. a full shuffled deck) by Fisher-Yates
+    pub fn shuffle(&mut self, len: usize) -> Vec<u16> {
+        let mut items: Vec<u16> = (0..len as u16).collect();
+        for i in (1..len).rev() {
+            let j = self.next_uniform(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+        items
+    }
+}
+
+// Accept only candidates from the largest multiple of `upper` that fits in 2^64,
+// so reducing them modulo `upper` has no bias
+pub fn accept_candidate(candidate: u64, upper: u64) -> Option<u64> {
+    // 2^64 mod upper: the number of low candidates that would over-weight small values
+    let rejected = upper.wrapping_neg() % upper;
+    if candidate >= rejected {
+        Some(candidate % upper)
+    } else {
+        None
+    }
+}
+
+fn mul_div(value: u64, numerator: u64, denominator: u64) -> ReplayResult<u64> {
+    let result = value as u128 * numerator as u128 / denominator as u128;
+    u64::try_from(result).map_err(|_| ReplayError::MathOverflow)
+}
+
+fn checked_add(a: u64, b: u64) -> ReplayResult<u64> {
+    a.checked_add(b).ok_or(ReplayError::MathOverflow)
+}
+
+// Slots.
+// A machine is a set of weighted reel strips, paylines reading one window row per reel, and
+// a paytable of line-bet multipliers for runs of a symbol starting on the leftmost reel.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stop {
+    pub symbol: u8,
+    pub weight: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinePay {
+    pub symbol: u8,
+    pub count: u8,
+    pub multiplier: u64,   // SLOTS_MULTIPLIER_SCALE = 1x line bet
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotsMachine {
+    pub reels: Vec<Vec<Stop>>,
+    pub paylines: Vec<Vec<u8>>,   // Window row read on each reel, left to right
+    pub paytable: Vec<LinePay>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotsSpin {
+    pub stop_positions: Vec<usize>,
+    pub window: Vec<u8>,
+    pub payout: u64,
+}
+
+pub fn strip_weight(stops: &[Stop]) -> u64 {
+    stops.iter().map(|stop| stop.weight as u64).sum()
+}
+
+/// Stop a reel lands on for a draw in `0..strip_weight`
+pub fn select_stop(stops: &[Stop], draw: u64) -> usize {
+    let mut remaining = draw;
+    for (position, stop) in stops.iter().enumerate() {
+        if remaining < stop.weight as u64 {
+            return position;
+        }
+        remaining -= stop.weight as u64;
+    }
+    stops.len() - 1
+}
+
+/// Symbols shown after a spin, reel by reel (`window[reel * SLOTS_ROWS + row]`)
+pub fn spin_window(reels: &[Vec<Stop>], stop_positions: &[usize]) -> Vec<u8> {
+    let mut window = Vec::with_capacity(reels.len() * SLOTS_ROWS);
+    for (stops, position) in reels.iter().zip(stop_positions.iter()) {
+        for row in 0..SLOTS_ROWS {
+            window.push(stops[(position + row) % stops.len()].symbol);
+        }
+    }
+    window
+}
+
+/// Line bet multiplier for a run of `run` matching symbols (best entry no longer than the run)
+pub fn line_multiplier(paytable: &[LinePay], symbol: u8, run: usize) -> u64 {
+    paytable
+        .iter()
+        .filter(|entry| entry.symbol == symbol && entry.count as usize <= run)
+        .map(|entry| entry.multiplier)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Gross payout of a window over the selected paylines (bit i = paylines[i])
+pub fn slots_payout(machine: &SlotsMachine, window: &[u8], active_paylines: u32, line_bet: u64) -> ReplayResult<u64> {
+    let mut payout = 0u64;
+    for (index, rows) in machine.paylines.iter().enumerate() {
+        if active_paylines & (1 << index) == 0 {
+            continue;
+        }
+
+        let symbol_at = |reel: usize| window[reel * SLOTS_ROWS + rows[reel] as usize];
+        let first_symbol = symbol_at(0);
+        let run = (0..rows.len())
+            .take_while(|reel| symbol_at(*reel) == first_symbol)
+            .count();
+
+        let multiplier = line_multiplier(&machine.paytable, first_symbol, run);
+        if multiplier > 0 {
+            payout = checked_add(payout, mul_div(line_bet, multiplier, SLOTS_MULTIPLIER_SCALE)?)?;
+        }
+    }
+    Ok(payout)
+}
+
+/// Land every reel on a weighted stop and pay the active paylines (the bet is split evenly)
+pub fn slots_spin(machine: &SlotsMachine, game: [u8; 32], randomness: &[u8], active_paylines: u32, bet_amount: u64) -> ReplayResult<SlotsSpin> {
+    let line_count = active_paylines.count_ones() as u64;
+    if line_count == 0 || machine.reels.iter().any(|stops| strip_weight(stops) == 0) {
+        return Err(ReplayError::InvalidInputs);
+    }
+
+    let mut draws = RandomnessStream::new(DOMAIN_SLOTS_REEL, game, randomness);
+    let stop_positions: Vec<usize> = machine.reels
+        .iter()
+        .map(|stops| select_stop(stops, draws.next_uniform(strip_weight(stops))))
+        .collect();
+    let window = spin_window(&machine.reels, &stop_positions);
+    let payout = slots_payout(machine, &window, active_paylines, bet_amount / line_count)?;
+
+    Ok(SlotsSpin { stop_positions, window, payout })
+}
+
+// Roulette.
+// Each bet is reduced to the bitmask of numbers it covers (bit n = number n) and its net odds;
+// bets pay true odds, so the single zero alone carries the house edge.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouletteWager {
+    pub covered: u64,
+    pub net_odds: u64,     // 35 = 35:1
+    pub amount: u64,
+}
+
+/// Gross amount returned by one bet for a winning number (stake included)
+pub fn wager_payout(wager: &RouletteWager, winning_number: u8) -> ReplayResult<u64> {
+    if u64::from(winning_number) < ROULETTE_POCKETS && wager.covered & (1 << winning_number) != 0 {
+        let odds = wager.net_odds.checked_add(1).ok_or(ReplayError::MathOverflow)?;
+        wager.amount.checked_mul(odds).ok_or(ReplayError::MathOverflow)
+    } else {
+        Ok(0)
+    }
+}
+
+pub fn roulette_winning_number(game: [u8; 32], randomness: &[u8]) -> u8 {
+    RandomnessStream::new(DOMAIN_ROULETTE_SPIN, game, randomness).uniform(0, ROULETTE_POCKETS) as u8
+}
+
+/// One winning number settles every bet; returns it with each bet's payout
+pub fn roulette_spin(wagers: &[RouletteWager], game: [u8; 32], randomness: &[u8]) -> ReplayResult<(u8, Vec<u64>)> {
+    let winning_number = roulette_winning_number(game, randomness);
+    let payouts = wagers
+        .iter()
+        .map(|wager| wager_payout(wager, winning_number))
+        .collect::<ReplayResult<Vec<u64>>>()?;
+    Ok((winning_number, payouts))
+}
+
+// Aviator.
+
+/// Crash point from a uniform 52-bit draw, with the house edge built into the distribution:
+/// P(crash >= m) = rtp / m for every m >= 1.00x, so any cashout target returns exactly `rtp_bps`.
+pub fn crash_multiplier(uniform_52: u64, rtp_bps: u16) -> u64 {
+    const RANGE: u128 = 1 << CRASH_DRAW_BITS;
+    let draw = uniform_52 as u128 & (RANGE - 1);
+
+    let crash = rtp_bps as u128 * AVIATOR_MULTIPLIER_SCALE as u128 * RANGE / (10000 * (RANGE - draw));
+
+    // Anything below 1.00x busts immediately
+    crash.max(AVIATOR_MULTIPLIER_SCALE as u128) as u64
+}
+
+/// Gross payout for an auto-cashout target: paid when the target is at or below the crash point
+pub fn aviator_payout(cashout_multiplier: u64, crash_multiplier: u64, bet_amount: u64) -> ReplayResult<u64> {
+    if cashout_multiplier <= crash_multiplier {
+        mul_div(bet_amount, cashout_multiplier, AVIATOR_MULTIPLIER_SCALE)
+    } else {
+        Ok(0)
+    }
+}
+
+/// Crash point and payout of a flight (the crash point carries the edge, nothing is scaled again)
+pub fn aviator_flight(target_multiplier: u64, rtp_bps: u16, game: [u8; 32], randomness: &[u8], bet_amount: u64) -> ReplayResult<(u64, u64)> {
+    let uniform_draw = RandomnessStream::new(DOMAIN_AVIATOR_CRASH, game, randomness).uniform(0, 1 << CRASH_DRAW_BITS);
+    let crash = crash_multiplier(uniform_draw, rtp_bps);
+    Ok((crash, aviator_payout(target_multiplier, crash, bet_amount)?))
+}
+
+// Blackjack.
+
+/// Next card from an infinite deck: every card is an independent rank 1-13
+pub fn next_card(draws: &mut RandomnessStream) -> u8 {
+    draws.next_uniform(CARD_RANKS) as u8 + 1
+}
+
+/// The first `cards` cards drawn in one blackjack settle round
+pub fn blackjack_cards(game: [u8; 32], randomness: &[u8], cards: u8) -> Vec<u8> {
+    let mut draws = RandomnessStream::new(DOMAIN_BLACKJACK_CARD, game, randomness);
+    (0..cards).map(|_| next_card(&mut draws)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(seed: u8) -> [u8; 32] {
+        Sha256::digest([seed]).into()
+    }
+
+    #[test]
+    fn expansion_is_sha256_of_the_draw_preimage() {
+        let randomness = bytes(2);
+        let stream = RandomnessStream::new(DOMAIN_BLACKJACK_CARD, bytes(1), &randomness);
+
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(DOMAIN_BLACKJACK_CARD);
+        preimage.extend_from_slice(&bytes(1));
+        preimage.extend_from_slice(&randomness);
+        preimage.extend_from_slice(&3u32.to_le_bytes());
+        preimage.extend_from_slice(&1u32.to_le_bytes());
+        let digest = Sha256::digest(&preimage);
+        assert_eq!(stream.expand(3, 1), u64::from_le_bytes(digest[..8].try_into().unwrap()));
+    }
+
+    #[test]
+    fn wagers_pay_true_odds_on_covered_numbers() {
+        let straight = RouletteWager { covered: 1 << 17, net_odds: 35, amount: 10 };
+        assert_eq!(wager_payout(&straight, 17), Ok(360));
+        assert_eq!(wager_payout(&straight, 0), Ok(0));
+        assert_eq!(wager_payout(&straight, 37), Ok(0));
+
+        let overflowing = RouletteWager { covered: 1, net_odds: 35, amount: u64::MAX };
+        assert_eq!(wager_payout(&overflowing, 0), Err(ReplayError::MathOverflow));
+    }
+
+    #[test]
+    fn spins_need_a_payline_and_weighted_reels() {
+        let machine = SlotsMachine {
+            reels: alloc::vec![alloc::vec![Stop { symbol: 1, weight: 0 }]; 3],
+            paylines: alloc::vec![alloc::vec![0, 0, 0]],
+            paytable: Vec::new(),
+        };
+        assert_eq!(slots_spin(&machine, bytes(1), &bytes(2), 1, 100), Err(ReplayError::InvalidInputs));
+        assert_eq!(slots_spin(&machine, bytes(1), &bytes(2), 0, 100), Err(ReplayError::InvalidInputs));
+    }
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
 pub enum GameType {
     Slots,
     Roulette,
@@ -8,7 +8,7 @@ pub enum GameType {
     Blackjack,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
 pub enum RouletteBetType {
     Straight,    // Single number
     Split,       // Two adjacent numbers
@@ -25,12 +25,20 @@ pub enum RouletteBetType {
     High,        // 19-36
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+// What the platform fee is charged on when a game settles
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub enum FeeBasis {
+    HouseRevenue, // Stake kept by the house (wagered minus payout, zero when the player wins)
+    Volume,       // Total amount wagered
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum BlackjackAction {
     Hit,
     Stand,
     DoubleDown,
     Split,
+    Surrender,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
@@ -39,19 +47,39 @@ pub enum BlackjackHandStatus {
     Busted,
     Blackjack,
     Stand,
+    Surrendered,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum BlackjackPhase {
+    Dealing,        // Bet placed, waiting for randomness to deal the opening cards
+    PlayerTurn,     // Waiting for the player's next action
+    ActionPending,  // Action committed, waiting for randomness to draw
+    Finished,       // All hands resolved and paid
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct CasinoState {
     pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>, // Proposed authority, set until it accepts
     pub vault: Pubkey,
+    pub fee_vault: Pubkey,  // Program-owned token account collecting platform fees
     pub total_games_played: u64,
     pub total_volume: u64,
     pub total_payouts: u64,
+    pub total_fees_collected: u64,
+    pub outstanding_liabilities: u64, // Worst-case payouts reserved for unsettled games
+    pub pending_stakes: u64,          // Stakes held in the vault for unsettled games
+    pub rakeback_owed: u64,           // Accrued and unclaimed VIP rakeback
     pub house_edge_config: HouseEdgeConfig,
+    pub referral_config: crate::state::referral::ReferralConfig,
     pub is_active: bool,
+    pub max_pending_games: u8, // Max unsettled games a single user may have open
+    pub max_exposure_bps: u16, // Max outstanding liabilities as a share of the vault balance
+    pub fee_vault_bump: u8,
     pub casino_state_bump: u8,
+    pub layout_version: u8,    // CASINO_STATE_VERSION; older layouts upgrade with migrate_casino_state
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
@@ -61,8 +89,19 @@ pub struct HouseEdgeConfig {
     pub aviator_rtp_bps: u16,    // 9600 = 96% RTP (4% house edge)
     pub blackjack_rtp_bps: u16,  // 9950 = 99.5% RTP (0.5% house edge)
     pub platform_fee_bps: u16,  // 200 = 2% platform fee
+    pub fee_basis: FeeBasis,
 }
 
+// House edge a new casino starts with (initialize_casino, and rtp_certify without a CasinoState)
+pub const DEFAULT_HOUSE_EDGE_CONFIG: HouseEdgeConfig = HouseEdgeConfig {
+    slots_rtp_bps: 9500,      // 95% RTP
+    roulette_rtp_bps: crate::utils::roulette::ROULETTE_RTP_BPS,   // 97.3% RTP (single zero)
+    aviator_rtp_bps: 9600,    // 96% RTP
+    blackjack_rtp_bps: 9950,   // 99.5% RTP
+    platform_fee_bps: 200,    // 2% platform fee
+    fee_basis: FeeBasis::HouseRevenue,
+};
+
 #[account]
 #[derive(InitSpace)]
 pub struct GameState {
@@ -77,6 +116,17 @@ pub struct GameState {
     pub bump: u8,
 }
 
+// Platform fees collected during one FEE_DISTRIBUTION_EPOCH, swept by distribute_platform_fees
+#[account]
+#[derive(InitSpace)]
+pub struct FeeEpoch {
+    pub epoch: u64,
+    pub total_fees: u64,
+    pub games_settled: u64,
+    pub is_distributed: bool,
+    pub bump: u8,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct UserStats {
@@ -86,18 +136,62 @@ pub struct UserStats {
     pub total_losses: u64,
     pub loyalty_points: u64,
     pub games_played: u64,
+    pub game_nonce: u64,    // Seeds the next VrfGameState PDA for this user
+    pub pending_games: u8,  // Games requested but not yet settled
+    pub free_spins: u16,    // House-funded slots spins redeemed from loyalty points
+    pub free_spin_bet: u64, // Bet amount of every free spin
+    pub quest_boost_bps: u16,
+    pub quest_boost_expires_at: i64,
+    pub vip_tier: u8,       // 1-based position in VipConfig.tiers, 0 = none
+    pub daily_volume: [u64; 30], // Volume per day, indexed by day % VIP_ROLLING_DAYS
+    pub last_volume_day: i64,    // Day (unix time / SECONDS_PER_DAY) of the last recorded bet
+    pub rakeback_accrued: u64,   // Claimable with claim_rakeback
+    pub active_tournament: Option<Pubkey>, // Tournament the player is registered in
+    pub tournament_ends_at: i64,           // Its end time; the player may join another after it
     pub bump: u8,
+    pub layout_version: u8,   // USER_STATS_VERSION; older layouts upgrade with migrate_user_stats
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct ReelStop {
+    pub symbol: u8,
+    pub weight: u16,      // Relative chance of the reel landing on this stop
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct ReelStrip {
+    #[max_len(24)]
+    pub stops: Vec<ReelStop>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct Payline {
+    #[max_len(5)]
+    pub rows: Vec<u8>,    // Window row read on each reel, left to right
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct SlotsPayout {
+    pub symbol: u8,
+    pub count: u8,        // Run of matching symbols starting on the leftmost reel
+    pub multiplier: u64,  // Line bet multiplier (SLOTS_MULTIPLIER_SCALE = 1x)
+}
+
+// Slots machines are immutable once created (only `is_active` changes), so a pending
+// spin is always settled against the exact configuration it was placed on.
 #[account]
 #[derive(InitSpace)]
-pub struct SlotsGame {
-    pub user: Pubkey,
-    pub bet_amount: u64,
-    pub reels: [u8; 3], // 0-9 for each reel
-    pub paylines: [bool; 5], // Which paylines are active
-    pub payout: u64,
-    pub is_complete: bool,
+pub struct SlotsMachineConfig {
+    pub machine_id: u64,
+    pub reel_count: u8,   // 3 or 5 reels, each showing SLOTS_ROWS rows
+    #[max_len(5)]
+    pub reel_strips: Vec<ReelStrip>,
+    #[max_len(20)]
+    pub paylines: Vec<Payline>,
+    #[max_len(20)]
+    pub paytable: Vec<SlotsPayout>,
+    pub rtp_bps: u16,     // Theoretical RTP computed from the strips and paytable
+    pub is_active: bool,
     pub bump: u8,
 }
 
@@ -115,48 +209,92 @@ pub struct RouletteGame {
     pub bump: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct RouletteBet {
+    pub bet_type: RouletteBetType,
+    #[max_len(12)]
+    pub numbers: Vec<u8>,  // Covered numbers (empty for outside bets)
+    pub amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct RouletteBetResult {
+    pub bet: RouletteBet,
+    pub payout: u64,       // Amount returned for this bet (stake included, after RTP)
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct AviatorGame {
     pub user: Pubkey,
     pub bet_amount: u64,
-    pub cashout_multiplier: f64,
-    pub crash_multiplier: f64,
+    pub cashout_multiplier: u64, // Fixed-point, AVIATOR_MULTIPLIER_SCALE = 1.00x
+    pub crash_multiplier: u64,   // Fixed-point, AVIATOR_MULTIPLIER_SCALE = 1.00x
     pub payout: u64,
     pub is_complete: bool,
     pub bump: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct BlackjackRules {
+    pub dealer_hits_soft_17: bool,
+    pub surrender_allowed: bool,  // Late surrender on the first two cards
+    pub insurance_allowed: bool,  // Offered when the dealer shows an ace
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BlackjackTable {
+    pub table_id: u64,
+    pub rules: BlackjackRules,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct BlackjackHand {
+    #[max_len(13)]
+    pub cards: Vec<u8>,   // Card values (1-13)
+    pub bet_amount: u64,  // Stake riding on this hand (doubled on double down)
+    pub status: BlackjackHandStatus,
+    pub is_split: bool,   // Split hands cannot be a natural blackjack
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct BlackjackGame {
     pub user: Pubkey,
-    pub bet_amount: u64,
-    #[max_len(13)]
-    pub player_hand: Vec<u8>, // Card values (1-13)
+    pub table: Pubkey,
+    pub nonce: u64,                  // UserStats.game_nonce at deal time (PDA seed)
+    pub bet_amount: u64,             // Original stake
+    pub total_wagered: u64,          // Original stake plus double, split and insurance stakes
+    pub max_payout: u64,             // Liability reserved for the round so far
+    pub insurance_bet: u64,
+    #[max_len(2)]
+    pub player_hands: Vec<BlackjackHand>,
+    pub active_hand: u8,
     #[max_len(13)]
     pub dealer_hand: Vec<u8>,
-    pub player_status: BlackjackHandStatus,
     pub dealer_status: BlackjackHandStatus,
+    pub phase: BlackjackPhase,
+    pub pending_action: Option<BlackjackAction>,
+    pub randomness_account: Pubkey,  // Randomness committed for the pending draw
+    pub commit_slot: u64,
     pub payout: u64,
     pub is_complete: bool,
     pub bump: u8,
 }
 
-// Constants for game logic
-pub const SLOTS_SYMBOLS: [u8; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]; // 10 different symbols
-pub const SLOTS_PAYOUTS: [[u64; 3]; 10] = [
-    [0, 0, 0],     // Symbol 0: No payout
-    [2, 5, 10],    // Symbol 1: 2x, 5x, 10x
-    [3, 8, 15],    // Symbol 2: 3x, 8x, 15x
-    [5, 12, 25],   // Symbol 3: 5x, 12x, 25x
-    [8, 20, 50],   // Symbol 4: 8x, 20x, 50x
-    [12, 30, 75],  // Symbol 5: 12x, 30x, 75x
-    [20, 50, 100], // Symbol 6: 20x, 50x, 100x
-    [30, 75, 200], // Symbol 7: 30x, 75x, 200x
-    [50, 125, 500], // Symbol 8: 50x, 125x, 500x
-    [100, 250, 1000], // Symbol 9: 100x, 250x, 1000x
-];
+// Slots constants (limits match the SlotsMachineConfig max_len values)
+pub const SLOTS_ROWS: u8 = crate::replay::SLOTS_ROWS as u8;
+pub const SLOTS_MAX_REELS: usize = 5;
+pub const SLOTS_MAX_STOPS: usize = 24;
+pub const SLOTS_MAX_PAYLINES: usize = 20;
+pub const SLOTS_MAX_PAYTABLE: usize = 20;
+pub const SLOTS_MAX_SYMBOLS: u8 = 16;                 // Symbols are 0-15
+pub const SLOTS_MULTIPLIER_SCALE: u64 = crate::replay::SLOTS_MULTIPLIER_SCALE;  // 100 = 1x line bet
+pub const SLOTS_MAX_MULTIPLIER: u64 = 1_000_000;      // 10000x line bet
+pub const SLOTS_RTP_TOLERANCE_BPS: u16 = 25;          // Allowed drift from slots_rtp_bps per payline
 
 pub const ROULETTE_NUMBERS: [u8; 37] = [
     0, 32, 15, 19, 4, 21, 2, 25, 17, 34, 6, 27, 13, 36, 11, 30, 8, 23, 10, 5, 24, 16, 33, 1, 20, 14, 31, 9, 22, 18, 29, 7, 28, 12, 35, 3, 26
@@ -170,7 +308,26 @@ pub const ROULETTE_BLACK_NUMBERS: [u8; 18] = [
     2, 4, 6, 8, 10, 11, 13, 15, 17, 20, 22, 24, 26, 28, 29, 31, 33, 35
 ];
 
+pub const MAX_ROULETTE_BETS: usize = 10; // Bets per spin
+
+// Aviator constants (multipliers are fixed-point with two decimals)
+pub const AVIATOR_MULTIPLIER_SCALE: u64 = crate::replay::AVIATOR_MULTIPLIER_SCALE;  // 100 = 1.00x
+pub const AVIATOR_MIN_TARGET_MULTIPLIER: u64 = 101;     // 1.01x
+pub const AVIATOR_MAX_TARGET_MULTIPLIER: u64 = 100_000; // 1000.00x
+
+// Session constants
+pub const DEFAULT_MAX_PENDING_GAMES: u8 = 5;
+
+// Bankroll constants
+pub const DEFAULT_MAX_EXPOSURE_BPS: u16 = 2_000; // 20% of the vault
+
+// Default GameState bet limits
+pub const DEFAULT_MIN_BET: u64 = 1_000;
+pub const DEFAULT_MAX_BET: u64 = 1_000_000;
+
 // Blackjack constants
 pub const BLACKJACK_VALUE: u8 = 21;
 pub const DEALER_STAND_VALUE: u8 = 17;
 pub const BLACKJACK_PAYOUT: u64 = 150; // 150% for blackjack
+pub const INSURANCE_PAYOUT: u64 = 200; // 2:1 on insurance
+pub const MAX_BLACKJACK_HANDS: usize = 2; // One split per round
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::casino::GameType;
+
+// One settled draw, kept so it can be re-verified after the game account is closed
+// (see verify::stored_record)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct StoredProof {
+    pub game: Pubkey,              // VrfGameState or BlackjackGame account (keys every draw)
+    pub nonce: u64,
+    pub game_type: GameType,
+    pub bet_amount: u64,
+    pub rtp_bps: u16,
+    #[max_len(400)]
+    pub game_data: Vec<u8>,        // Bet inputs as committed (blackjack: cards drawn in the round)
+    pub randomness: [u8; 32],      // Revealed Switchboard value the game was settled with
+    pub payout: u64,
+    pub slot: u64,
+}
+
+// Per-player ring of the latest settled proofs
+#[account]
+#[derive(InitSpace)]
+pub struct GameHistory {
+    pub user: Pubkey,
+    #[max_len(8)]
+    pub proofs: Vec<StoredProof>,  // Most recent proofs, oldest first
+    pub bump: u8,
+}
+
+// History constants
+pub const GAME_HISTORY_LEN: usize = 8;           // Matches GameHistory.proofs
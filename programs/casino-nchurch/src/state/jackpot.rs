@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub enum JackpotTrigger {
+    TopCombination,  // An active payline landed the jackpot run
+    Roll,            // The separate 1-in-roll_odds jackpot draw hit
+}
+
+// One jackpot payout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct JackpotWin {
+    pub user: Pubkey,
+    pub game: Pubkey,         // Settled VrfGameState
+    pub amount: u64,
+    pub trigger: JackpotTrigger,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+// Progressive slots jackpot, paid from its own vault
+#[account]
+#[derive(InitSpace)]
+pub struct ProgressiveJackpot {
+    pub vault: Pubkey,             // Token account holding the pot
+    pub contribution_bps: u16,     // Slice of every paid slots wager moved into the pot
+    pub reseed_amount: u64,        // Left in the pot after a win to seed the next one
+    pub jackpot_symbol: u8,        // Symbol of the top combination
+    pub jackpot_run: u8,           // Symbols in a row from the first reel that win the jackpot
+    pub roll_odds: u64,            // 1-in-N chance of the jackpot roll per spin (0 = off)
+    pub total_contributed: u64,
+    pub total_paid: u64,
+    pub total_wins: u64,
+    #[max_len(16)]
+    pub recent_wins: Vec<JackpotWin>, // Most recent wins, oldest first
+    pub is_active: bool,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+// Jackpot constants
+pub const JACKPOT_HISTORY_LEN: usize = 16;       // Matches ProgressiveJackpot.recent_wins
+pub const MAX_JACKPOT_CONTRIBUTION_BPS: u16 = 500; // At most 5% of a wager
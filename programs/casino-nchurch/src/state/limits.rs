@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+// Limits a player sets on their own play (None = no limit)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct LimitSettings {
+    pub daily_wager_limit: Option<u64>,
+    pub weekly_wager_limit: Option<u64>,
+    pub monthly_wager_limit: Option<u64>,
+    pub daily_loss_limit: Option<u64>,    // Net loss: stakes minus payouts and refunds
+    pub weekly_loss_limit: Option<u64>,
+    pub monthly_loss_limit: Option<u64>,
+    pub session_length: Option<u32>,      // Seconds of play allowed per session
+    pub session_cooldown: u32,            // Seconds a player must wait after a session ends
+}
+
+// Wagered and net-loss totals for one limit period
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct LimitUsage {
+    pub period: i64,      // Day index / PERIOD_DAYS of the period being tracked
+    pub wagered: u64,
+    pub net_loss: u64,
+}
+
+// Player-owned responsible-gaming account, enforced by every request instruction
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerLimits {
+    pub user: Pubkey,
+    pub limits: LimitSettings,
+    pub pending_limits: Option<LimitSettings>, // Looser limits waiting out LIMIT_LOOSEN_DELAY
+    pub pending_effective_at: i64,
+    pub daily_usage: LimitUsage,
+    pub weekly_usage: LimitUsage,
+    pub monthly_usage: LimitUsage,
+    pub session_started_at: i64,
+    pub excluded_until: i64,                   // i64::MAX = permanent self-exclusion
+    pub bump: u8,
+}
+
+// Responsible-gaming constants
+pub const LIMIT_LOOSEN_DELAY: i64 = 24 * 60 * 60;  // Looser limits apply after 24 hours
+pub const DAILY_PERIOD_DAYS: i64 = 1;
+pub const WEEKLY_PERIOD_DAYS: i64 = 7;
+pub const MONTHLY_PERIOD_DAYS: i64 = 30;
+pub const PERMANENT_EXCLUSION: i64 = i64::MAX;
@@ -21,15 +21,15 @@ pub enum LPStatus {
 pub struct LiquidityPool {
     pub authority: Pubkey,
     pub lp_token_mint: Pubkey,
-    pub total_liquidity: u64,
-    pub lp_token_supply: u64,
+    pub total_liquidity: u64,         // House equity (casino vault minus pending stakes) at the last sync
+    pub lp_token_supply: u64,         // Minted LP tokens plus the dead shares held for the house
     pub platform_fee_share_bps: u16, // % of platform fees to LPs
-    pub staking_rewards_apr: u16,     // Annual percentage rate for staking
+    pub staking_rewards_apr: u16,     // Unused: LPs earn house results and the fee share, not a fixed APR
     pub total_fees_distributed: u64,
     pub total_staking_rewards: u64,
     pub status: LPStatus,
     pub bump: u8,
-    pub lp_vault_bump: u8,
+    pub lp_vault_bump: u8,            // Legacy LP vault; the LP fee share now goes to the casino vault
 }
 
 #[account]
@@ -64,7 +64,7 @@ pub struct FeeDistribution {
     pub epoch: u64,
     pub total_platform_fees: u64,
     pub lp_fee_share: u64,
-    pub platform_fee_share: u64,
+    pub platform_fee_share: u64,      // Platform share still in the fee vault; withdraw_platform_fees draws it down
     pub distribution_complete: bool,
     pub bump: u8,
 }
@@ -119,6 +119,7 @@ pub enum VoteChoice {
 // LP Token economics constants
 pub const LP_TOKEN_DECIMALS: u8 = 6;
 pub const MIN_STAKING_AMOUNT: u64 = 1000; // Minimum LP tokens to stake
+pub const LP_MINIMUM_LIQUIDITY: u64 = 1000; // Dead shares locked from the first deposit
 pub const MAX_STAKING_PERIOD: i64 = 365 * 24 * 60 * 60; // 1 year in seconds
 
 // Staking period multipliers
@@ -129,5 +130,4 @@ pub const ULTRA_STAKING_MULTIPLIER: u16 = 300;   // 3x
 
 // Fee distribution constants
 pub const DEFAULT_PLATFORM_FEE_SHARE_BPS: u16 = 3000; // 30% to LPs
-pub const DEFAULT_STAKING_REWARDS_APR: u16 = 1200;     // 12% APR
 pub const FEE_DISTRIBUTION_EPOCH: i64 = 7 * 24 * 60 * 60; // 7 days
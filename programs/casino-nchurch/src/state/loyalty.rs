@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub enum LoyaltyReward {
+    FreeSpins { count: u16, bet_amount: u64 },   // House-funded slots spins at a fixed bet
+    UsdcCashback { amount: u64 },                 // Paid from the casino vault
+    QuestBoost { boost_bps: u16, duration: i64 }, // Extra quest reward share for `duration` seconds
+}
+
+// Casino-wide loyalty settings
+#[account]
+#[derive(InitSpace)]
+pub struct LoyaltyConfig {
+    pub points_mint: Option<Pubkey>, // SPL mint points can be tokenized into (0 decimals)
+    pub total_points_redeemed: u64,
+    pub total_points_tokenized: u64,
+    pub points_mint_bump: u8,
+    pub bump: u8,
+}
+
+// One entry of the redemption catalog
+#[account]
+#[derive(InitSpace)]
+pub struct LoyaltyItem {
+    pub item_id: u64,
+    pub reward: LoyaltyReward,
+    pub cost_points: u64,
+    pub stock: Option<u32>,  // Redemptions left (None = unlimited)
+    pub times_redeemed: u64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+// Loyalty constants
+pub const LOYALTY_POINTS_DIVISOR: u64 = 100;   // 1 point per 100 base units wagered
+pub const MAX_QUEST_BOOST_BPS: u16 = 10_000;   // At most doubles a quest reward
+pub const POINTS_MINT_DECIMALS: u8 = 0;
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+// Account layouts from before `layout_version` existed (version 1). Anchor discriminators
+// are derived from the type name, so a version 1 account carries the current discriminator
+// and is told apart by its length alone; every later layout records its version.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct HouseEdgeConfigV1 {
+    pub slots_rtp_bps: u16,
+    pub roulette_rtp_bps: u16,
+    pub aviator_rtp_bps: u16,
+    pub blackjack_rtp_bps: u16,
+    pub platform_fee_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct CasinoStateV1 {
+    pub authority: Pubkey,
+    pub vault: Pubkey,
+    pub total_games_played: u64,
+    pub total_volume: u64,
+    pub total_payouts: u64,
+    pub house_edge_config: HouseEdgeConfigV1,
+    pub is_active: bool,
+    pub casino_state_bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct UserStatsV1 {
+    pub user: Pubkey,
+    pub total_bets: u64,
+    pub total_wins: u64,
+    pub total_losses: u64,
+    pub loyalty_points: u64,
+    pub games_played: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct QuestCampaignV1 {
+    pub creator: Pubkey,
+    #[max_len(100)]
+    pub title: String,
+    #[max_len(500)]
+    pub description: String,
+    pub quest_type: crate::state::quest::QuestType,
+    pub reward_pool: u64,
+    pub max_participants: u32,
+    pub current_participants: u32,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub status: crate::state::quest::QuestStatus,
+    #[max_len(200)]
+    pub completion_criteria: String,
+    #[max_len(200)]
+    pub verification_method: String,
+    pub is_active: bool,
+    pub campaign_counter: u64,
+    pub bump: u8,
+}
+
+// Migration constants
+pub const CASINO_STATE_VERSION: u8 = 2;         // Matches CasinoState.layout_version
+pub const USER_STATS_VERSION: u8 = 2;           // Matches UserStats.layout_version
+pub const QUEST_CAMPAIGN_VERSION: u8 = 2;       // Matches QuestCampaign.layout_version
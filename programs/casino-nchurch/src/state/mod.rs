@@ -1,7 +1,25 @@
 pub mod casino;
 pub mod quest;
 pub mod liquidity;
+pub mod loyalty;
+pub mod vip;
+pub mod limits;
+pub mod referral;
+pub mod session;
+pub mod jackpot;
+pub mod tournament;
+pub mod history;
+pub mod migration;
 
 pub use casino::*;
 pub use quest::*;
 pub use liquidity::*;
+pub use loyalty::*;
+pub use vip::*;
+pub use limits::*;
+pub use referral::*;
+pub use session::*;
+pub use jackpot::*;
+pub use tournament::*;
+pub use history::*;
+pub use migration::*;
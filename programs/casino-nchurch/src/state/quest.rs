@@ -45,8 +45,11 @@ pub struct QuestCampaign {
     #[max_len(200)]
     pub verification_method: String,
     pub is_active: bool,
+    pub completed_participants: u32,
+    pub rewards_committed: u64,    // Sum of reward_amount over completed participations
     pub campaign_counter:u64,
     pub bump: u8,
+    pub layout_version: u8,        // QUEST_CAMPAIGN_VERSION; older layouts upgrade with migrate_quest_campaign
 }
 
 #[account]
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+// Referral revenue share, kept on CasinoState
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct ReferralConfig {
+    pub level_share_bps: [u16; 3],  // Share of house net revenue per upline level (direct referrer first)
+    pub max_fee_share_bps: u16,     // Most of a game's platform fee all levels together may take
+}
+
+// A player's referrer, set once, with the upline it inherits
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub user: Pubkey,
+    #[max_len(3)]
+    pub upline: Vec<Pubkey>,   // Direct referrer first, then their referrers
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+// Per-referrer earnings and stats
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerStats {
+    pub referrer: Pubkey,
+    pub direct_referrals: u64,
+    pub referred_games: u64,     // Settled games that credited this referrer
+    pub referred_volume: u64,    // Stakes on those games
+    pub total_earned: u64,
+    pub claimable: u64,          // Held in the fee vault until claim_referral_earnings
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+// Referral constants
+pub const MAX_REFERRAL_LEVELS: usize = 3;   // Matches Referral.upline and level_share_bps
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::state::casino::GameType;
+
+// Ephemeral key a player authorizes to sign game instructions on their behalf.
+// Stakes are still taken from the player's token account through an SPL delegation to this
+// PDA, and payouts always go back to it.
+#[account]
+#[derive(InitSpace)]
+pub struct SessionKey {
+    pub user: Pubkey,             // Owner wallet
+    pub session_signer: Pubkey,   // Ephemeral key allowed to sign
+    pub expires_at: i64,
+    pub max_wager: u64,           // Total the key may stake (also the delegated token amount)
+    pub wagered: u64,
+    #[max_len(4)]
+    pub allowed_games: Vec<GameType>,
+    pub bump: u8,
+}
+
+// Session key constants
+pub const MAX_SESSION_DURATION: i64 = 7 * 24 * 60 * 60;  // Keys live at most a week
+pub const MAX_SESSION_GAMES: usize = 4;                   // Matches SessionKey.allowed_games
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::casino::GameType;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum TournamentMetric {
+    NetProfit,          // Payouts minus stakes over the tournament
+    BiggestMultiplier,  // Best single-game payout / stake (TOURNAMENT_MULTIPLIER_SCALE = 1.00x)
+    WageredVolume,      // Total staked over the tournament
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct LeaderboardEntry {
+    pub user: Pubkey,
+    pub score: i64,
+}
+
+// Tournament rules, fixed at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct TournamentConfig {
+    pub entry_fee: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    #[max_len(4)]
+    pub eligible_games: Vec<GameType>,
+    pub metric: TournamentMetric,
+    #[max_len(10)]
+    pub payout_curve_bps: Vec<u16>,      // Prize pool share per rank, first place first
+    pub leaderboard_size: u8,            // Top N kept on the leaderboard
+}
+
+// Time-boxed tournament; entry fees are held in the tournament vault as the prize pool
+#[account]
+#[derive(InitSpace)]
+pub struct Tournament {
+    pub tournament_id: u64,
+    pub config: TournamentConfig,
+    #[max_len(10)]
+    pub leaderboard: Vec<LeaderboardEntry>, // Best score first, ties keep the earlier score ahead
+    pub prize_pool: u64,
+    pub participants: u32,
+    pub is_finalized: bool,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+// A player's registration and running score in one tournament
+#[account]
+#[derive(InitSpace)]
+pub struct TournamentEntry {
+    pub tournament: Pubkey,
+    pub user: Pubkey,
+    pub score: i64,
+    pub games_played: u32,       // Scored games
+    pub wagered: u64,
+    pub payout: u64,
+    pub bump: u8,
+}
+
+// Tournament constants
+pub const MAX_LEADERBOARD_SIZE: usize = 10;        // Matches Tournament.leaderboard and payout_curve_bps
+pub const MAX_TOURNAMENT_GAMES: usize = 4;         // Matches Tournament.eligible_games
+pub const TOURNAMENT_MULTIPLIER_SCALE: u64 = 100;  // 100 = 1.00x
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum VipVolumeBasis {
+    Lifetime,       // UserStats.total_bets
+    Rolling30Days,  // Sum of UserStats.daily_volume
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct VipTier {
+    pub min_volume: u64,      // Volume needed to reach the tier
+    pub rakeback_bps: u16,    // Share of the house edge on every settled stake paid back
+    pub max_bet_bps: u32,     // Per-game max bet, 10000 = GameState.max_bet
+}
+
+// Admin-configured VIP tier table, sorted by min_volume
+#[account]
+#[derive(InitSpace)]
+pub struct VipConfig {
+    pub volume_basis: VipVolumeBasis,
+    #[max_len(8)]
+    pub tiers: Vec<VipTier>,
+    pub bump: u8,
+}
+
+// VIP constants
+pub const MAX_VIP_TIERS: usize = 8;             // Matches VipConfig.tiers
+pub const VIP_ROLLING_DAYS: usize = 30;         // Daily volume buckets kept per user
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
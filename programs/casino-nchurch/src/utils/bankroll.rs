@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::errors::CasinoError;
+use crate::utils::math::{checked_add, mul_bps, mul_div, BPS_DENOMINATOR};
+
+// Bankroll protection.
+// Every pending game reserves its worst-case payout in CasinoState.outstanding_liabilities
+// until it is settled or refunded, and new bets are only accepted while the total stays
+// within `max_exposure_bps` of the vault balance.
+
+/// Reject a new liability that would push outstanding liabilities past the exposure limit
+pub fn validate_exposure(outstanding_liabilities: u64, additional_liability: u64, vault_balance: u64, max_exposure_bps: u16) -> Result<()> {
+    let exposure_limit = mul_bps(vault_balance, max_exposure_bps as u64)?;
+    require!(
+        checked_add(outstanding_liabilities, additional_liability)? <= exposure_limit,
+        CasinoError::InsufficientBankroll
+    );
+    Ok(())
+}
+
+/// Kelly stake for the house. The house risks `net` units of bankroll to win one unit with an
+/// expected gain of `edge` per unit, so the Kelly fraction of bankroll put at risk is `edge`:
+/// max bet = bankroll * edge / net payout multiple.
+pub fn kelly_max_bet(bankroll: u64, rtp_bps: u16, max_payout_bps: u64) -> Result<u64> {
+    let edge_bps = BPS_DENOMINATOR.saturating_sub(rtp_bps as u64);
+    let net_payout_bps = max_payout_bps.saturating_sub(BPS_DENOMINATOR);
+    require!(net_payout_bps > 0, CasinoError::InvalidPayoutCalculation);
+    mul_div(bankroll, edge_bps, net_payout_bps)
+}
+
+/// Largest bet with a gross payout of `max_payout_bps` that still fits under the exposure limit
+pub fn exposure_max_bet(outstanding_liabilities: u64, vault_balance: u64, max_exposure_bps: u16, max_payout_bps: u64) -> Result<u64> {
+    require!(max_payout_bps > 0, CasinoError::InvalidPayoutCalculation);
+    let exposure_limit = mul_bps(vault_balance, max_exposure_bps as u64)?;
+    let headroom = exposure_limit.saturating_sub(outstanding_liabilities);
+    mul_div(headroom, BPS_DENOMINATOR, max_payout_bps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exposure_limit_is_a_fraction_of_the_vault() {
+        // 20% of a 1,000,000 vault
+        assert!(validate_exposure(150_000, 50_000, 1_000_000, 2_000).is_ok());
+        assert!(validate_exposure(150_000, 50_001, 1_000_000, 2_000).is_err());
+        assert!(validate_exposure(0, 1, 0, 2_000).is_err());
+    }
+
+    #[test]
+    fn kelly_bet_scales_with_edge_and_payout() {
+        // 2.7% edge on a 36x straight-up bet: risk 2.7% of 1,000,000 across 35 net units
+        assert_eq!(kelly_max_bet(1_000_000, 9_730, 360_000).unwrap(), 771);
+        // Even money at 1% edge: 1% of the bankroll
+        assert_eq!(kelly_max_bet(1_000_000, 9_900, 20_000).unwrap(), 10_000);
+        // No edge, no bet
+        assert_eq!(kelly_max_bet(1_000_000, 10_000, 20_000).unwrap(), 0);
+        assert!(kelly_max_bet(1_000_000, 9_900, 10_000).is_err());
+    }
+
+    #[test]
+    fn exposure_bet_uses_remaining_headroom() {
+        // 200,000 limit, 100,000 reserved, 10x payout
+        assert_eq!(exposure_max_bet(100_000, 1_000_000, 2_000, 100_000).unwrap(), 10_000);
+        assert_eq!(exposure_max_bet(250_000, 1_000_000, 2_000, 100_000).unwrap(), 0);
+    }
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::history::*;
+
+/// Keep a settled proof in the player's history (created on their first settle)
+pub fn store_game_proof(game_history: &mut GameHistory, user: Pubkey, bump: u8, proof: StoredProof) {
+    game_history.user = user;
+    game_history.bump = bump;
+    record_game_proof(&mut game_history.proofs, proof);
+}
+
+/// Append a proof to the player's history, dropping the oldest once it is full
+pub fn record_game_proof(proofs: &mut Vec<StoredProof>, proof: StoredProof) {
+    if proofs.len() >= GAME_HISTORY_LEN {
+        proofs.remove(0);
+    }
+    proofs.push(proof);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::casino::GameType;
+
+    #[test]
+    fn history_keeps_the_latest_proofs() {
+        let mut proofs = Vec::new();
+        for nonce in 0..GAME_HISTORY_LEN as u64 + 3 {
+            record_game_proof(&mut proofs, StoredProof {
+                game: Pubkey::default(),
+                nonce,
+                game_type: GameType::Roulette,
+                bet_amount: 0,
+                rtp_bps: 0,
+                game_data: vec![],
+                randomness: [0; 32],
+                payout: 0,
+                slot: nonce,
+            });
+        }
+        assert_eq!(proofs.len(), GAME_HISTORY_LEN);
+        assert_eq!(proofs.first().unwrap().nonce, 3);
+        assert_eq!(proofs.last().unwrap().nonce, GAME_HISTORY_LEN as u64 + 2);
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::errors::CasinoError;
+use crate::state::casino::{Payline, SLOTS_MAX_REELS, SLOTS_MAX_SYMBOLS, SLOTS_ROWS};
+use crate::state::jackpot::*;
+use crate::utils::math::mul_bps;
+use crate::utils::randomness::{RandomnessStream, DOMAIN_SLOTS_JACKPOT};
+
+// Progressive jackpot.
+// Every paid spin moves `contribution_bps` of its stake into the jackpot vault and becomes
+// eligible. An eligible spin wins when an active payline lands `jackpot_run` of the jackpot
+// symbol from the first reel, or when the separate 1-in-`roll_odds` draw on the spin's own
+// revealed randomness hits. The winner takes the pot above `reseed_amount`.
+
+/// Slice of a stake moved into the pot
+pub fn jackpot_contribution(wagered: u64, contribution_bps: u16) -> Result<u64> {
+    mul_bps(wagered, contribution_bps as u64)
+}
+
+/// True when any active payline starts with `run` of `symbol`
+pub fn hits_top_combination(paylines: &[Payline], window: &[u8], active_paylines: u32, symbol: u8, run: u8) -> bool {
+    paylines.iter().enumerate().any(|(index, payline)| {
+        active_paylines & (1 << index) != 0
+            && payline.rows.len() >= run as usize
+            && (0..run as usize).all(|reel| window[reel * SLOTS_ROWS as usize + payline.rows[reel] as usize] == symbol)
+    })
+}
+
+/// Separate jackpot draw, domain-separated from the reel stops (roll_odds 0 = no roll)
+pub fn jackpot_roll_hits(game: Pubkey, randomness: &[u8], roll_odds: u64) -> bool {
+    roll_odds > 0 && RandomnessStream::new(DOMAIN_SLOTS_JACKPOT, game.to_bytes(), randomness).uniform(0, roll_odds) == 0
+}
+
+pub fn validate_jackpot_config(contribution_bps: u16, jackpot_symbol: u8, jackpot_run: u8) -> Result<()> {
+    require!(contribution_bps <= MAX_JACKPOT_CONTRIBUTION_BPS, CasinoError::InvalidJackpotConfig);
+    require!(jackpot_symbol < SLOTS_MAX_SYMBOLS, CasinoError::InvalidJackpotConfig);
+    require!(jackpot_run > 0 && jackpot_run as usize <= SLOTS_MAX_REELS, CasinoError::InvalidJackpotConfig);
+    Ok(())
+}
+
+/// Append a win to the on-chain history, dropping the oldest once it is full
+pub fn record_jackpot_win(recent_wins: &mut Vec<JackpotWin>, win: JackpotWin) {
+    if recent_wins.len() >= JACKPOT_HISTORY_LEN {
+        recent_wins.remove(0);
+    }
+    recent_wins.push(win);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(rows: &[u8]) -> Payline {
+        Payline { rows: rows.to_vec() }
+    }
+
+    #[test]
+    fn top_combination_needs_an_active_payline() {
+        let paylines = vec![line(&[1, 1, 1]), line(&[0, 1, 2])];
+        // Reel-major window: the middle row shows 9 9 9, the diagonal 4 9 3
+        let window = [4, 9, 2, 5, 9, 6, 7, 9, 3];
+
+        assert!(hits_top_combination(&paylines, &window, 0b01, 9, 3));
+        assert!(!hits_top_combination(&paylines, &window, 0b10, 9, 3));
+        assert!(!hits_top_combination(&paylines, &window, 0b11, 9, 4));
+        assert!(hits_top_combination(&paylines, &window, 0b10, 4, 1));
+    }
+
+    #[test]
+    fn jackpot_run_must_fit_on_the_reels() {
+        assert!(validate_jackpot_config(100, 9, 3).is_ok());
+        assert!(validate_jackpot_config(100, 9, SLOTS_MAX_REELS as u8).is_ok());
+        assert!(validate_jackpot_config(100, 9, 0).is_err());
+        assert!(validate_jackpot_config(100, 9, SLOTS_MAX_REELS as u8 + 1).is_err());
+        assert!(validate_jackpot_config(100, 9, u8::MAX).is_err());
+    }
+
+    #[test]
+    fn history_keeps_the_latest_wins() {
+        let mut recent_wins = Vec::new();
+        for amount in 0..JACKPOT_HISTORY_LEN as u64 + 3 {
+            record_jackpot_win(&mut recent_wins, JackpotWin {
+                user: Pubkey::default(),
+                game: Pubkey::default(),
+                amount,
+                trigger: JackpotTrigger::Roll,
+                slot: amount,
+                timestamp: 0,
+            });
+        }
+        assert_eq!(recent_wins.len(), JACKPOT_HISTORY_LEN);
+        assert_eq!(recent_wins[0].amount, 3);
+        assert_eq!(recent_wins.last().unwrap().amount, JACKPOT_HISTORY_LEN as u64 + 2);
+    }
+}
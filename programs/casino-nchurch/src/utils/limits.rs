@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use crate::errors::LimitsError;
+use crate::state::limits::*;
+use crate::state::vip::SECONDS_PER_DAY;
+
+// Responsible gaming.
+// Wager and net-loss totals are kept per fixed calendar period (UTC days, 7-day weeks and
+// 30-day months counted from the unix epoch) and reset when a new period starts. A stake is
+// booked as a loss in full when it is placed; payouts and refunds are credited back to the
+// period they land in. Tighter limits apply at once, looser ones after LIMIT_LOOSEN_DELAY.
+
+/// The tighter of two optional limits (None = unlimited)
+fn tighter(current: Option<u64>, requested: Option<u64>) -> Option<u64> {
+    match (current, requested) {
+        (Some(current), Some(requested)) => Some(current.min(requested)),
+        (limit, None) | (None, limit) => limit,
+    }
+}
+
+/// Field-by-field tighter of the current and requested settings
+pub fn tighten_limits(current: &LimitSettings, requested: &LimitSettings) -> LimitSettings {
+    LimitSettings {
+        daily_wager_limit: tighter(current.daily_wager_limit, requested.daily_wager_limit),
+        weekly_wager_limit: tighter(current.weekly_wager_limit, requested.weekly_wager_limit),
+        monthly_wager_limit: tighter(current.monthly_wager_limit, requested.monthly_wager_limit),
+        daily_loss_limit: tighter(current.daily_loss_limit, requested.daily_loss_limit),
+        weekly_loss_limit: tighter(current.weekly_loss_limit, requested.weekly_loss_limit),
+        monthly_loss_limit: tighter(current.monthly_loss_limit, requested.monthly_loss_limit),
+        session_length: tighter(current.session_length.map(u64::from), requested.session_length.map(u64::from)).map(|length| length as u32),
+        session_cooldown: current.session_cooldown.max(requested.session_cooldown),
+    }
+}
+
+/// Apply requested settings: the tighter parts at once, the rest queued until `now + LIMIT_LOOSEN_DELAY`
+pub fn request_limits(player_limits: &mut PlayerLimits, requested: LimitSettings, now: i64) {
+    let immediate = tighten_limits(&player_limits.limits, &requested);
+
+    if immediate == requested {
+        player_limits.pending_limits = None;
+        player_limits.pending_effective_at = 0;
+    } else {
+        player_limits.pending_limits = Some(requested);
+        player_limits.pending_effective_at = now.saturating_add(LIMIT_LOOSEN_DELAY);
+    }
+    player_limits.limits = immediate;
+}
+
+/// Promote queued looser limits once their delay has passed
+pub fn activate_pending_limits(player_limits: &mut PlayerLimits, now: i64) {
+    if player_limits.pending_limits.is_some() && now >= player_limits.pending_effective_at {
+        player_limits.limits = player_limits.pending_limits.take().unwrap_or_default();
+        player_limits.pending_effective_at = 0;
+    }
+}
+
+fn roll_usage(usage: &mut LimitUsage, now: i64, period_days: i64) {
+    let period = now.div_euclid(SECONDS_PER_DAY * period_days);
+    if usage.period != period {
+        *usage = LimitUsage { period, ..Default::default() };
+    }
+}
+
+fn roll_all_usage(player_limits: &mut PlayerLimits, now: i64) {
+    roll_usage(&mut player_limits.daily_usage, now, DAILY_PERIOD_DAYS);
+    roll_usage(&mut player_limits.weekly_usage, now, WEEKLY_PERIOD_DAYS);
+    roll_usage(&mut player_limits.monthly_usage, now, MONTHLY_PERIOD_DAYS);
+}
+
+fn within_limit(used: u64, stake: u64, limit: Option<u64>) -> bool {
+    limit.is_none_or(|limit| used.saturating_add(stake) <= limit)
+}
+
+/// Reject a stake the player's limits do not allow, otherwise book it against every period
+pub fn check_and_record_stake(player_limits: &mut PlayerLimits, stake: u64, now: i64) -> Result<()> {
+    require!(now >= player_limits.excluded_until, LimitsError::SelfExcluded);
+
+    activate_pending_limits(player_limits, now);
+    roll_all_usage(player_limits, now);
+
+    // Validate the session: a new one starts once the previous session and its cooldown are over
+    if let Some(session_length) = player_limits.limits.session_length {
+        let session_end = player_limits.session_started_at.saturating_add(session_length as i64);
+        let cooldown_end = session_end.saturating_add(player_limits.limits.session_cooldown as i64);
+        if now >= cooldown_end {
+            player_limits.session_started_at = now;
+        } else {
+            require!(now < session_end, LimitsError::SessionCooldown);
+        }
+    }
+
+    let limits = &player_limits.limits;
+    require!(
+        within_limit(player_limits.daily_usage.wagered, stake, limits.daily_wager_limit)
+            && within_limit(player_limits.weekly_usage.wagered, stake, limits.weekly_wager_limit)
+            && within_limit(player_limits.monthly_usage.wagered, stake, limits.monthly_wager_limit),
+        LimitsError::WagerLimitExceeded
+    );
+    require!(
+        within_limit(player_limits.daily_usage.net_loss, stake, limits.daily_loss_limit)
+            && within_limit(player_limits.weekly_usage.net_loss, stake, limits.weekly_loss_limit)
+            && within_limit(player_limits.monthly_usage.net_loss, stake, limits.monthly_loss_limit),
+        LimitsError::LossLimitExceeded
+    );
+
+    for usage in [&mut player_limits.daily_usage, &mut player_limits.weekly_usage, &mut player_limits.monthly_usage] {
+        usage.wagered = usage.wagered.saturating_add(stake);
+        usage.net_loss = usage.net_loss.saturating_add(stake);
+    }
+    Ok(())
+}
+
+/// Credit a payout or refund back against the current periods. `stake_reversed` is the part
+/// of a refunded stake that no longer counts as wagered.
+pub fn record_return(player_limits: &mut PlayerLimits, stake_reversed: u64, returned: u64, now: i64) {
+    roll_all_usage(player_limits, now);
+
+    for usage in [&mut player_limits.daily_usage, &mut player_limits.weekly_usage, &mut player_limits.monthly_usage] {
+        usage.wagered = usage.wagered.saturating_sub(stake_reversed);
+        usage.net_loss = usage.net_loss.saturating_sub(returned);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = SECONDS_PER_DAY;
+
+    fn player_limits(limits: LimitSettings) -> PlayerLimits {
+        PlayerLimits {
+            user: Pubkey::default(),
+            limits,
+            pending_limits: None,
+            pending_effective_at: 0,
+            daily_usage: LimitUsage::default(),
+            weekly_usage: LimitUsage::default(),
+            monthly_usage: LimitUsage::default(),
+            session_started_at: 0,
+            excluded_until: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn tightening_applies_at_once_and_loosening_waits() {
+        let mut limits = player_limits(LimitSettings { daily_wager_limit: Some(1_000), ..Default::default() });
+
+        request_limits(&mut limits, LimitSettings { daily_wager_limit: Some(500), ..Default::default() }, 10 * DAY);
+        assert_eq!(limits.limits.daily_wager_limit, Some(500));
+        assert!(limits.pending_limits.is_none());
+
+        // Raising the limit and removing it both count as loosening
+        request_limits(&mut limits, LimitSettings { daily_wager_limit: None, daily_loss_limit: Some(200), ..Default::default() }, 10 * DAY);
+        assert_eq!(limits.limits.daily_wager_limit, Some(500));
+        assert_eq!(limits.limits.daily_loss_limit, Some(200));
+
+        activate_pending_limits(&mut limits, 10 * DAY + LIMIT_LOOSEN_DELAY - 1);
+        assert_eq!(limits.limits.daily_wager_limit, Some(500));
+        activate_pending_limits(&mut limits, 10 * DAY + LIMIT_LOOSEN_DELAY);
+        assert_eq!(limits.limits.daily_wager_limit, None);
+        assert_eq!(limits.limits.daily_loss_limit, Some(200));
+    }
+
+    #[test]
+    fn loss_limit_counts_payouts_and_resets_each_day() {
+        let mut limits = player_limits(LimitSettings { daily_loss_limit: Some(100), ..Default::default() });
+        let now = 3 * DAY;
+
+        check_and_record_stake(&mut limits, 80, now).unwrap();
+        assert!(check_and_record_stake(&mut limits, 30, now).is_err());
+
+        // A 50 payout brings the net loss back to 30
+        record_return(&mut limits, 0, 50, now);
+        check_and_record_stake(&mut limits, 70, now).unwrap();
+        assert_eq!(limits.daily_usage.net_loss, 100);
+        assert_eq!(limits.daily_usage.wagered, 150);
+
+        check_and_record_stake(&mut limits, 100, now + DAY).unwrap();
+    }
+
+    #[test]
+    fn sessions_and_self_exclusion_block_play() {
+        let mut limits = player_limits(LimitSettings { session_length: Some(3_600), session_cooldown: 600, ..Default::default() });
+        let start = 5 * DAY;
+
+        check_and_record_stake(&mut limits, 10, start).unwrap();
+        check_and_record_stake(&mut limits, 10, start + 3_599).unwrap();
+        assert!(check_and_record_stake(&mut limits, 10, start + 3_600).is_err());
+        check_and_record_stake(&mut limits, 10, start + 4_200).unwrap();
+        assert_eq!(limits.session_started_at, start + 4_200);
+
+        limits.excluded_until = PERMANENT_EXCLUSION;
+        assert!(check_and_record_stake(&mut limits, 0, start + 100 * DAY).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use crate::errors::LiquidityError;
+use crate::state::liquidity::LP_MINIMUM_LIQUIDITY;
+use crate::utils::math::{checked_add, mul_bps, mul_div};
+
+// LP shares.
+// LP deposits are the casino bankroll: they sit in the casino vault, so every game the
+// house wins or loses moves the value behind each LP token.
+
+/// Bankroll value owned by LPs: the vault balance minus stakes held for unsettled games
+/// and any other balance already owed to players (`reserved`, e.g. unclaimed rakeback)
+pub fn house_equity(vault_balance: u64, reserved: u64) -> u64 {
+    vault_balance.saturating_sub(reserved)
+}
+
+/// LP tokens minted for a deposit at the current share price, and the dead shares locked
+/// alongside them. `lp_supply` counts dead shares, which are never minted or redeemed. The
+/// first deposit is priced 1:1 against the bankroll already in the vault: that equity stays
+/// with the house as dead shares, together with LP_MINIMUM_LIQUIDITY taken from the deposit,
+/// so a donation to a near-empty pool cannot inflate the share price.
+pub fn lp_tokens_for_deposit(amount: u64, house_equity: u64, lp_supply: u64) -> Result<(u64, u64)> {
+    if lp_supply == 0 {
+        require!(amount > LP_MINIMUM_LIQUIDITY, LiquidityError::InvalidLPTokenAmount);
+        return Ok((amount - LP_MINIMUM_LIQUIDITY, checked_add(house_equity, LP_MINIMUM_LIQUIDITY)?));
+    }
+    require!(house_equity > 0, LiquidityError::LiquidityPoolInsolvent);
+    Ok((mul_div(amount, lp_supply, house_equity)?, 0))
+}
+
+/// USDC paid out for redeeming `lp_tokens` at the current share price
+pub fn lp_redemption_amount(lp_tokens: u64, house_equity: u64, lp_supply: u64) -> Result<u64> {
+    require!(lp_tokens <= lp_supply, LiquidityError::InsufficientLPTokens);
+    mul_div(lp_tokens, house_equity, lp_supply)
+}
+
+/// LP share of an epoch's platform fees, returned to the bankroll by distribute_platform_fees
+pub fn calculate_lp_fee_share(total_fees: u64, lp_share_bps: u16) -> Result<u64> {
+    mul_bps(total_fees, lp_share_bps as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lp_share_price_follows_house_results() {
+        // First deposit mints 1:1, less the locked minimum
+        assert_eq!(lp_tokens_for_deposit(1_000_000, 0, 0).unwrap(), (1_000_000 - LP_MINIMUM_LIQUIDITY, LP_MINIMUM_LIQUIDITY));
+
+        // The house wins 10%: new deposits buy fewer tokens and old tokens redeem for more
+        assert_eq!(lp_tokens_for_deposit(1_100_000, 1_100_000, 1_000_000).unwrap(), (1_000_000, 0));
+        assert_eq!(lp_redemption_amount(500_000, 1_100_000, 1_000_000).unwrap(), 550_000);
+
+        // The house loses 25%
+        assert_eq!(lp_redemption_amount(1_000_000, 750_000, 1_000_000).unwrap(), 750_000);
+        assert_eq!(lp_tokens_for_deposit(750_000, 750_000, 1_000_000).unwrap(), (1_000_000, 0));
+
+        // Rounding always favours the pool
+        assert_eq!(lp_tokens_for_deposit(2, 3, 1).unwrap(), (0, 0));
+        assert_eq!(lp_redemption_amount(1, 2, 3).unwrap(), 0);
+    }
+
+    #[test]
+    fn first_deposit_cannot_take_the_house_bankroll() {
+        // 5,000,000 of house bankroll is already in the vault when the first LP deposits
+        let (minted, dead) = lp_tokens_for_deposit(1_000_000, 5_000_000, 0).unwrap();
+        assert_eq!((minted, dead), (1_000_000 - LP_MINIMUM_LIQUIDITY, 5_000_000 + LP_MINIMUM_LIQUIDITY));
+        let supply = minted + dead;
+        assert_eq!(lp_redemption_amount(minted, 6_000_000, supply).unwrap(), 1_000_000 - LP_MINIMUM_LIQUIDITY);
+
+        // A donation is shared with the dead shares, so inflating the price costs the donor
+        let donation = 100_000_000;
+        let (attacker, dead) = lp_tokens_for_deposit(1_000_000, 0, 0).unwrap();
+        let supply = attacker + dead;
+        let (victim, _) = lp_tokens_for_deposit(1_000_000, 1_000_000 + donation, supply).unwrap();
+        let equity = 2_000_000 + donation;
+        let supply = supply + victim;
+        let attacker_out = lp_redemption_amount(attacker, equity, supply).unwrap();
+        let victim_out = lp_redemption_amount(victim, equity, supply).unwrap();
+        assert!(attacker_out < 1_000_000 + donation);
+        assert!(1_000_000 - victim_out < (1_000_000 + donation) - attacker_out);
+
+        assert!(lp_tokens_for_deposit(LP_MINIMUM_LIQUIDITY, 0, 0).is_err());
+    }
+
+    #[test]
+    fn lp_shares_reject_insolvent_pools_and_overdraws() {
+        assert!(lp_tokens_for_deposit(1_000_000, 0, 1_000_000).is_err());
+        assert!(lp_redemption_amount(1_000_001, 1_000_000, 1_000_000).is_err());
+        assert_eq!(lp_redemption_amount(1, 0, 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn pending_stakes_are_not_equity() {
+        assert_eq!(house_equity(1_000_000, 40_000), 960_000);
+        assert_eq!(house_equity(10, 40), 0);
+    }
+
+    #[test]
+    fn lp_fee_share_is_a_fraction_of_the_epoch_fees() {
+        assert_eq!(calculate_lp_fee_share(1_000, 3_000).unwrap(), 300);
+        assert_eq!(calculate_lp_fee_share(999, 3_000).unwrap(), 299);
+        assert_eq!(calculate_lp_fee_share(1_000, 0).unwrap(), 0);
+    }
+}
@@ -1,7 +1,109 @@
 use anchor_lang::prelude::*;
 use crate::state::casino::*;
+use crate::errors::CasinoError;
+
+// Fixed-point arithmetic for every payout, RTP, fee and APR calculation.
+// Everything is integer-only with u128 intermediates and checked operations, and every
+// division rounds down, so off-chain verifiers reproduce results bit for bit.
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// `amount * numerator / denominator`, rounded down
+pub fn mul_div(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    require!(denominator > 0, CasinoError::MathOverflow);
+    let result = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(CasinoError::MathOverflow)?
+        / denominator as u128;
+    u64::try_from(result).map_err(|_| error!(CasinoError::MathOverflow))
+}
+
+/// `amount * bps / 10_000`, rounded down
+pub fn mul_bps(amount: u64, bps: u64) -> Result<u64> {
+    mul_div(amount, bps, BPS_DENOMINATOR)
+}
 
+pub fn checked_add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or_else(|| error!(CasinoError::MathOverflow))
+}
 
+/// Unsigned Q64.64 fixed-point number (upper 64 bits integer part, lower 64 bits fraction)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Q64(pub u128);
+
+impl Q64 {
+    pub const FRACTION_BITS: u32 = 64;
+    pub const ZERO: Q64 = Q64(0);
+    pub const ONE: Q64 = Q64(1 << 64);
+
+    pub fn from_int(value: u64) -> Q64 {
+        Q64((value as u128) << Self::FRACTION_BITS)
+    }
+
+    /// `numerator / denominator`, rounded down
+    pub fn from_ratio(numerator: u64, denominator: u64) -> Result<Q64> {
+        require!(denominator > 0, CasinoError::MathOverflow);
+        Ok(Q64(((numerator as u128) << Self::FRACTION_BITS) / denominator as u128))
+    }
+
+    pub fn from_bps(bps: u64) -> Result<Q64> {
+        Self::from_ratio(bps, BPS_DENOMINATOR)
+    }
+
+    pub fn checked_add(self, other: Q64) -> Result<Q64> {
+        self.0.checked_add(other.0).map(Q64).ok_or_else(|| error!(CasinoError::MathOverflow))
+    }
+
+    pub fn checked_sub(self, other: Q64) -> Result<Q64> {
+        self.0.checked_sub(other.0).map(Q64).ok_or_else(|| error!(CasinoError::MathOverflow))
+    }
+
+    /// Product rounded down, computed from 64-bit limbs so no intermediate exceeds u128
+    pub fn checked_mul(self, other: Q64) -> Result<Q64> {
+        const LOW_MASK: u128 = u64::MAX as u128;
+        let (a_high, a_low) = (self.0 >> 64, self.0 & LOW_MASK);
+        let (b_high, b_low) = (other.0 >> 64, other.0 & LOW_MASK);
+
+        let high_product = a_high * b_high;
+        require!(high_product <= LOW_MASK, CasinoError::MathOverflow);
+        let high = high_product << 64;
+        let cross = (a_high * b_low)
+            .checked_add(a_low * b_high)
+            .ok_or(CasinoError::MathOverflow)?;
+        let low = (a_low * b_low) >> 64;
+
+        high.checked_add(cross)
+            .and_then(|sum| sum.checked_add(low))
+            .map(Q64)
+            .ok_or_else(|| error!(CasinoError::MathOverflow))
+    }
+
+    /// Exponentiation by squaring; each step rounds down
+    pub fn checked_pow(self, exponent: u32) -> Result<Q64> {
+        let mut result = Q64::ONE;
+        let mut base = self;
+        let mut remaining = exponent;
+
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// `amount * self`, rounded down
+    pub fn mul_u64(self, amount: u64) -> Result<u64> {
+        let product = Q64::from_int(amount).checked_mul(self)?;
+        u64::try_from(product.0 >> Self::FRACTION_BITS).map_err(|_| error!(CasinoError::MathOverflow))
+    }
+}
 
 // Mathematical utility functions for casino games
 pub fn calculate_rtp(house_edge_bps: u16) -> u16 {
@@ -12,68 +114,27 @@ pub fn calculate_house_edge(rtp_bps: u16) -> u16 {
     10000 - rtp_bps
 }
 
-pub fn calculate_platform_fee(amount: u64, fee_bps: u16) -> u64 {
-    (amount * fee_bps as u64) / 10000
+/// Scale a raw game payout by the configured return-to-player
+pub fn apply_rtp(payout: u64, rtp_bps: u16) -> Result<u64> {
+    mul_bps(payout, rtp_bps as u64)
 }
 
-pub fn calculate_lp_fee_share(total_fees: u64, lp_share_bps: u16) -> u64 {
-    (total_fees * lp_share_bps as u64) / 10000
+pub fn calculate_platform_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    mul_bps(amount, fee_bps as u64)
 }
 
-pub fn calculate_staking_rewards(staked_amount: u64, apr_bps: u16, time_elapsed: i64) -> u64 {
-    let seconds_per_year = 365 * 24 * 60 * 60;
-    let time_elapsed_seconds = time_elapsed as u64;
-    
-    if time_elapsed_seconds >= seconds_per_year {
-        (staked_amount * apr_bps as u64) / 10000
-    } else {
-        (staked_amount * apr_bps as u64 * time_elapsed_seconds) / (10000 * seconds_per_year)
-    }
+/// Platform fee owed on a settled game, charged on house revenue or on volume per `fee_basis`
+pub fn calculate_game_fee(config: &HouseEdgeConfig, wagered: u64, payout: u64) -> Result<u64> {
+    let fee_base = match config.fee_basis {
+        FeeBasis::HouseRevenue => wagered.saturating_sub(payout),
+        FeeBasis::Volume => wagered,
+    };
+    calculate_platform_fee(fee_base, config.platform_fee_bps)
 }
 
-pub fn calculate_compound_interest(principal: u64, rate_bps: u16, periods: u32) -> u64 {
-    let rate = rate_bps as f64 / 10000.0;
-    let compound_factor = (1.0 + rate).powi(periods as i32);
-    (principal as f64 * compound_factor) as u64
-}
-
-// Probability calculations for games
-pub fn calculate_slots_probability(symbol: u8, count: u8) -> f64 {
-    match count {
-        1 => 0.3,  // 30% chance for single symbol
-        2 => 0.1,  // 10% chance for pair
-        3 => 0.01, // 1% chance for triple
-        _ => 0.0,
-    }
-}
-
-pub fn calculate_roulette_probability(bet_type: RouletteBetType) -> f64 {
-    match bet_type {
-        RouletteBetType::Straight => 1.0 / 37.0,
-        RouletteBetType::Split => 2.0 / 37.0,
-        RouletteBetType::Street => 3.0 / 37.0,
-        RouletteBetType::Corner => 4.0 / 37.0,
-        RouletteBetType::Line => 6.0 / 37.0,
-        RouletteBetType::Column => 12.0 / 37.0,
-        RouletteBetType::Dozen => 12.0 / 37.0,
-        RouletteBetType::Red => 18.0 / 37.0,
-        RouletteBetType::Black => 18.0 / 37.0,
-        RouletteBetType::Even => 18.0 / 37.0,
-        RouletteBetType::Odd => 18.0 / 37.0,
-        RouletteBetType::Low => 18.0 / 37.0,
-        RouletteBetType::High => 18.0 / 37.0,
-    }
-}
-
-pub fn calculate_blackjack_probability(hand_value: u8) -> f64 {
-    match hand_value {
-        21 => 0.048,  // 4.8% chance of blackjack
-        20 => 0.1,    // 10% chance of 20
-        19 => 0.1,    // 10% chance of 19
-        18 => 0.1,    // 10% chance of 18
-        17 => 0.1,    // 10% chance of 17
-        _ => 0.0,
-    }
+pub fn calculate_compound_interest(principal: u64, rate_bps: u16, periods: u32) -> Result<u64> {
+    let growth = Q64::ONE.checked_add(Q64::from_bps(rate_bps as u64)?)?;
+    growth.checked_pow(periods)?.mul_u64(principal)
 }
 
 // VRF utility functions
@@ -86,9 +147,9 @@ pub fn generate_random_number(seed: u64, max: u64) -> u64 {
     x % max
 }
 
-pub fn generate_random_float(seed: u64) -> f64 {
+pub fn generate_random_fraction(seed: u64) -> Result<Q64> {
     let random_int = generate_random_number(seed, 1000000);
-    random_int as f64 / 1000000.0
+    Q64::from_ratio(random_int, 1000000)
 }
 
 // Validation functions
@@ -108,3 +169,83 @@ pub fn validate_fee_config(fee_bps: u16) -> Result<()> {
     require!(fee_bps <= 1000, crate::errors::CasinoError::InvalidHouseEdgeConfig); // Max 10% fee
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_div_rounds_down_through_a_u128_intermediate() {
+        assert_eq!(mul_div(10, 3, 4).unwrap(), 7);
+        assert_eq!(mul_div(7, 1, 8).unwrap(), 0);
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(mul_div(u64::MAX, 3, 4).unwrap(), 13_835_058_055_282_163_711);
+
+        assert!(mul_div(u64::MAX, 2, 1).is_err());
+        assert!(mul_div(1, 1, 0).is_err());
+    }
+
+    #[test]
+    fn mul_bps_rounds_down() {
+        assert_eq!(mul_bps(9_999, 1).unwrap(), 0);
+        assert_eq!(mul_bps(10_000, 1).unwrap(), 1);
+        assert_eq!(mul_bps(1_000_000, 250).unwrap(), 25_000);
+        assert_eq!(mul_bps(1_999, 5_000).unwrap(), 999);
+        assert_eq!(mul_bps(u64::MAX, BPS_DENOMINATOR).unwrap(), u64::MAX);
+        assert!(mul_bps(u64::MAX, BPS_DENOMINATOR + 1).is_err());
+    }
+
+    #[test]
+    fn q64_products_round_down() {
+        let third = Q64::from_ratio(1, 3).unwrap();
+        assert_eq!(third, Q64(6_148_914_691_236_517_205));
+        // Three thirds fall one unit short of one, and a scaled amount keeps the shortfall
+        assert_eq!(Q64::from_int(3).checked_mul(third).unwrap(), Q64(u64::MAX as u128));
+        assert_eq!(third.mul_u64(3).unwrap(), 0);
+        assert_eq!(third.mul_u64(300).unwrap(), 99);
+
+        assert_eq!(Q64(1).checked_mul(Q64(1)).unwrap(), Q64::ZERO);
+        assert_eq!(Q64::ONE.checked_mul(Q64::ONE).unwrap(), Q64::ONE);
+        assert_eq!(Q64::from_bps(5_000).unwrap().mul_u64(3).unwrap(), 1);
+        assert_eq!(Q64::from_bps(2_500).unwrap(), Q64(1 << 62));
+    }
+
+    #[test]
+    fn q64_products_overflow_at_the_integer_limit() {
+        let max_square_root = Q64::from_int(u32::MAX as u64);
+        assert_eq!(max_square_root.checked_mul(max_square_root).unwrap(), Q64::from_int(18_446_744_065_119_617_025));
+        assert!(Q64::from_int(1 << 32).checked_mul(Q64::from_int(1 << 32)).is_err());
+
+        assert_eq!(Q64(u128::MAX).checked_mul(Q64::ONE).unwrap(), Q64(u128::MAX));
+        assert!(Q64(u128::MAX).checked_mul(Q64::ONE.checked_add(Q64(1)).unwrap()).is_err());
+
+        assert_eq!(Q64::from_int(u64::MAX).mul_u64(1).unwrap(), u64::MAX);
+        assert!(Q64::from_int(2).mul_u64(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn q64_powers() {
+        let two = Q64::from_int(2);
+        assert_eq!(two.checked_pow(0).unwrap(), Q64::ONE);
+        assert_eq!(two.checked_pow(63).unwrap(), Q64::from_int(1 << 63));
+        assert!(two.checked_pow(64).is_err());
+
+        // Halving is exact down to the last fractional bit, then rounds to zero
+        let half = Q64::from_ratio(1, 2).unwrap();
+        assert_eq!(half.checked_pow(64).unwrap(), Q64(1));
+        assert_eq!(half.checked_pow(65).unwrap(), Q64::ZERO);
+    }
+
+    #[test]
+    fn compound_interest_golden_values() {
+        // 10% is not exact in Q64.64, so 1.1^2 lands just under 1.21
+        assert_eq!(calculate_compound_interest(1_000_000, 1_000, 2).unwrap(), 1_209_999);
+        assert_eq!(calculate_compound_interest(10_000, 500, 10).unwrap(), 16_288);
+        assert_eq!(calculate_compound_interest(1_000_000_000, 100, 365).unwrap(), 37_783_434_332);
+
+        assert_eq!(calculate_compound_interest(1_000_000, 0, 1_000).unwrap(), 1_000_000);
+        assert_eq!(calculate_compound_interest(1_000_000, 1_000, 0).unwrap(), 1_000_000);
+        assert!(calculate_compound_interest(u64::MAX, 10_000, 1).is_err());
+        assert!(calculate_compound_interest(1_000_000, 10_000, 64).is_err());
+    }
+}
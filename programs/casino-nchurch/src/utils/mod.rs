@@ -1,144 +1,87 @@
 use anchor_lang::prelude::*;
 use crate::state::casino::*;
 
+pub mod bankroll;
+pub mod history;
+pub mod jackpot;
+pub mod limits;
+pub mod liquidity;
 pub mod math;
+pub mod randomness;
+pub mod referral;
+pub mod roulette;
+pub mod slots;
+pub mod tournament;
+pub mod vip;
 pub mod vrf;
 
 pub use math::*;
 pub use vrf::*;
 
 // Utility functions for casino games
-pub fn calculate_slots_payout(reels: [u8; 3], bet_amount: u64) -> u64 {
-    let mut payout = 0u64;
-    
-    // Check each payline
-    for i in 0..3 {
-        let symbol = reels[i];
-        if symbol < 10 {
-            // Check for 3-of-a-kind
-            if reels[0] == symbol && reels[1] == symbol && reels[2] == symbol {
-                payout += bet_amount * SLOTS_PAYOUTS[symbol as usize][2] / 100;
-            }
-            // Check for 2-of-a-kind
-            else if (reels[0] == symbol && reels[1] == symbol) || 
-                    (reels[1] == symbol && reels[2] == symbol) ||
-                    (reels[0] == symbol && reels[2] == symbol) {
-                payout += bet_amount * SLOTS_PAYOUTS[symbol as usize][1] / 100;
-            }
-            // Check for single symbol
-            else if reels[0] == symbol || reels[1] == symbol || reels[2] == symbol {
-                payout += bet_amount * SLOTS_PAYOUTS[symbol as usize][0] / 100;
-            }
-        }
-    }
-    
-    payout
+/// Gross amount returned for a roulette bet (stake included). Pays only when the winning
+/// number is one of the numbers the bet covers on the board.
+pub fn calculate_roulette_payout(bet_type: RouletteBetType, numbers: &[u8], bet_amount: u64, winning_number: u8) -> Result<u64> {
+    let wager = roulette::replay_wager(&RouletteBet { bet_type, numbers: numbers.to_vec(), amount: bet_amount })?;
+    Ok(crate::replay::wager_payout(&wager, winning_number)?)
 }
 
-pub fn calculate_roulette_payout(bet_type: RouletteBetType, bet_amount: u64, winning_number: u8) -> u64 {
-    match bet_type {
-        RouletteBetType::Straight => {
-            // 35:1 payout
-            bet_amount * 35
-        },
-        RouletteBetType::Split => {
-            // 17:1 payout
-            bet_amount * 17
-        },
-        RouletteBetType::Street => {
-            // 11:1 payout
-            bet_amount * 11
-        },
-        RouletteBetType::Corner => {
-            // 8:1 payout
-            bet_amount * 8
-        },
-        RouletteBetType::Line => {
-            // 5:1 payout
-            bet_amount * 5
-        },
-        RouletteBetType::Column => {
-            // 2:1 payout
-            bet_amount * 2
-        },
-        RouletteBetType::Dozen => {
-            // 2:1 payout
-            bet_amount * 2
-        },
-        RouletteBetType::Red => {
-            if ROULETTE_RED_NUMBERS.contains(&winning_number) {
-                bet_amount
-            } else {
-                0
-            }
-        },
-        RouletteBetType::Black => {
-            if ROULETTE_BLACK_NUMBERS.contains(&winning_number) {
-                bet_amount
-            } else {
-                0
-            }
-        },
-        RouletteBetType::Even => {
-            if winning_number != 0 && winning_number % 2 == 0 {
-                bet_amount
-            } else {
-                0
-            }
-        },
-        RouletteBetType::Odd => {
-            if winning_number % 2 == 1 {
-                bet_amount
-            } else {
-                0
-            }
-        },
-        RouletteBetType::Low => {
-            if winning_number >= 1 && winning_number <= 18 {
-                bet_amount
-            } else {
-                0
-            }
-        },
-        RouletteBetType::High => {
-            if winning_number >= 19 && winning_number <= 36 {
-                bet_amount
-            } else {
-                0
-            }
-        },
-    }
+/// Gross payout for an auto-cashout target. Multipliers are fixed-point (AVIATOR_MULTIPLIER_SCALE = 1.00x)
+/// and the round pays when the target is at or below the crash point.
+pub fn calculate_aviator_payout(cashout_multiplier: u64, crash_multiplier: u64, bet_amount: u64) -> Result<u64> {
+    Ok(crate::replay::aviator_payout(cashout_multiplier, crash_multiplier, bet_amount)?)
 }
 
-pub fn calculate_aviator_payout(cashout_multiplier: f64, crash_multiplier: f64, bet_amount: u64) -> u64 {
-    if cashout_multiplier <= crash_multiplier {
-        // Player cashed out before crash
-        (bet_amount as f64 * cashout_multiplier) as u64
-    } else {
-        // Player didn't cash out in time
-        0
-    }
+pub use crate::replay::crash_multiplier as calculate_crash_multiplier;
+
+/// Most a blackjack round can return: every hand stake paid as a natural and insurance paid 2:1
+pub fn calculate_blackjack_max_payout(total_wagered: u64, insurance_bet: u64) -> Result<u64> {
+    let hand_stakes = total_wagered.saturating_sub(insurance_bet);
+    let max_hand_payout = checked_add(hand_stakes, mul_div(hand_stakes, BLACKJACK_PAYOUT, 100)?)?;
+    let max_insurance_payout = checked_add(insurance_bet, mul_div(insurance_bet, INSURANCE_PAYOUT, 100)?)?;
+    checked_add(max_hand_payout, max_insurance_payout)
 }
 
-pub fn calculate_blackjack_payout(player_hand: &Vec<u8>, dealer_hand: &Vec<u8>, bet_amount: u64) -> u64 {
+/// Gross amount returned for a resolved blackjack hand (stake included).
+/// Split hands that reach 21 in two cards are paid as a regular 21, not a blackjack.
+pub fn calculate_blackjack_payout(player_hand: &Vec<u8>, dealer_hand: &Vec<u8>, bet_amount: u64, is_split_hand: bool) -> Result<u64> {
     let player_value = calculate_hand_value(player_hand);
     let dealer_value = calculate_hand_value(dealer_hand);
+    let player_natural = !is_split_hand && is_blackjack(player_hand);
+    let dealer_natural = is_blackjack(dealer_hand);
     
-    if player_value > BLACKJACK_VALUE {
+    let payout = if player_value > BLACKJACK_VALUE {
         // Player busted
         0
-    } else if dealer_value > BLACKJACK_VALUE {
-        // Dealer busted, player wins
-        bet_amount
-    } else if player_value > dealer_value {
-        // Player wins
+    } else if player_natural && dealer_natural {
+        // Both blackjack, push
         bet_amount
+    } else if player_natural {
+        // Blackjack pays 3:2
+        checked_add(bet_amount, mul_div(bet_amount, BLACKJACK_PAYOUT, 100)?)?
+    } else if dealer_natural {
+        // Dealer blackjack beats any other 21
+        0
+    } else if dealer_value > BLACKJACK_VALUE || player_value > dealer_value {
+        // Dealer busted or player wins, pays 1:1
+        mul_div(bet_amount, 2, 1)?
     } else if player_value == dealer_value {
         // Push
         bet_amount
     } else {
         // Dealer wins
         0
+    };
+    
+    Ok(payout)
+}
+
+pub fn card_value(card: u8) -> u8 {
+    match card {
+        1 => 1,
+        2..=10 => card,
+        11..=13 => 10,
+        _ => 0,
     }
 }
 
@@ -167,11 +110,18 @@ pub fn calculate_hand_value(hand: &Vec<u8>) -> u8 {
     value
 }
 
+/// A hand is soft while one of its aces is still counted as 11
+pub fn is_soft_hand(hand: &[u8]) -> bool {
+    let hard_value: u8 = hand.iter().map(|card| card_value(*card)).sum();
+    hand.contains(&1) && hard_value + 10 <= BLACKJACK_VALUE
+}
+
 pub fn is_blackjack(hand: &Vec<u8>) -> bool {
     hand.len() == 2 && calculate_hand_value(hand) == BLACKJACK_VALUE
 }
 
-pub fn should_dealer_hit(dealer_hand: &Vec<u8>) -> bool {
+pub fn should_dealer_hit(dealer_hand: &Vec<u8>, hits_soft_17: bool) -> bool {
     let value = calculate_hand_value(dealer_hand);
     value < DEALER_STAND_VALUE
+        || (hits_soft_17 && value == DEALER_STAND_VALUE && is_soft_hand(dealer_hand))
 }
@@ -0,0 +1,171 @@
+// Randomness derivation from one revealed Switchboard value. The stream itself lives in the
+// replay core so off-chain verifiers derive exactly the same draws; the statistical checks on
+// it stay here.
+
+pub use crate::replay::{
+    accept_candidate, RandomnessStream, DOMAIN_AVIATOR_CRASH, DOMAIN_BLACKJACK_CARD, DOMAIN_ROULETTE_SPIN,
+    DOMAIN_SLOTS_JACKPOT, DOMAIN_SLOTS_REEL,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::*;
+    use anchor_lang::solana_program::hash::hashv;
+
+    // Chi-square upper critical values at p = 0.001, so a fair generator fails a
+    // single test with probability 1/1000; the inputs are fixed, so results are stable.
+    const CHI_SQUARE_P001_DF9: f64 = 27.877;
+    const CHI_SQUARE_P001_DF12: f64 = 32.909;
+    const CHI_SQUARE_P001_DF23: f64 = 49.728;
+    const CHI_SQUARE_P001_DF36: f64 = 67.985;
+    const CHI_SQUARE_P001_DF168: f64 = 230.0;
+
+    fn reveal(seed: u64) -> [u8; 32] {
+        hashv(&[b"test reveal", &seed.to_le_bytes()]).to_bytes()
+    }
+
+    fn game(seed: u64) -> [u8; 32] {
+        Pubkey::new_from_array(hashv(&[b"test game", &seed.to_le_bytes()]).to_bytes()).to_bytes()
+    }
+
+    fn chi_square(counts: &[u64]) -> f64 {
+        let total: u64 = counts.iter().sum();
+        let expected = total as f64 / counts.len() as f64;
+        counts
+            .iter()
+            .map(|count| {
+                let diff = *count as f64 - expected;
+                diff * diff / expected
+            })
+            .sum()
+    }
+
+    // Histogram of `per_reveal` sequential draws over `reveals` different reveals and games
+    fn histogram(domain: &[u8], upper: u64, reveals: u64, per_reveal: usize) -> Vec<u64> {
+        let mut counts = vec![0u64; upper as usize];
+        for seed in 0..reveals {
+            let randomness = reveal(seed);
+            let mut stream = RandomnessStream::new(domain, game(seed), &randomness);
+            for value in stream.draws(per_reveal, upper) {
+                counts[value as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn roulette_numbers_are_uniform() {
+        let counts = histogram(DOMAIN_ROULETTE_SPIN, 37, 37_000, 1);
+        assert!(chi_square(&counts) < CHI_SQUARE_P001_DF36, "{:?}", counts);
+    }
+
+    #[test]
+    fn card_ranks_are_uniform() {
+        let counts = histogram(DOMAIN_BLACKJACK_CARD, 13, 2_000, 13);
+        assert!(chi_square(&counts) < CHI_SQUARE_P001_DF12, "{:?}", counts);
+    }
+
+    #[test]
+    fn reel_draws_are_uniform() {
+        let counts = histogram(DOMAIN_SLOTS_REEL, 10, 4_000, 5);
+        assert!(chi_square(&counts) < CHI_SQUARE_P001_DF9, "{:?}", counts);
+    }
+
+    #[test]
+    fn consecutive_draws_are_independent() {
+        // Pairs of consecutive card draws should fill the 13 x 13 grid evenly
+        let mut counts = vec![0u64; 169];
+        for seed in 0..8_450 {
+            let randomness = reveal(seed);
+            let mut stream = RandomnessStream::new(DOMAIN_BLACKJACK_CARD, game(seed), &randomness);
+            for _ in 0..5 {
+                let first = stream.next_uniform(13);
+                let second = stream.next_uniform(13);
+                counts[(first * 13 + second) as usize] += 1;
+            }
+        }
+        assert!(chi_square(&counts) < CHI_SQUARE_P001_DF168, "{:?}", counts);
+    }
+
+    #[test]
+    fn shuffles_are_uniform_permutations() {
+        // Every one of the 4! orderings of a 4-card deck should be equally likely
+        let mut counts = vec![0u64; 24];
+        for seed in 0..24_000 {
+            let randomness = reveal(seed);
+            let mut stream = RandomnessStream::new(DOMAIN_BLACKJACK_CARD, game(seed), &randomness);
+            let deck = stream.shuffle(4);
+            let rank = deck.iter().enumerate().fold(0usize, |rank, (position, card)| {
+                let smaller_after = deck[position + 1..].iter().filter(|other| *other < card).count();
+                rank * (4 - position) + smaller_after
+            });
+            counts[rank] += 1;
+        }
+        assert!(chi_square(&counts) < CHI_SQUARE_P001_DF23, "{:?}", counts);
+    }
+
+    #[test]
+    fn full_deck_shuffle_is_a_permutation() {
+        let randomness = reveal(7);
+        let mut stream = RandomnessStream::new(DOMAIN_BLACKJACK_CARD, game(7), &randomness);
+        let mut deck = stream.shuffle(52);
+        deck.sort_unstable();
+        assert_eq!(deck, (0..52).collect::<Vec<u16>>());
+    }
+
+    #[test]
+    fn crash_draw_bits_are_balanced() {
+        // Each of the 52 bits used for the aviator crash point is set about half the time
+        let samples = 20_000u64;
+        let mut set_bits = [0u64; 52];
+        for seed in 0..samples {
+            let randomness = reveal(seed);
+            let stream = RandomnessStream::new(DOMAIN_AVIATOR_CRASH, game(seed), &randomness);
+            let value = stream.uniform(0, 1 << 52);
+            assert!(value < 1 << 52);
+            for (bit, count) in set_bits.iter_mut().enumerate() {
+                *count += (value >> bit) & 1;
+            }
+        }
+        for count in set_bits.iter() {
+            // 1/2 +- 4 standard deviations (sqrt(n) / 2 = ~71)
+            assert!(count.abs_diff(samples / 2) < 283, "{:?}", set_bits);
+        }
+    }
+
+    #[test]
+    fn rejection_removes_modulo_bias() {
+        // 2^64 = 1 (mod 3), so exactly one low candidate must be thrown away
+        assert_eq!(accept_candidate(0, 3), None);
+        assert_eq!(accept_candidate(1, 3), Some(1));
+        assert_eq!(accept_candidate(u64::MAX, 3), Some(u64::MAX % 3));
+
+        // Powers of two never reject
+        assert_eq!(accept_candidate(0, 1 << 52), Some(0));
+
+        // Near 2^63 almost half the candidates are rejected
+        let upper = (1u64 << 63) + 1;
+        assert_eq!(accept_candidate((1 << 63) - 2, upper), None);
+        assert_eq!(accept_candidate(u64::MAX, upper), Some(u64::MAX - upper));
+    }
+
+    #[test]
+    fn draws_are_domain_and_game_separated() {
+        let randomness = reveal(1);
+        let card = RandomnessStream::new(DOMAIN_BLACKJACK_CARD, game(1), &randomness);
+        let reel = RandomnessStream::new(DOMAIN_SLOTS_REEL, game(1), &randomness);
+        let other_game = RandomnessStream::new(DOMAIN_BLACKJACK_CARD, game(2), &randomness);
+
+        assert_ne!(card.expand(0, 0), reel.expand(0, 0));
+        assert_ne!(card.expand(0, 0), other_game.expand(0, 0));
+        assert_ne!(card.expand(0, 0), card.expand(1, 0));
+        assert_ne!(card.expand(0, 0), card.expand(0, 1));
+
+        // Sequential draws address the same positions as indexed draws
+        let mut stream = RandomnessStream::new(DOMAIN_BLACKJACK_CARD, game(1), &randomness);
+        let sequential = stream.draws(8, 13);
+        let indexed: Vec<u64> = (0..8).map(|index| card.uniform(index, 13)).collect();
+        assert_eq!(sequential, indexed);
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::errors::CasinoError;
+use crate::state::referral::*;
+use crate::utils::math::{mul_bps, mul_div, BPS_DENOMINATOR};
+
+// Referral revenue share.
+// Every upline level earns its `level_share_bps` of the house's net revenue on a settled game
+// (stakes minus payout, nothing when the player wins). The credit is carved out of that game's
+// platform fee: all levels together never take more than `max_fee_share_bps` of the fee, and
+// are scaled down pro rata to fit, so LP funds are never touched.
+
+/// Credit owed to each of the first `levels` upline levels for one settled game
+pub fn calculate_referral_shares(config: &ReferralConfig, levels: usize, wagered: u64, payout: u64, fee: u64) -> Result<[u64; MAX_REFERRAL_LEVELS]> {
+    let net_revenue = wagered.saturating_sub(payout);
+    let mut shares = [0u64; MAX_REFERRAL_LEVELS];
+    for (share, level_bps) in shares.iter_mut().zip(config.level_share_bps).take(levels) {
+        *share = mul_bps(net_revenue, level_bps as u64)?;
+    }
+
+    let total: u64 = shares.iter().sum();
+    let cap = mul_bps(fee, config.max_fee_share_bps as u64)?;
+    if total > cap {
+        for share in shares.iter_mut() {
+            *share = mul_div(*share, cap, total)?;
+        }
+    }
+    Ok(shares)
+}
+
+pub fn validate_referral_config(config: &ReferralConfig) -> Result<()> {
+    let total_bps: u64 = config.level_share_bps.iter().map(|bps| *bps as u64).sum();
+    require!(
+        total_bps <= BPS_DENOMINATOR && config.max_fee_share_bps as u64 <= BPS_DENOMINATOR,
+        CasinoError::InvalidReferralConfig
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(level_share_bps: [u16; 3], max_fee_share_bps: u16) -> ReferralConfig {
+        ReferralConfig { level_share_bps, max_fee_share_bps }
+    }
+
+    #[test]
+    fn shares_follow_net_revenue_and_upline_depth() {
+        let config = config([100, 50, 25], 10_000);
+
+        // House won 1_000, fee 200: 1%, 0.5%, 0.25% of the net revenue
+        assert_eq!(calculate_referral_shares(&config, 3, 1_000, 0, 200).unwrap(), [10, 5, 2]);
+        assert_eq!(calculate_referral_shares(&config, 1, 1_000, 0, 200).unwrap(), [10, 0, 0]);
+
+        // A winning player earns the upline nothing
+        assert_eq!(calculate_referral_shares(&config, 3, 1_000, 1_500, 200).unwrap(), [0, 0, 0]);
+    }
+
+    #[test]
+    fn shares_are_scaled_into_the_fee_cap() {
+        let config = config([2_000, 1_000, 1_000], 5_000);
+
+        // 400 + 200 + 200 wanted, but only half of the 200 fee may be shared
+        let shares = calculate_referral_shares(&config, 3, 2_000, 0, 200).unwrap();
+        assert_eq!(shares, [50, 25, 25]);
+        assert!(shares.iter().sum::<u64>() <= 100);
+    }
+}
@@ -0,0 +1,459 @@
+use anchor_lang::prelude::*;
+use crate::state::casino::*;
+use crate::errors::CasinoError;
+use crate::utils::math::{checked_add, mul_div};
+
+// European single-zero board model.
+// Numbers 1-36 sit in 12 rows of three (3r+1, 3r+2, 3r+3) with 0 above the first row,
+// touching 1, 2 and 3. Every bet is reduced to a bitmask of the numbers it covers
+// (bit n set = number n covered), and only a winning number inside that mask pays.
+// Bets pay true odds; the single zero alone carries the house edge (RTP 36/37).
+
+pub const ROULETTE_MAX_NUMBER: u8 = 36;
+pub const ROULETTE_RTP_BPS: u16 = 9_730;   // 36/37 to the nearest basis point
+
+const fn range_mask(first: u8, last: u8) -> u64 {
+    let mut mask = 0u64;
+    let mut number = first;
+    while number <= last {
+        mask |= 1 << number;
+        number += 1;
+    }
+    mask
+}
+
+const fn list_mask(numbers: &[u8]) -> u64 {
+    let mut mask = 0u64;
+    let mut i = 0;
+    while i < numbers.len() {
+        mask |= 1 << numbers[i];
+        i += 1;
+    }
+    mask
+}
+
+const fn parity_mask(remainder: u8) -> u64 {
+    let mut mask = 0u64;
+    let mut number = 1u8;
+    while number <= ROULETTE_MAX_NUMBER {
+        if number % 2 == remainder {
+            mask |= 1 << number;
+        }
+        number += 1;
+    }
+    mask
+}
+
+pub const RED_MASK: u64 = list_mask(&ROULETTE_RED_NUMBERS);
+pub const BLACK_MASK: u64 = list_mask(&ROULETTE_BLACK_NUMBERS);
+pub const EVEN_MASK: u64 = parity_mask(0);
+pub const ODD_MASK: u64 = parity_mask(1);
+pub const LOW_MASK: u64 = range_mask(1, 18);
+pub const HIGH_MASK: u64 = range_mask(19, 36);
+
+/// Net odds paid on a winning bet (35 = 35:1)
+pub fn roulette_payout_odds(bet_type: &RouletteBetType) -> u64 {
+    match bet_type {
+        RouletteBetType::Straight => 35,
+        RouletteBetType::Split => 17,
+        RouletteBetType::Street => 11,
+        RouletteBetType::Corner => 8,
+        RouletteBetType::Line => 5,
+        RouletteBetType::Column | RouletteBetType::Dozen => 2,
+        RouletteBetType::Red
+        | RouletteBetType::Black
+        | RouletteBetType::Even
+        | RouletteBetType::Odd
+        | RouletteBetType::Low
+        | RouletteBetType::High => 1,
+    }
+}
+
+/// Validate a bet's numbers against the table layout and return the numbers it covers.
+/// Inside bets may list their numbers in any order; outside bets must list none.
+pub fn covered_numbers(bet_type: &RouletteBetType, numbers: &[u8]) -> Result<u64> {
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    require!(sorted.len() == numbers.len(), CasinoError::InvalidRouletteNumbers);
+    require!(
+        sorted.iter().all(|number| *number <= ROULETTE_MAX_NUMBER),
+        CasinoError::InvalidRouletteNumbers
+    );
+
+    let is_valid = match bet_type {
+        RouletteBetType::Straight => sorted.len() == 1,
+        RouletteBetType::Split => is_split(&sorted),
+        RouletteBetType::Street => is_street(&sorted),
+        RouletteBetType::Corner => is_corner(&sorted),
+        RouletteBetType::Line => is_line(&sorted),
+        RouletteBetType::Column => is_column(&sorted),
+        RouletteBetType::Dozen => is_dozen(&sorted),
+        _ => sorted.is_empty(),
+    };
+    require!(is_valid, CasinoError::InvalidRouletteNumbers);
+
+    let mask = match bet_type {
+        RouletteBetType::Red => RED_MASK,
+        RouletteBetType::Black => BLACK_MASK,
+        RouletteBetType::Even => EVEN_MASK,
+        RouletteBetType::Odd => ODD_MASK,
+        RouletteBetType::Low => LOW_MASK,
+        RouletteBetType::High => HIGH_MASK,
+        _ => sorted.iter().fold(0u64, |mask, number| mask | 1 << number),
+    };
+
+    Ok(mask)
+}
+
+/// A bet as the replay core settles it: covered numbers, net odds and stake
+pub fn replay_wager(bet: &RouletteBet) -> Result<crate::replay::RouletteWager> {
+    Ok(crate::replay::RouletteWager {
+        covered: covered_numbers(&bet.bet_type, &bet.numbers)?,
+        net_odds: roulette_payout_odds(&bet.bet_type),
+        amount: bet.amount,
+    })
+}
+
+pub fn validate_roulette_bet(bet_type: &RouletteBetType, numbers: &[u8]) -> Result<()> {
+    covered_numbers(bet_type, numbers).map(|_| ())
+}
+
+/// Most a spin can return over every possible winning number (matches settlement)
+pub fn max_spin_payout(bets: &[RouletteBet]) -> Result<u64> {
+    let mut bet_payouts = Vec::with_capacity(bets.len());
+    for bet in bets {
+        let covered = covered_numbers(&bet.bet_type, &bet.numbers)?;
+        let gross = mul_div(bet.amount, roulette_payout_odds(&bet.bet_type) + 1, 1)?;
+        bet_payouts.push((covered, gross));
+    }
+
+    let mut max_payout = 0u64;
+    for number in 0..=ROULETTE_MAX_NUMBER {
+        let mut payout = 0u64;
+        for (covered, bet_payout) in bet_payouts.iter() {
+            if covered & (1 << number) != 0 {
+                payout = checked_add(payout, *bet_payout)?;
+            }
+        }
+        max_payout = max_payout.max(payout);
+    }
+
+    Ok(max_payout)
+}
+
+// Column of a non-zero number: 1, 2 or 3 (left to right)
+fn column_of(number: u8) -> u8 {
+    (number - 1) % 3 + 1
+}
+
+// Two numbers sharing an edge, including 0 with 1, 2 or 3
+fn is_split(sorted: &[u8]) -> bool {
+    match sorted {
+        [0, b] => (1..=3).contains(b),
+        [a, b] => *b == a + 3 || (*b == a + 1 && column_of(*a) != 3),
+        _ => false,
+    }
+}
+
+// A full row, or one of the two zero trios (0-1-2, 0-2-3)
+fn is_street(sorted: &[u8]) -> bool {
+    match sorted {
+        [0, 1, 2] | [0, 2, 3] => true,
+        [a, b, c] => *a != 0 && column_of(*a) == 1 && *b == a + 1 && *c == a + 2,
+        _ => false,
+    }
+}
+
+// Four numbers meeting at a corner, or the first four (0-1-2-3)
+fn is_corner(sorted: &[u8]) -> bool {
+    match sorted {
+        [0, 1, 2, 3] => true,
+        [a, b, c, d] => {
+            *a != 0 && column_of(*a) != 3 && *b == a + 1 && *c == a + 3 && *d == a + 4
+        },
+        _ => false,
+    }
+}
+
+// Two adjacent full rows
+fn is_line(sorted: &[u8]) -> bool {
+    sorted.len() == 6
+        && sorted[0] != 0
+        && column_of(sorted[0]) == 1
+        && sorted.windows(2).all(|pair| pair[1] == pair[0] + 1)
+}
+
+fn is_column(sorted: &[u8]) -> bool {
+    sorted.len() == 12
+        && (1..=3).contains(&sorted[0])
+        && sorted.windows(2).all(|pair| pair[1] == pair[0] + 3)
+}
+
+fn is_dozen(sorted: &[u8]) -> bool {
+    sorted.len() == 12
+        && matches!(sorted[0], 1 | 13 | 25)
+        && sorted.windows(2).all(|pair| pair[1] == pair[0] + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INSIDE_BETS: [RouletteBetType; 7] = [
+        RouletteBetType::Straight,
+        RouletteBetType::Split,
+        RouletteBetType::Street,
+        RouletteBetType::Corner,
+        RouletteBetType::Line,
+        RouletteBetType::Column,
+        RouletteBetType::Dozen,
+    ];
+
+    const OUTSIDE_BETS: [RouletteBetType; 6] = [
+        RouletteBetType::Red,
+        RouletteBetType::Black,
+        RouletteBetType::Even,
+        RouletteBetType::Odd,
+        RouletteBetType::Low,
+        RouletteBetType::High,
+    ];
+
+    // Independent geometry: (row, column) for 1-36
+    fn position(number: u8) -> (i32, i32) {
+        (((number - 1) / 3) as i32, ((number - 1) % 3) as i32)
+    }
+
+    fn touches_zero(number: u8) -> bool {
+        (1..=3).contains(&number)
+    }
+
+    fn combinations(size: usize) -> Vec<Vec<u8>> {
+        fn extend(start: u8, size: usize, current: &mut Vec<u8>, out: &mut Vec<Vec<u8>>) {
+            if current.len() == size {
+                out.push(current.clone());
+                return;
+            }
+            for number in start..=ROULETTE_MAX_NUMBER {
+                current.push(number);
+                extend(number + 1, size, current, out);
+                current.pop();
+            }
+        }
+        let mut out = Vec::new();
+        extend(0, size, &mut Vec::new(), &mut out);
+        out
+    }
+
+    fn valid_sets(bet_type: &RouletteBetType, size: usize) -> Vec<Vec<u8>> {
+        combinations(size)
+            .into_iter()
+            .filter(|set| covered_numbers(bet_type, set).is_ok())
+            .collect()
+    }
+
+    fn expected_split(set: &[u8]) -> bool {
+        match set {
+            [0, b] => touches_zero(*b),
+            [a, b] => {
+                let ((ra, ca), (rb, cb)) = (position(*a), position(*b));
+                (ra - rb).abs() + (ca - cb).abs() == 1
+            },
+            _ => false,
+        }
+    }
+
+    fn expected_street(set: &[u8]) -> bool {
+        if set[0] == 0 {
+            return set == [0, 1, 2] || set == [0, 2, 3];
+        }
+        set.iter().all(|number| position(*number).0 == position(set[0]).0)
+    }
+
+    fn expected_corner(set: &[u8]) -> bool {
+        if set[0] == 0 {
+            return set == [0, 1, 2, 3];
+        }
+        let rows: Vec<i32> = set.iter().map(|number| position(*number).0).collect();
+        let columns: Vec<i32> = set.iter().map(|number| position(*number).1).collect();
+        let (min_row, max_row) = (*rows.iter().min().unwrap(), *rows.iter().max().unwrap());
+        let (min_col, max_col) = (*columns.iter().min().unwrap(), *columns.iter().max().unwrap());
+        max_row - min_row == 1 && max_col - min_col == 1
+    }
+
+    #[test]
+    fn straight_covers_every_single_number() {
+        for number in 0..=ROULETTE_MAX_NUMBER {
+            assert_eq!(covered_numbers(&RouletteBetType::Straight, &[number]).unwrap(), 1 << number);
+        }
+        assert!(covered_numbers(&RouletteBetType::Straight, &[37]).is_err());
+        assert!(covered_numbers(&RouletteBetType::Straight, &[]).is_err());
+        assert!(covered_numbers(&RouletteBetType::Straight, &[1, 2]).is_err());
+    }
+
+    #[test]
+    fn splits_match_board_adjacency() {
+        let valid = valid_sets(&RouletteBetType::Split, 2);
+        for set in combinations(2) {
+            assert_eq!(
+                covered_numbers(&RouletteBetType::Split, &set).is_ok(),
+                expected_split(&set),
+                "split {:?}",
+                set
+            );
+        }
+        // 24 horizontal + 33 vertical + 3 zero splits
+        assert_eq!(valid.len(), 60);
+        assert!(valid.contains(&vec![0, 2]));
+        assert!(!valid.contains(&vec![3, 4]));
+    }
+
+    #[test]
+    fn streets_match_board_rows() {
+        let valid = valid_sets(&RouletteBetType::Street, 3);
+        for set in combinations(3) {
+            assert_eq!(
+                covered_numbers(&RouletteBetType::Street, &set).is_ok(),
+                expected_street(&set),
+                "street {:?}",
+                set
+            );
+        }
+        // 12 rows + two zero trios
+        assert_eq!(valid.len(), 14);
+        assert!(!valid.contains(&vec![0, 1, 3]));
+        assert!(!valid.contains(&vec![2, 3, 4]));
+    }
+
+    #[test]
+    fn corners_match_board_squares() {
+        let valid = valid_sets(&RouletteBetType::Corner, 4);
+        for set in combinations(4) {
+            assert_eq!(
+                covered_numbers(&RouletteBetType::Corner, &set).is_ok(),
+                expected_corner(&set),
+                "corner {:?}",
+                set
+            );
+        }
+        // 11 row pairs x 2 column pairs + first four
+        assert_eq!(valid.len(), 23);
+        assert!(!valid.contains(&vec![3, 4, 6, 7]));
+    }
+
+    #[test]
+    fn lines_are_two_adjacent_rows() {
+        let mut valid = 0;
+        for first in 0..=31u8 {
+            let set: Vec<u8> = (first..first + 6).collect();
+            let is_valid = covered_numbers(&RouletteBetType::Line, &set).is_ok();
+            assert_eq!(is_valid, first != 0 && first % 3 == 1, "line {:?}", set);
+            valid += is_valid as usize;
+        }
+        assert_eq!(valid, 11);
+        assert!(covered_numbers(&RouletteBetType::Line, &[1, 2, 3, 4, 5, 7]).is_err());
+        assert!(covered_numbers(&RouletteBetType::Line, &[6, 5, 4, 3, 2, 1]).is_ok());
+    }
+
+    #[test]
+    fn columns_and_dozens_cover_twelve_numbers() {
+        for column in 1..=3u8 {
+            let set: Vec<u8> = (0..12).map(|row| row * 3 + column).collect();
+            assert!(covered_numbers(&RouletteBetType::Column, &set).is_ok());
+            assert!(covered_numbers(&RouletteBetType::Dozen, &set).is_err());
+
+            let mut broken = set.clone();
+            broken[11] = 0;
+            assert!(covered_numbers(&RouletteBetType::Column, &broken).is_err());
+        }
+        for dozen in 0..3u8 {
+            let set: Vec<u8> = (dozen * 12 + 1..=dozen * 12 + 12).collect();
+            assert!(covered_numbers(&RouletteBetType::Dozen, &set).is_ok());
+            assert!(covered_numbers(&RouletteBetType::Column, &set).is_err());
+        }
+        let shifted: Vec<u8> = (2..=13).collect();
+        assert!(covered_numbers(&RouletteBetType::Dozen, &shifted).is_err());
+    }
+
+    #[test]
+    fn outside_bets_cover_eighteen_numbers_and_never_zero() {
+        for bet_type in OUTSIDE_BETS.iter() {
+            let mask = covered_numbers(bet_type, &[]).unwrap();
+            assert_eq!(mask.count_ones(), 18);
+            assert_eq!(mask & 1, 0);
+            assert!(covered_numbers(bet_type, &[1]).is_err());
+        }
+        assert_eq!(RED_MASK & BLACK_MASK, 0);
+        assert_eq!(RED_MASK | BLACK_MASK, range_mask(1, 36));
+        assert_eq!(EVEN_MASK | ODD_MASK, range_mask(1, 36));
+        assert_eq!(LOW_MASK | HIGH_MASK, range_mask(1, 36));
+    }
+
+    #[test]
+    fn duplicate_numbers_are_rejected() {
+        assert!(covered_numbers(&RouletteBetType::Split, &[5, 5]).is_err());
+        assert!(covered_numbers(&RouletteBetType::Corner, &[1, 2, 4, 4]).is_err());
+    }
+
+    #[test]
+    fn max_spin_payout_matches_the_worst_winning_number() {
+        let bets = vec![
+            RouletteBet { bet_type: RouletteBetType::Straight, numbers: vec![17], amount: 1_000 },
+            RouletteBet { bet_type: RouletteBetType::Corner, numbers: vec![14, 17, 13, 16], amount: 2_000 },
+            RouletteBet { bet_type: RouletteBetType::Red, numbers: vec![], amount: 5_000 },
+            RouletteBet { bet_type: RouletteBetType::Black, numbers: vec![], amount: 5_000 },
+        ];
+        let mut worst = 0u64;
+        for winning_number in 0..=ROULETTE_MAX_NUMBER {
+            let mut payout = 0u64;
+            for bet in bets.iter() {
+                let gross = crate::utils::calculate_roulette_payout(bet.bet_type.clone(), &bet.numbers, bet.amount, winning_number).unwrap();
+                payout += gross;
+            }
+            worst = worst.max(payout);
+        }
+
+        // 17 is black and covered by the straight and the corner
+        assert_eq!(worst, 36_000 + 18_000 + 10_000);
+        assert_eq!(max_spin_payout(&bets).unwrap(), worst);
+    }
+
+    #[test]
+    fn every_valid_bet_pays_only_on_covered_numbers() {
+        let bet_amount = 1_000u64;
+        let mut all_bets: Vec<(RouletteBetType, Vec<u8>)> = Vec::new();
+        for (bet_type, size) in INSIDE_BETS.iter().zip([1usize, 2, 3, 4]) {
+            for set in valid_sets(bet_type, size) {
+                all_bets.push((bet_type.clone(), set));
+            }
+        }
+        for first in [1u8, 4, 7, 10, 13, 16, 19, 22, 25, 28, 31] {
+            all_bets.push((RouletteBetType::Line, (first..first + 6).collect()));
+        }
+        for column in 1..=3u8 {
+            all_bets.push((RouletteBetType::Column, (0..12).map(|row| row * 3 + column).collect()));
+        }
+        for dozen in 0..3u8 {
+            all_bets.push((RouletteBetType::Dozen, (dozen * 12 + 1..=dozen * 12 + 12).collect()));
+        }
+        for bet_type in OUTSIDE_BETS.iter() {
+            all_bets.push((bet_type.clone(), Vec::new()));
+        }
+
+        for (bet_type, numbers) in all_bets.iter() {
+            let mask = covered_numbers(bet_type, numbers).unwrap();
+            let mut total_returned = 0u64;
+            for winning_number in 0..=ROULETTE_MAX_NUMBER {
+                let payout = crate::utils::calculate_roulette_payout(bet_type.clone(), numbers, bet_amount, winning_number).unwrap();
+                if mask & (1 << winning_number) != 0 {
+                    assert_eq!(payout, bet_amount * (roulette_payout_odds(bet_type) + 1));
+                } else {
+                    assert_eq!(payout, 0, "{:?} {:?} paid on {}", bet_type, numbers, winning_number);
+                }
+                total_returned += payout;
+            }
+            // Every European bet returns 36 units over the 37 pockets
+            assert_eq!(total_returned, bet_amount * 36, "{:?} {:?}", bet_type, numbers);
+        }
+    }
+}
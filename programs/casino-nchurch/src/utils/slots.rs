@@ -0,0 +1,364 @@
+use anchor_lang::prelude::*;
+use crate::state::casino::*;
+use crate::errors::CasinoError;
+use crate::utils::math::{mul_div, BPS_DENOMINATOR};
+
+// Slots engine driven by a SlotsMachineConfig.
+// Each reel lands on one weighted stop and shows it plus the next SLOTS_ROWS - 1 stops,
+// wrapping around the strip. A payline reads one row per reel and pays the best paytable
+// entry for the run of its leftmost symbol. The theoretical RTP is computed exactly from
+// the strip weights, one payline at a time, with integer arithmetic only.
+
+type RowSymbolWeights = [[u64; SLOTS_MAX_SYMBOLS as usize]; SLOTS_ROWS as usize];
+
+pub fn validate_machine_config(reel_strips: &[ReelStrip], paylines: &[Payline], paytable: &[SlotsPayout]) -> Result<()> {
+    let reel_count = reel_strips.len();
+    require!(reel_count == 3 || reel_count == SLOTS_MAX_REELS, CasinoError::InvalidSlotsConfig);
+
+    for strip in reel_strips {
+        require!(
+            strip.stops.len() >= SLOTS_ROWS as usize && strip.stops.len() <= SLOTS_MAX_STOPS,
+            CasinoError::InvalidSlotsConfig
+        );
+        require!(
+            strip.stops.iter().all(|stop| stop.symbol < SLOTS_MAX_SYMBOLS && stop.weight > 0),
+            CasinoError::InvalidSlotsConfig
+        );
+        // Keeps the product of all reel weights inside u128 for the RTP calculation
+        require!(strip_weight(strip) <= u16::MAX as u64, CasinoError::InvalidSlotsConfig);
+    }
+
+    require!(
+        !paylines.is_empty() && paylines.len() <= SLOTS_MAX_PAYLINES,
+        CasinoError::InvalidSlotsPayline
+    );
+    for payline in paylines {
+        require!(
+            payline.rows.len() == reel_count && payline.rows.iter().all(|row| *row < SLOTS_ROWS),
+            CasinoError::InvalidSlotsPayline
+        );
+    }
+
+    require!(
+        !paytable.is_empty() && paytable.len() <= SLOTS_MAX_PAYTABLE,
+        CasinoError::InvalidSlotsConfig
+    );
+    for (index, entry) in paytable.iter().enumerate() {
+        require!(entry.symbol < SLOTS_MAX_SYMBOLS, CasinoError::InvalidSlotsConfig);
+        require!(
+            entry.count >= 1 && entry.count as usize <= reel_count,
+            CasinoError::InvalidSlotsConfig
+        );
+        require!(
+            entry.multiplier > 0 && entry.multiplier <= SLOTS_MAX_MULTIPLIER,
+            CasinoError::InvalidSlotsConfig
+        );
+        require!(
+            paytable[..index].iter().all(|other| other.symbol != entry.symbol || other.count != entry.count),
+            CasinoError::InvalidSlotsConfig
+        );
+    }
+
+    Ok(())
+}
+
+/// Validate a payline selection (bit i = paylines[i]) and return the number of lines played
+pub fn validate_active_paylines(payline_count: usize, active_paylines: u32) -> Result<u64> {
+    require!(active_paylines != 0, CasinoError::InvalidSlotsPayline);
+    require!(
+        payline_count >= 32 || active_paylines >> payline_count == 0,
+        CasinoError::InvalidSlotsPayline
+    );
+    Ok(active_paylines.count_ones() as u64)
+}
+
+pub fn strip_weight(strip: &ReelStrip) -> u64 {
+    strip.stops.iter().map(|stop| stop.weight as u64).sum()
+}
+
+// Total weight of the stops that put each symbol on each visible row of a reel
+fn row_symbol_weights(strip: &ReelStrip) -> RowSymbolWeights {
+    let mut weights = [[0u64; SLOTS_MAX_SYMBOLS as usize]; SLOTS_ROWS as usize];
+    let len = strip.stops.len();
+
+    for (position, stop) in strip.stops.iter().enumerate() {
+        for (row, row_weights) in weights.iter_mut().enumerate() {
+            let symbol = strip.stops[(position + row) % len].symbol;
+            row_weights[symbol as usize] += stop.weight as u64;
+        }
+    }
+
+    weights
+}
+
+fn checked_mul(a: u128, b: u128) -> Result<u128> {
+    a.checked_mul(b).ok_or_else(|| error!(CasinoError::MathOverflow))
+}
+
+/// Exact theoretical RTP of every payline in basis points (rounded down).
+/// Reels are independent, so the weight of a run of exactly `n` symbols is the product of the
+/// matching row weights on the first `n` reels, the non-matching weight on reel `n`, and the
+/// total weight of every reel after it.
+pub fn calculate_payline_rtps(reel_strips: &[ReelStrip], paylines: &[Payline], paytable: &[SlotsPayout]) -> Result<Vec<u64>> {
+    let reel_count = reel_strips.len();
+    let weights: Vec<RowSymbolWeights> = reel_strips.iter().map(row_symbol_weights).collect();
+
+    // trailing_weight[i] = product of the total weights of reels i..
+    let mut trailing_weight = vec![1u128; reel_count + 1];
+    for reel in (0..reel_count).rev() {
+        trailing_weight[reel] = checked_mul(trailing_weight[reel + 1], strip_weight(&reel_strips[reel]) as u128)?;
+    }
+
+    let line_pays = replay_machine(&[], &[], paytable).paytable;
+    let mut symbols: Vec<u8> = paytable.iter().map(|entry| entry.symbol).collect();
+    symbols.sort_unstable();
+    symbols.dedup();
+
+    let mut rtps = Vec::with_capacity(paylines.len());
+    for payline in paylines {
+        let row_weight = |reel: usize, symbol: u8| -> u128 {
+            weights[reel][payline.rows[reel] as usize][symbol as usize] as u128
+        };
+
+        let mut weighted_multiplier = 0u128;
+        for symbol in symbols.iter() {
+            let mut run_weight = 1u128;
+            for run in 1..=reel_count {
+                run_weight = checked_mul(run_weight, row_weight(run - 1, *symbol))?;
+                if run_weight == 0 {
+                    break;
+                }
+
+                let multiplier = crate::replay::line_multiplier(&line_pays, *symbol, run);
+                if multiplier == 0 {
+                    continue;
+                }
+
+                let exact_run_weight = if run < reel_count {
+                    let total = strip_weight(&reel_strips[run]) as u128;
+                    let miss_weight = total - row_weight(run, *symbol);
+                    checked_mul(checked_mul(run_weight, miss_weight)?, trailing_weight[run + 1])?
+                } else {
+                    run_weight
+                };
+
+                weighted_multiplier = weighted_multiplier
+                    .checked_add(checked_mul(exact_run_weight, multiplier as u128)?)
+                    .ok_or(CasinoError::MathOverflow)?;
+            }
+        }
+
+        let rtp_bps = checked_mul(weighted_multiplier, BPS_DENOMINATOR as u128)?
+            / checked_mul(trailing_weight[0], SLOTS_MULTIPLIER_SCALE as u128)?;
+        rtps.push(u64::try_from(rtp_bps).map_err(|_| error!(CasinoError::MathOverflow))?);
+    }
+
+    Ok(rtps)
+}
+
+/// The machine as the replay core sees it (see replay::slots_spin)
+pub fn replay_machine(reel_strips: &[ReelStrip], paylines: &[Payline], paytable: &[SlotsPayout]) -> crate::replay::SlotsMachine {
+    crate::replay::SlotsMachine {
+        reels: reel_strips
+            .iter()
+            .map(|strip| strip.stops.iter().map(|stop| crate::replay::Stop { symbol: stop.symbol, weight: stop.weight }).collect())
+            .collect(),
+        paylines: paylines.iter().map(|payline| payline.rows.clone()).collect(),
+        paytable: paytable
+            .iter()
+            .map(|entry| crate::replay::LinePay { symbol: entry.symbol, count: entry.count, multiplier: entry.multiplier })
+            .collect(),
+    }
+}
+
+/// Most a spin can return: every played line hitting the top paytable entry
+pub fn calculate_slots_max_payout(paytable: &[SlotsPayout], line_bet: u64, line_count: u64) -> Result<u64> {
+    let max_multiplier = paytable.iter().map(|entry| entry.multiplier).max().unwrap_or(0);
+    let max_line_payout = mul_div(line_bet, max_multiplier, SLOTS_MULTIPLIER_SCALE)?;
+    max_line_payout.checked_mul(line_count).ok_or_else(|| error!(CasinoError::MathOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{select_stop, slots_payout, spin_window};
+
+    fn strip(stops: &[(u8, u16)]) -> ReelStrip {
+        ReelStrip {
+            stops: stops.iter().map(|(symbol, weight)| ReelStop { symbol: *symbol, weight: *weight }).collect(),
+        }
+    }
+
+    fn payout(symbol: u8, count: u8, multiplier: u64) -> SlotsPayout {
+        SlotsPayout { symbol, count, multiplier }
+    }
+
+    fn line(rows: &[u8]) -> Payline {
+        Payline { rows: rows.to_vec() }
+    }
+
+    // Enumerate every stop combination and compare the weighted payout with the closed form
+    fn brute_force_rtps(reel_strips: &[ReelStrip], paylines: &[Payline], paytable: &[SlotsPayout]) -> Vec<u64> {
+        let machine = replay_machine(reel_strips, paylines, paytable);
+        let mut positions = vec![0usize; reel_strips.len()];
+        let mut weighted = vec![0u128; paylines.len()];
+        let total_weight: u128 = reel_strips.iter().map(|strip| strip_weight(strip) as u128).product();
+
+        loop {
+            let window = spin_window(&machine.reels, &positions);
+            let weight: u128 = reel_strips
+                .iter()
+                .zip(positions.iter())
+                .map(|(strip, position)| strip.stops[*position].weight as u128)
+                .product();
+            for (index, line_total) in weighted.iter_mut().enumerate() {
+                // A line bet of one scale unit pays exactly the multiplier
+                let line_pay = slots_payout(&machine, &window, 1 << index, SLOTS_MULTIPLIER_SCALE).unwrap();
+                *line_total += weight * line_pay as u128;
+            }
+
+            let mut reel = 0;
+            loop {
+                if reel == positions.len() {
+                    return weighted
+                        .iter()
+                        .map(|total| (total * BPS_DENOMINATOR as u128 / (total_weight * SLOTS_MULTIPLIER_SCALE as u128)) as u64)
+                        .collect();
+                }
+                positions[reel] += 1;
+                if positions[reel] < reel_strips[reel].stops.len() {
+                    break;
+                }
+                positions[reel] = 0;
+                reel += 1;
+            }
+        }
+    }
+
+    fn three_reel_machine() -> (Vec<ReelStrip>, Vec<Payline>, Vec<SlotsPayout>) {
+        let reel_strips = vec![
+            strip(&[(0, 5), (1, 3), (2, 2), (3, 1), (0, 4)]),
+            strip(&[(1, 2), (0, 6), (3, 1), (2, 3)]),
+            strip(&[(2, 1), (1, 2), (0, 7), (3, 1), (1, 1), (0, 2)]),
+        ];
+        let paylines = vec![line(&[1, 1, 1]), line(&[0, 0, 0]), line(&[2, 2, 2]), line(&[0, 1, 2]), line(&[2, 1, 0])];
+        let paytable = vec![
+            payout(0, 3, 300),
+            payout(1, 2, 150),
+            payout(1, 3, 800),
+            payout(2, 3, 2_000),
+            payout(3, 1, 50),
+            payout(3, 3, 10_000),
+        ];
+        (reel_strips, paylines, paytable)
+    }
+
+    #[test]
+    fn three_reel_rtp_matches_enumeration() {
+        let (reel_strips, paylines, paytable) = three_reel_machine();
+        validate_machine_config(&reel_strips, &paylines, &paytable).unwrap();
+
+        let rtps = calculate_payline_rtps(&reel_strips, &paylines, &paytable).unwrap();
+        assert_eq!(rtps, brute_force_rtps(&reel_strips, &paylines, &paytable));
+        assert!(rtps.iter().all(|rtp| *rtp > 0));
+    }
+
+    #[test]
+    fn five_reel_rtp_matches_enumeration() {
+        let reel_strips = vec![
+            strip(&[(0, 3), (1, 2), (2, 1), (0, 1)]),
+            strip(&[(1, 1), (0, 4), (2, 2)]),
+            strip(&[(2, 2), (0, 3), (1, 1), (1, 2)]),
+            strip(&[(0, 2), (2, 1), (1, 3)]),
+            strip(&[(1, 1), (2, 1), (0, 5), (2, 2)]),
+        ];
+        let paylines = vec![line(&[1, 1, 1, 1, 1]), line(&[0, 1, 2, 1, 0]), line(&[2, 2, 1, 0, 0])];
+        let paytable = vec![
+            payout(0, 3, 200),
+            payout(0, 4, 500),
+            payout(0, 5, 2_500),
+            payout(1, 3, 400),
+            payout(1, 5, 5_000),
+            payout(2, 2, 100),
+            payout(2, 4, 1_500),
+        ];
+        validate_machine_config(&reel_strips, &paylines, &paytable).unwrap();
+
+        let rtps = calculate_payline_rtps(&reel_strips, &paylines, &paytable).unwrap();
+        assert_eq!(rtps, brute_force_rtps(&reel_strips, &paylines, &paytable));
+    }
+
+    #[test]
+    fn runs_pay_best_entry_from_the_left_only() {
+        let paylines = vec![line(&[0, 0, 0])];
+        let paytable = vec![payout(1, 2, 150), payout(1, 3, 800)];
+
+        // Window is reel-major: [reel0 row0..2, reel1 row0..2, reel2 row0..2]
+        let full_run = [1, 0, 0, 1, 0, 0, 1, 0, 0];
+        let two_run = [1, 0, 0, 1, 0, 0, 2, 0, 0];
+        let broken_run = [2, 0, 0, 1, 0, 0, 1, 0, 0];
+
+        let machine = replay_machine(&[], &paylines, &paytable);
+        assert_eq!(slots_payout(&machine, &full_run, 1, 1_000).unwrap(), 8_000);
+        assert_eq!(slots_payout(&machine, &two_run, 1, 1_000).unwrap(), 1_500);
+        assert_eq!(slots_payout(&machine, &broken_run, 1, 1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn only_selected_paylines_pay() {
+        let (reel_strips, paylines, paytable) = three_reel_machine();
+        let machine = replay_machine(&reel_strips, &paylines, &paytable);
+        // Symbol 0 across the middle row only
+        let window = spin_window(&machine.reels, &[4, 0, 1]);
+        assert_eq!(&window[..], &[0, 0, 1, 1, 0, 3, 1, 0, 3]);
+
+        assert_eq!(slots_payout(&machine, &window, 0b00001, 1_000).unwrap(), 3_000);
+        assert_eq!(slots_payout(&machine, &window, 0b11110, 1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn weighted_draws_map_to_stops() {
+        let reel = strip(&[(0, 5), (1, 3), (2, 2)]);
+        let machine = replay_machine(std::slice::from_ref(&reel), &[], &[]);
+        let stops: Vec<usize> = (0..strip_weight(&reel)).map(|draw| select_stop(&machine.reels[0], draw)).collect();
+        assert_eq!(stops, vec![0, 0, 0, 0, 0, 1, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn payline_selection_is_validated() {
+        assert_eq!(validate_active_paylines(5, 0b10101).unwrap(), 3);
+        assert!(validate_active_paylines(5, 0).is_err());
+        assert!(validate_active_paylines(5, 0b100000).is_err());
+        assert_eq!(validate_active_paylines(20, (1 << 20) - 1).unwrap(), 20);
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        let (reel_strips, paylines, paytable) = three_reel_machine();
+
+        assert!(validate_machine_config(&reel_strips[..2], &paylines, &paytable).is_err());
+
+        let mut short_strip = reel_strips.clone();
+        short_strip[0] = strip(&[(0, 1), (1, 1)]);
+        assert!(validate_machine_config(&short_strip, &paylines, &paytable).is_err());
+
+        let mut zero_weight = reel_strips.clone();
+        zero_weight[1].stops[0].weight = 0;
+        assert!(validate_machine_config(&zero_weight, &paylines, &paytable).is_err());
+
+        let mut heavy = reel_strips.clone();
+        heavy[2].stops[0].weight = u16::MAX;
+        assert!(validate_machine_config(&heavy, &paylines, &paytable).is_err());
+
+        assert!(validate_machine_config(&reel_strips, &[line(&[0, 1])], &paytable).is_err());
+        assert!(validate_machine_config(&reel_strips, &[line(&[0, 3, 1])], &paytable).is_err());
+        assert!(validate_machine_config(&reel_strips, &[], &paytable).is_err());
+
+        let mut duplicate = paytable.clone();
+        duplicate.push(payout(0, 3, 100));
+        assert!(validate_machine_config(&reel_strips, &paylines, &duplicate).is_err());
+
+        assert!(validate_machine_config(&reel_strips, &paylines, &[payout(0, 4, 100)]).is_err());
+        assert!(validate_machine_config(&reel_strips, &paylines, &[payout(16, 3, 100)]).is_err());
+    }
+}
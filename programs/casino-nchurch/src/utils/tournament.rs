@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use crate::errors::CasinoError;
+use crate::state::tournament::*;
+use crate::utils::math::{mul_div, BPS_DENOMINATOR};
+
+// Tournaments.
+// Every eligible game a registered player settles between start and end time updates their
+// score under the tournament's metric, and the top `leaderboard_size` scores are kept sorted
+// in the Tournament account. Players who drop off a full board re-enter on their next scored
+// game. At finalization the prize pool is split along the payout curve; when fewer players
+// scored than the curve has places, the filled places share the whole pool pro rata.
+
+/// Score after one more settled game
+pub fn score_game(metric: TournamentMetric, score: i64, wagered: u64, payout: u64) -> Result<i64> {
+    let score = match metric {
+        TournamentMetric::NetProfit => score.saturating_add(payout as i64).saturating_sub(wagered as i64),
+        TournamentMetric::BiggestMultiplier => {
+            // Free spins have no stake to measure against
+            if wagered == 0 {
+                return Ok(score);
+            }
+            score.max(mul_div(payout, TOURNAMENT_MULTIPLIER_SCALE, wagered)? as i64)
+        },
+        TournamentMetric::WageredVolume => score.saturating_add(wagered as i64),
+    };
+    Ok(score)
+}
+
+/// Move the player to their new score's place on the board, keeping it sorted and at most `size` long
+pub fn update_leaderboard(leaderboard: &mut Vec<LeaderboardEntry>, size: usize, user: Pubkey, score: i64) {
+    leaderboard.retain(|entry| entry.user != user);
+    let position = leaderboard.partition_point(|entry| entry.score >= score);
+    if position < size {
+        leaderboard.insert(position, LeaderboardEntry { user, score });
+        leaderboard.truncate(size);
+    }
+}
+
+/// Tournament a settle has to score for: the player's registration until it ends
+pub fn running_tournament(active_tournament: Option<Pubkey>, tournament_ends_at: i64, now: i64) -> Option<Pubkey> {
+    active_tournament.filter(|_| now < tournament_ends_at)
+}
+
+/// Prize for each of the first `winners` places (rounding dust goes to first place)
+pub fn calculate_prizes(prize_pool: u64, payout_curve_bps: &[u16], winners: usize) -> Result<Vec<u64>> {
+    let curve = &payout_curve_bps[..winners.min(payout_curve_bps.len())];
+    let total_bps: u64 = curve.iter().map(|bps| *bps as u64).sum();
+    if total_bps == 0 {
+        return Ok(vec![0; curve.len()]);
+    }
+
+    let mut prizes = curve
+        .iter()
+        .map(|bps| mul_div(prize_pool, *bps as u64, total_bps))
+        .collect::<Result<Vec<u64>>>()?;
+    let paid: u64 = prizes.iter().sum();
+    prizes[0] += prize_pool - paid;
+    Ok(prizes)
+}
+
+pub fn validate_tournament_config(config: &TournamentConfig) -> Result<()> {
+    require!(config.start_time < config.end_time, CasinoError::InvalidTournamentConfig);
+    require!(
+        !config.eligible_games.is_empty() && config.eligible_games.len() <= MAX_TOURNAMENT_GAMES,
+        CasinoError::InvalidTournamentConfig
+    );
+    require!(
+        config.leaderboard_size > 0 && config.leaderboard_size as usize <= MAX_LEADERBOARD_SIZE,
+        CasinoError::InvalidTournamentConfig
+    );
+    require!(
+        !config.payout_curve_bps.is_empty() && config.payout_curve_bps.len() <= config.leaderboard_size as usize,
+        CasinoError::InvalidTournamentConfig
+    );
+    let total_bps: u64 = config.payout_curve_bps.iter().map(|bps| *bps as u64).sum();
+    require!(total_bps == BPS_DENOMINATOR, CasinoError::InvalidTournamentConfig);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(index: u8) -> Pubkey {
+        Pubkey::new_from_array([index; 32])
+    }
+
+    #[test]
+    fn metrics_accumulate_or_keep_the_best() {
+        assert_eq!(score_game(TournamentMetric::NetProfit, 0, 100, 0).unwrap(), -100);
+        assert_eq!(score_game(TournamentMetric::NetProfit, -100, 100, 350).unwrap(), 150);
+
+        // 250 back on a 100 stake is 2.50x; a smaller multiplier keeps the best
+        assert_eq!(score_game(TournamentMetric::BiggestMultiplier, 0, 100, 250).unwrap(), 250);
+        assert_eq!(score_game(TournamentMetric::BiggestMultiplier, 250, 100, 120).unwrap(), 250);
+        assert_eq!(score_game(TournamentMetric::BiggestMultiplier, 250, 0, 5_000).unwrap(), 250);
+
+        assert_eq!(score_game(TournamentMetric::WageredVolume, 300, 100, 0).unwrap(), 400);
+    }
+
+    #[test]
+    fn leaderboard_stays_sorted_and_capped() {
+        let mut leaderboard = Vec::new();
+        update_leaderboard(&mut leaderboard, 3, player(1), 50);
+        update_leaderboard(&mut leaderboard, 3, player(2), 80);
+        update_leaderboard(&mut leaderboard, 3, player(3), 50);
+        update_leaderboard(&mut leaderboard, 3, player(4), 10);
+
+        let order: Vec<Pubkey> = leaderboard.iter().map(|entry| entry.user).collect();
+        assert_eq!(order, vec![player(2), player(1), player(3)]);
+
+        // A player whose score drops moves down, and off a full board below the last place
+        update_leaderboard(&mut leaderboard, 3, player(2), 20);
+        let order: Vec<Pubkey> = leaderboard.iter().map(|entry| entry.user).collect();
+        assert_eq!(order, vec![player(1), player(3), player(2)]);
+        update_leaderboard(&mut leaderboard, 3, player(4), 60);
+        let order: Vec<Pubkey> = leaderboard.iter().map(|entry| entry.user).collect();
+        assert_eq!(order, vec![player(4), player(1), player(3)]);
+    }
+
+    #[test]
+    fn registration_counts_until_the_tournament_ends() {
+        assert_eq!(running_tournament(Some(player(1)), 1_000, 999), Some(player(1)));
+        assert_eq!(running_tournament(Some(player(1)), 1_000, 1_000), None);
+        assert_eq!(running_tournament(None, 1_000, 0), None);
+    }
+
+    #[test]
+    fn prizes_follow_the_curve_and_use_the_whole_pool() {
+        let curve = [5_000, 3_000, 2_000];
+        assert_eq!(calculate_prizes(1_000, &curve, 5).unwrap(), vec![500, 300, 200]);
+
+        // Two finishers split the pool 5:3, the dust going to first place
+        assert_eq!(calculate_prizes(1_000, &curve, 2).unwrap(), vec![625, 375]);
+        assert_eq!(calculate_prizes(1_001, &curve, 2).unwrap(), vec![626, 375]);
+        assert!(calculate_prizes(1_000, &curve, 0).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use crate::errors::CasinoError;
+use crate::state::vip::*;
+use crate::utils::math::{mul_bps, BPS_DENOMINATOR};
+
+// VIP tiers.
+// Volume is kept per user both as a lifetime total and in VIP_ROLLING_DAYS daily buckets
+// (`daily_volume[day % VIP_ROLLING_DAYS]`), so the rolling-30-day total is exact to the day.
+// Tier numbers are 1-based positions in VipConfig.tiers; 0 means no tier.
+
+/// Clear the buckets of every day between `last_day` and `today` (exclusive/inclusive)
+pub fn roll_daily_volume(daily_volume: &mut [u64; VIP_ROLLING_DAYS], last_day: i64, today: i64) {
+    if today <= last_day {
+        return;
+    }
+    let stale_days = (today - last_day).min(VIP_ROLLING_DAYS as i64);
+    for offset in 0..stale_days {
+        let day = today - offset;
+        daily_volume[day.rem_euclid(VIP_ROLLING_DAYS as i64) as usize] = 0;
+    }
+}
+
+pub fn rolling_volume(daily_volume: &[u64; VIP_ROLLING_DAYS]) -> u64 {
+    daily_volume.iter().fold(0u64, |total, volume| total.saturating_add(*volume))
+}
+
+/// Highest tier whose volume threshold is met (0 = none)
+pub fn tier_for_volume(tiers: &[VipTier], volume: u64) -> u8 {
+    tiers.iter().take_while(|tier| volume >= tier.min_volume).count() as u8
+}
+
+/// Game max bet scaled for a tier
+pub fn vip_max_bet(game_max_bet: u64, tier: Option<&VipTier>) -> Result<u64> {
+    match tier {
+        Some(tier) => mul_bps(game_max_bet, tier.max_bet_bps as u64),
+        None => Ok(game_max_bet),
+    }
+}
+
+/// Rakeback on a settled stake: `rakeback_bps` of the expected house edge on it
+pub fn calculate_rakeback(wagered: u64, rtp_bps: u16, rakeback_bps: u16) -> Result<u64> {
+    let house_edge = mul_bps(wagered, BPS_DENOMINATOR.saturating_sub(rtp_bps as u64))?;
+    mul_bps(house_edge, rakeback_bps as u64)
+}
+
+pub fn validate_vip_tiers(tiers: &[VipTier]) -> Result<()> {
+    require!(tiers.len() <= MAX_VIP_TIERS, CasinoError::InvalidVipTiers);
+    require!(
+        tiers.windows(2).all(|pair| pair[1].min_volume > pair[0].min_volume),
+        CasinoError::InvalidVipTiers
+    );
+    require!(
+        tiers.iter().all(|tier| tier.rakeback_bps as u64 <= BPS_DENOMINATOR && tier.max_bet_bps as u64 >= BPS_DENOMINATOR),
+        CasinoError::InvalidVipTiers
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(min_volume: u64, rakeback_bps: u16, max_bet_bps: u32) -> VipTier {
+        VipTier { min_volume, rakeback_bps, max_bet_bps }
+    }
+
+    #[test]
+    fn rolling_window_drops_days_older_than_thirty() {
+        let mut daily_volume = [0u64; VIP_ROLLING_DAYS];
+        daily_volume[(100 % 30) as usize] = 500;
+        daily_volume[(110 % 30) as usize] = 300;
+
+        roll_daily_volume(&mut daily_volume, 110, 110);
+        assert_eq!(rolling_volume(&daily_volume), 800);
+
+        // Day 130 reuses day 100's bucket
+        roll_daily_volume(&mut daily_volume, 110, 130);
+        assert_eq!(rolling_volume(&daily_volume), 300);
+
+        roll_daily_volume(&mut daily_volume, 130, 500);
+        assert_eq!(rolling_volume(&daily_volume), 0);
+    }
+
+    #[test]
+    fn tiers_pick_the_highest_threshold_met() {
+        let tiers = vec![tier(1_000, 500, 10_000), tier(10_000, 1_000, 15_000), tier(100_000, 2_000, 30_000)];
+        validate_vip_tiers(&tiers).unwrap();
+
+        assert_eq!(tier_for_volume(&tiers, 999), 0);
+        assert_eq!(tier_for_volume(&tiers, 1_000), 1);
+        assert_eq!(tier_for_volume(&tiers, 99_999), 2);
+        assert_eq!(tier_for_volume(&tiers, u64::MAX), 3);
+
+        assert_eq!(vip_max_bet(2_000, None).unwrap(), 2_000);
+        assert_eq!(vip_max_bet(2_000, Some(&tiers[2])).unwrap(), 6_000);
+
+        assert!(validate_vip_tiers(&[tier(10, 0, 10_000), tier(10, 0, 10_000)]).is_err());
+        assert!(validate_vip_tiers(&[tier(10, 0, 9_999)]).is_err());
+    }
+
+    #[test]
+    fn rakeback_is_a_share_of_the_house_edge() {
+        // 4% edge on 100,000 is 4,000; 25% of that comes back
+        assert_eq!(calculate_rakeback(100_000, 9_600, 2_500).unwrap(), 1_000);
+        assert_eq!(calculate_rakeback(100_000, 9_600, 0).unwrap(), 0);
+        assert_eq!(calculate_rakeback(0, 9_600, 2_500).unwrap(), 0);
+    }
+}
@@ -1,14 +1,27 @@
 use anchor_lang::prelude::*;
+#[cfg(not(feature = "mock-vrf"))]
 use switchboard_on_demand::accounts::RandomnessAccountData;
-use anchor_lang::solana_program::hash::hash;
 
 // VRF integration with Switchboard On-Demand
 // Following Switchboard's two-phase pattern: Request -> Settle
+//
+// With the `mock-vrf` feature the randomness account is a program-owned MockRandomness
+// instead, written by test code (see instructions::mock_vrf). Everything else - the
+// seed slot check at request, the timing checks and the outcome derivation - is unchanged.
+
+/// Randomness account data the games commit to and settle from
+#[cfg(not(feature = "mock-vrf"))]
+pub type RandomnessData = RandomnessAccountData;
+
+#[cfg(feature = "mock-vrf")]
+pub type RandomnessData = MockRandomness;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum GamePhase {
     Requested,  // Randomness requested, waiting for reveal
     Settled,    // Game completed with revealed randomness
+    Refunded,   // Randomness never revealed, bet returned after timeout
+    Voided,     // Cancelled by the casino authority, bet returned
 }
 
 // VRF account for storing game state with randomness
@@ -16,44 +29,87 @@ pub enum GamePhase {
 #[derive(InitSpace)]
 pub struct VrfGameState {
     pub user: Pubkey,
+    pub nonce: u64,                  // UserStats.game_nonce at request time (PDA seed)
     pub game_type: crate::state::casino::GameType,
     pub bet_amount: u64,
+    pub max_payout: u64,             // Worst-case payout reserved in CasinoState liabilities
+    pub is_free_spin: bool,          // Stake funded by the house (loyalty free spin)
+    pub jackpot_contribution: u64,   // Stake slice paid into the progressive jackpot (slots only)
+    pub rtp_bps: u16,                // GameState RTP at request time, used to settle
     pub randomness_account: Pubkey,  // Reference to Switchboard randomness account
     pub commit_slot: u64,            // Slot when randomness was committed
     pub game_phase: GamePhase,
-    #[max_len(100)]
-    pub game_data: Vec<u8>,          // Game-specific data (reels, cards, etc.)
+    #[max_len(400)]
+    pub game_data: Vec<u8>,          // Bet inputs committed at request (see verify::decode_inputs)
     pub payout: u64,
     pub is_complete: bool,
     pub bump: u8,
 }
 
+impl VrfGameState {
+    /// Stake the player actually put up (a free spin is funded by the house)
+    pub fn wagered(&self) -> u64 {
+        if self.is_free_spin { 0 } else { self.bet_amount }
+    }
+}
 
-pub fn generate_mock_randomness() -> [u8; 32] {
-    // Combine current slot + some entropy from timestamp
-    let clock = Clock::get().unwrap();
-    let seed_bytes = clock.slot.to_le_bytes();
+// Stand-in for a Switchboard randomness account, written directly by test code
+#[cfg(feature = "mock-vrf")]
+#[account]
+#[derive(InitSpace)]
+pub struct MockRandomness {
+    pub authority: Pubkey,           // Signer allowed to rewrite it
+    pub mock_id: u64,
+    pub seed_slot: u64,              // Slot the randomness was committed at
+    pub reveal_slot: u64,            // Slot the value was revealed at (0 = not revealed yet)
+    pub value: [u8; 32],
+    pub strict_reveal: bool,         // Only readable in reveal_slot, like Switchboard
+    pub bump: u8,
+}
+
+#[cfg(feature = "mock-vrf")]
+impl MockRandomness {
+    /// Revealed value as of `clock_slot`, mirroring `RandomnessAccountData::get_value`
+    pub fn get_value(&self, clock_slot: u64) -> Result<[u8; 32]> {
+        require!(self.reveal_slot != 0, crate::errors::CasinoError::VrfRequestFailed);
 
-    // Hash it for pseudo-random output
-    let hash_result = hash(&seed_bytes);
-    hash_result.to_bytes()
+        if self.strict_reveal {
+            require!(clock_slot == self.reveal_slot, crate::errors::CasinoError::VrfRequestFailed);
+        } else {
+            require!(clock_slot >= self.reveal_slot, crate::errors::CasinoError::VrfRequestFailed);
+        }
+
+        Ok(self.value)
+    }
 }
 
 // VRF utility functions for Switchboard integration
 
 /// Parse and validate Switchboard randomness account data
+#[cfg(not(feature = "mock-vrf"))]
 pub fn parse_randomness_data(
     randomness_account_data: &AccountInfo,
-) -> Result<RandomnessAccountData> {
+) -> Result<RandomnessData> {
     let data = randomness_account_data.data.borrow();
     let parsed = RandomnessAccountData::parse(data)
         .map_err(|_| crate::errors::CasinoError::VrfRequestFailed)?;
     Ok(parsed.clone())
 }
 
+/// Parse a MockRandomness account (must be owned by this program)
+#[cfg(feature = "mock-vrf")]
+pub fn parse_randomness_data(
+    randomness_account_data: &AccountInfo,
+) -> Result<RandomnessData> {
+    require_keys_eq!(*randomness_account_data.owner, crate::ID, crate::errors::CasinoError::VrfRequestFailed);
+    let data = randomness_account_data.data.borrow();
+    MockRandomness::try_deserialize(&mut &data[..])
+        .map_err(|_| crate::errors::CasinoError::VrfRequestFailed.into())
+}
+
 /// Validate that randomness is ready to be revealed
 pub fn validate_randomness_timing(
-    randomness_data: &RandomnessAccountData,
+    randomness_data: &RandomnessData,
     expected_commit_slot: u64,
 ) -> Result<()> {
     let clock = Clock::get()?;
@@ -73,61 +129,42 @@ pub fn validate_randomness_timing(
     Ok(())
 }
 
-/// Get revealed random value from Switchboard
+/// Check that a pending request has outlived the reveal window
+pub fn validate_request_expired(commit_slot: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(
+        clock.slot > commit_slot.saturating_add(VRF_TIMEOUT_SLOTS),
+        crate::errors::CasinoError::GameNotExpired
+    );
+    
+    Ok(())
+}
+
+/// Check that the randomness committed at `commit_slot` was never revealed, so a refund cannot
+/// be claimed on an outcome the player has already seen. A randomness account re-committed
+/// since then no longer proves this; only the casino authority can void such a game.
+pub fn validate_randomness_unrevealed(
+    randomness_data: &RandomnessData,
+    commit_slot: u64,
+) -> Result<()> {
+    require!(
+        randomness_data.seed_slot == commit_slot && randomness_data.reveal_slot == 0,
+        crate::errors::CasinoError::RandomnessAlreadyRevealed
+    );
+    
+    Ok(())
+}
+
+/// Get revealed random value from Switchboard (or the mock account)
 pub fn get_revealed_randomness(
-    randomness_data: &RandomnessAccountData,
-) -> Result<Vec<u8>> {
+    randomness_data: &RandomnessData,
+) -> Result<[u8; 32]> {
     let clock = Clock::get()?;
     let random_bytes: [u8; 32] = randomness_data
         .get_value(clock.slot)
         .map_err(|_| crate::errors::CasinoError::VrfRequestFailed)?;
-    Ok(random_bytes.to_vec())
-}
-
-/// Generate game-specific random values from Switchboard randomness
-pub fn generate_game_randomness(
-    game_type: crate::state::casino::GameType,
-    randomness_bytes: &[u8],
-) -> Result<Vec<u8>> {
-    match game_type {
-        crate::state::casino::GameType::Slots => {
-            // Generate 3 random numbers for slots (0-9)
-            let mut reels = Vec::new();
-            for i in 0..3 {
-                let random_byte = randomness_bytes[i % randomness_bytes.len()];
-                reels.push(random_byte % 10);
-            }
-            Ok(reels)
-        },
-        crate::state::casino::GameType::Roulette => {
-            // Generate random number 0-36 for roulette
-            let random_byte = randomness_bytes[0];
-            let number = random_byte % 37;
-            Ok(vec![number])
-        },
-        crate::state::casino::GameType::Aviator => {
-            // Generate crash multiplier (1.0x - 100.0x)
-            let random_bytes: [u8; 4] = [
-                randomness_bytes[0],
-                randomness_bytes[1],
-                randomness_bytes[2],
-                randomness_bytes[3],
-            ];
-            let random_value = u32::from_le_bytes(random_bytes);
-            let multiplier = 1.0 + (random_value % 9900) as f64 / 100.0;
-            let multiplier_bytes = multiplier.to_le_bytes();
-            Ok(multiplier_bytes.to_vec())
-        },
-        crate::state::casino::GameType::Blackjack => {
-            // Generate multiple card values (1-13) for blackjack
-            let mut cards = Vec::new();
-            for i in 0..4 { // Generate 4 cards initially
-                let random_byte = randomness_bytes[i % randomness_bytes.len()];
-                cards.push((random_byte % 13) + 1);
-            }
-            Ok(cards)
-        },
-    }
+    Ok(random_bytes)
 }
 
 /// Transfer funds with optional seeds for PDA authority
@@ -154,3 +191,34 @@ pub fn transfer_funds<'a>(
 // VRF configuration constants
 pub const VRF_TIMEOUT_SLOTS: u64 = 150; // ~1 minute timeout for VRF requests
 pub const MAX_VRF_REQUESTS: u32 = 1000; // Maximum concurrent VRF requests
+
+#[cfg(all(test, feature = "mock-vrf"))]
+mod tests {
+    use super::*;
+
+    fn mock(reveal_slot: u64, strict_reveal: bool) -> MockRandomness {
+        MockRandomness {
+            authority: Pubkey::default(),
+            mock_id: 0,
+            seed_slot: 10,
+            reveal_slot,
+            value: [7; 32],
+            strict_reveal,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn mock_value_is_hidden_until_revealed() {
+        assert!(mock(0, false).get_value(50).is_err());
+        assert!(mock(12, false).get_value(11).is_err());
+        assert_eq!(mock(12, false).get_value(12).unwrap(), [7; 32]);
+        assert_eq!(mock(12, false).get_value(40).unwrap(), [7; 32]);
+    }
+
+    #[test]
+    fn strict_mock_value_is_only_readable_in_the_reveal_slot() {
+        assert_eq!(mock(12, true).get_value(12).unwrap(), [7; 32]);
+        assert!(mock(12, true).get_value(13).is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use anchor_lang::prelude::*;
+use crate::errors::CasinoError;
+use crate::state::casino::*;
+use crate::state::history::StoredProof;
+use crate::replay::{self, ReplayError};
+use crate::utils::math::checked_add;
+use crate::utils::roulette::replay_wager;
+use crate::utils::slots::replay_machine;
+
+// Provably-fair verification.
+// A GameRecord holds everything an outcome is derived from: the game account the draws are
+// keyed to, the bet inputs and the revealed Switchboard randomness. Settlement computes every
+// game through `verify_outcome`, emits the record and keeps it in the player's GameHistory.
+// The draws and payouts themselves live in `replay` (core, alloc and sha2 only), so an
+// off-chain verifier links that module alone; this file maps program accounts onto it.
+
+impl From<ReplayError> for Error {
+    fn from(error: ReplayError) -> Self {
+        match error {
+            ReplayError::InvalidInputs => CasinoError::InvalidGameState.into(),
+            ReplayError::MathOverflow => CasinoError::MathOverflow.into(),
+        }
+    }
+}
+
+/// Bet inputs committed at request time
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum GameInputs {
+    Slots { machine: Pubkey, active_paylines: u32 },
+    Roulette { bets: Vec<RouletteBet> },
+    Aviator { target_multiplier: u64, rtp_bps: u16 },
+    BlackjackDraw { cards: u8 },   // Cards drawn in one blackjack settle round
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GameRecord {
+    pub game: Pubkey,              // VrfGameState or BlackjackGame account (keys every draw)
+    pub nonce: u64,
+    pub bet_amount: u64,
+    pub inputs: GameInputs,
+    pub randomness: [u8; 32],      // Revealed Switchboard value the game was settled with
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Slots { stop_positions: Vec<u8>, window: Vec<u8>, payout: u64 },
+    Roulette { winning_number: u8, results: Vec<RouletteBetResult>, payout: u64 },
+    Aviator { crash_multiplier: u64, payout: u64 },
+    BlackjackDraw { cards: Vec<u8> },
+}
+
+/// Decode the inputs a VRF game stored in `VrfGameState.game_data` at request time
+pub fn decode_inputs(game_type: &GameType, game_data: &[u8], rtp_bps: u16) -> Result<GameInputs> {
+    match game_type {
+        GameType::Slots => {
+            require!(game_data.len() == 36, CasinoError::InvalidGameState);
+            let machine = Pubkey::try_from(&game_data[..32]).map_err(|_| CasinoError::InvalidGameState)?;
+            let active_paylines = u32::from_le_bytes(
+                game_data[32..36].try_into().map_err(|_| CasinoError::InvalidGameState)?
+            );
+            Ok(GameInputs::Slots { machine, active_paylines })
+        },
+        GameType::Roulette => {
+            let bets = Vec::<RouletteBet>::try_from_slice(game_data).map_err(|_| CasinoError::InvalidGameState)?;
+            Ok(GameInputs::Roulette { bets })
+        },
+        GameType::Aviator => {
+            require!(game_data.len() == 8, CasinoError::InvalidGameState);
+            let target_multiplier = u64::from_le_bytes(
+                game_data.try_into().map_err(|_| CasinoError::InvalidGameState)?
+            );
+            Ok(GameInputs::Aviator { target_multiplier, rtp_bps })
+        },
+        GameType::Blackjack => {
+            // Stored blackjack proofs keep the number of cards drawn in the round
+            require!(game_data.len() == 1, CasinoError::InvalidGameState);
+            Ok(GameInputs::BlackjackDraw { cards: game_data[0] })
+        },
+    }
+}
+
+/// Proof kept in a player's GameHistory for a settled record (inverse of `stored_record`)
+pub fn stored_proof(record: &GameRecord, payout: u64, slot: u64) -> Result<StoredProof> {
+    let (game_type, game_data, rtp_bps) = match &record.inputs {
+        GameInputs::Slots { machine, active_paylines } => {
+            (GameType::Slots, [machine.to_bytes().as_ref(), &active_paylines.to_le_bytes()].concat(), 0)
+        },
+        GameInputs::Roulette { bets } => (GameType::Roulette, bets.try_to_vec()?, 0),
+        GameInputs::Aviator { target_multiplier, rtp_bps } => {
+            (GameType::Aviator, target_multiplier.to_le_bytes().to_vec(), *rtp_bps)
+        },
+        GameInputs::BlackjackDraw { cards } => (GameType::Blackjack, vec![*cards], 0),
+    };
+
+    Ok(StoredProof {
+        game: record.game,
+        nonce: record.nonce,
+        game_type,
+        bet_amount: record.bet_amount,
+        rtp_bps,
+        game_data,
+        randomness: record.randomness,
+        payout,
+        slot,
+    })
+}
+
+/// Rebuild the record of a proof kept in a player's GameHistory
+pub fn stored_record(proof: &StoredProof) -> Result<GameRecord> {
+    Ok(GameRecord {
+        game: proof.game,
+        nonce: proof.nonce,
+        bet_amount: proof.bet_amount,
+        inputs: decode_inputs(&proof.game_type, &proof.game_data, proof.rtp_bps)?,
+        randomness: proof.randomness,
+    })
+}
+
+/// Recompute a game's outcome from its record and the revealed randomness.
+/// Slots spins also need the (immutable) SlotsMachineConfig named in the record.
+pub fn verify_outcome(record: &GameRecord, randomness: &[u8; 32], machine: Option<&SlotsMachineConfig>) -> Result<Outcome> {
+    let game = record.game.to_bytes();
+    match &record.inputs {
+        GameInputs::Slots { active_paylines, .. } => {
+            let machine = machine.ok_or(CasinoError::InvalidGameState)?;
+            require!(*active_paylines != 0, CasinoError::InvalidSlotsPayline);
+
+            // The house edge is built into the machine's strips and paytable
+            let spin = replay::slots_spin(
+                &replay_machine(&machine.reel_strips, &machine.paylines, &machine.paytable),
+                game,
+                randomness,
+                *active_paylines,
+                record.bet_amount
+            )?;
+
+            Ok(Outcome::Slots {
+                stop_positions: spin.stop_positions.iter().map(|position| *position as u8).collect(),
+                window: spin.window,
+                payout: spin.payout,
+            })
+        },
+        GameInputs::Roulette { bets } => {
+            // One winning number (0-36) settles every bet at true odds; the zero is the house edge
+            let wagers = bets.iter().map(replay_wager).collect::<Result<Vec<_>>>()?;
+            let (winning_number, payouts) = replay::roulette_spin(&wagers, game, randomness)?;
+
+            let mut results = Vec::with_capacity(bets.len());
+            let mut payout = 0u64;
+            for (bet, bet_payout) in bets.iter().zip(payouts) {
+                payout = checked_add(payout, bet_payout)?;
+                results.push(RouletteBetResult { bet: bet.clone(), payout: bet_payout });
+            }
+
+            Ok(Outcome::Roulette { winning_number, results, payout })
+        },
+        GameInputs::Aviator { target_multiplier, rtp_bps } => {
+            // Crash point carries the house edge, so the payout is not scaled by RTP again
+            let (crash_multiplier, payout) =
+                replay::aviator_flight(*target_multiplier, *rtp_bps, game, randomness, record.bet_amount)?;
+
+            Ok(Outcome::Aviator { crash_multiplier, payout })
+        },
+        GameInputs::BlackjackDraw { cards } => {
+            Ok(Outcome::BlackjackDraw { cards: replay::blackjack_cards(game, randomness, *cards) })
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hashv;
+    use crate::replay::{slots_payout, spin_window, RandomnessStream, DOMAIN_ROULETTE_SPIN};
+    use crate::utils::calculate_roulette_payout;
+
+    fn record(inputs: GameInputs, bet_amount: u64) -> GameRecord {
+        GameRecord {
+            game: Pubkey::new_from_array(hashv(&[b"verify game"]).to_bytes()),
+            nonce: 7,
+            bet_amount,
+            inputs,
+            randomness: hashv(&[b"verify reveal"]).to_bytes(),
+        }
+    }
+
+    fn machine() -> SlotsMachineConfig {
+        let strip = ReelStrip {
+            stops: (0..4u8).map(|symbol| ReelStop { symbol, weight: symbol as u16 + 1 }).collect(),
+        };
+        SlotsMachineConfig {
+            machine_id: 1,
+            reel_count: 3,
+            reel_strips: vec![strip; 3],
+            paylines: vec![Payline { rows: vec![1, 1, 1] }, Payline { rows: vec![0, 0, 0] }],
+            paytable: vec![SlotsPayout { symbol: 3, count: 3, multiplier: 500 }],
+            rtp_bps: 0,
+            is_active: true,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn outcomes_are_deterministic_per_record() {
+        let roulette = record(GameInputs::Roulette {
+            bets: vec![RouletteBet { bet_type: RouletteBetType::Red, numbers: vec![], amount: 1_000 }],
+        }, 1_000);
+        let first = verify_outcome(&roulette, &roulette.randomness, None).unwrap();
+        assert_eq!(first, verify_outcome(&roulette, &roulette.randomness, None).unwrap());
+
+        // A different reveal or game account gives an independent draw
+        let other_reveal = hashv(&[b"other reveal"]).to_bytes();
+        let draws: Vec<Outcome> = (0..16u8)
+            .map(|seed| {
+                let mut other = roulette.clone();
+                other.game = Pubkey::new_from_array(hashv(&[&[seed], &other_reveal]).to_bytes());
+                verify_outcome(&other, &other_reveal, None).unwrap()
+            })
+            .collect();
+        assert!(draws.iter().any(|outcome| *outcome != first));
+    }
+
+    #[test]
+    fn roulette_outcome_matches_the_spin_stream() {
+        let bets = vec![
+            RouletteBet { bet_type: RouletteBetType::Straight, numbers: vec![17], amount: 100 },
+            RouletteBet { bet_type: RouletteBetType::Even, numbers: vec![], amount: 300 },
+        ];
+        let spin = record(GameInputs::Roulette { bets: bets.clone() }, 400);
+        let expected_number = RandomnessStream::new(DOMAIN_ROULETTE_SPIN, spin.game.to_bytes(), &spin.randomness).uniform(0, 37) as u8;
+
+        let Outcome::Roulette { winning_number, results, payout } = verify_outcome(&spin, &spin.randomness, None).unwrap() else {
+            panic!("expected a roulette outcome");
+        };
+        assert_eq!(winning_number, expected_number);
+        assert_eq!(results.len(), 2);
+        assert_eq!(payout, results.iter().map(|result| result.payout).sum::<u64>());
+        for result in results {
+            let gross = calculate_roulette_payout(result.bet.bet_type.clone(), &result.bet.numbers, result.bet.amount, winning_number).unwrap();
+            assert_eq!(result.payout, gross);
+        }
+    }
+
+    #[test]
+    fn slots_outcome_requires_the_machine_and_matches_its_window() {
+        let spin = record(GameInputs::Slots { machine: Pubkey::default(), active_paylines: 0b11 }, 200);
+        assert!(verify_outcome(&spin, &spin.randomness, None).is_err());
+
+        let machine = machine();
+        let Outcome::Slots { stop_positions, window, payout } = verify_outcome(&spin, &spin.randomness, Some(&machine)).unwrap() else {
+            panic!("expected a slots outcome");
+        };
+        let replayed = replay_machine(&machine.reel_strips, &machine.paylines, &machine.paytable);
+        let positions: Vec<usize> = stop_positions.iter().map(|position| *position as usize).collect();
+        assert_eq!(window, spin_window(&replayed.reels, &positions));
+        assert_eq!(payout, slots_payout(&replayed, &window, 0b11, 100).unwrap());
+    }
+
+    #[test]
+    fn aviator_and_blackjack_outcomes_stay_in_range() {
+        let flight = record(GameInputs::Aviator { target_multiplier: 200, rtp_bps: 9_600 }, 1_000);
+        let Outcome::Aviator { crash_multiplier, payout } = verify_outcome(&flight, &flight.randomness, None).unwrap() else {
+            panic!("expected an aviator outcome");
+        };
+        assert!(crash_multiplier >= AVIATOR_MULTIPLIER_SCALE);
+        assert_eq!(payout, if crash_multiplier >= 200 { 2_000 } else { 0 });
+
+        let round = record(GameInputs::BlackjackDraw { cards: 6 }, 0);
+        let Outcome::BlackjackDraw { cards } = verify_outcome(&round, &round.randomness, None).unwrap() else {
+            panic!("expected blackjack cards");
+        };
+        assert_eq!(cards.len(), 6);
+        assert!(cards.iter().all(|card| (1..=13).contains(card)));
+    }
+
+    #[test]
+    fn stored_game_data_round_trips() {
+        let machine = Pubkey::new_unique();
+        let game_data = [machine.to_bytes().as_ref(), &5u32.to_le_bytes()].concat();
+        assert_eq!(
+            decode_inputs(&GameType::Slots, &game_data, 0).unwrap(),
+            GameInputs::Slots { machine, active_paylines: 5 }
+        );
+        assert_eq!(
+            decode_inputs(&GameType::Aviator, &250u64.to_le_bytes(), 9_600).unwrap(),
+            GameInputs::Aviator { target_multiplier: 250, rtp_bps: 9_600 }
+        );
+        assert!(decode_inputs(&GameType::Aviator, &[0u8; 7], 9_600).is_err());
+        assert_eq!(
+            decode_inputs(&GameType::Blackjack, &[4], 0).unwrap(),
+            GameInputs::BlackjackDraw { cards: 4 }
+        );
+        assert!(decode_inputs(&GameType::Blackjack, &[], 0).is_err());
+    }
+
+    #[test]
+    fn stored_proofs_replay_to_the_settled_outcome() {
+        let flight = record(GameInputs::Aviator { target_multiplier: 150, rtp_bps: 9_600 }, 1_000);
+        let Outcome::Aviator { payout, .. } = verify_outcome(&flight, &flight.randomness, None).unwrap() else {
+            panic!("expected an aviator outcome");
+        };
+        let proof = stored_proof(&flight, payout, 1).unwrap();
+        assert_eq!(proof.game_type, GameType::Aviator);
+        assert_eq!(proof.game_data, 150u64.to_le_bytes().to_vec());
+
+        let stored = stored_record(&proof).unwrap();
+        assert_eq!(stored, flight);
+        assert_eq!(verify_outcome(&stored, &proof.randomness, None).unwrap(), verify_outcome(&flight, &flight.randomness, None).unwrap());
+
+        // Every game kind survives the round trip
+        let records = [
+            record(GameInputs::Slots { machine: Pubkey::new_unique(), active_paylines: 0b101 }, 300),
+            record(GameInputs::Roulette {
+                bets: vec![RouletteBet { bet_type: RouletteBetType::Straight, numbers: vec![17], amount: 100 }],
+            }, 100),
+            record(GameInputs::BlackjackDraw { cards: 5 }, 0),
+        ];
+        for original in records {
+            assert_eq!(stored_record(&stored_proof(&original, 0, 1).unwrap()).unwrap(), original);
+        }
+    }
+}